@@ -0,0 +1,619 @@
+//! Decoding of function invocations and events using the ABI of the classes. Selectors are
+//! resolved to function and event names, calldata, results and event data are decoded into the
+//! types of the ABI. Both the Sierra ABI of Cairo 1 and the ABI of Cairo 0 are supported.
+
+use std::collections::HashMap;
+
+use cairo_lang_utils::byte_array::BYTES_IN_WORD;
+use ethers::types::U256;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use starknet_rs_core::utils::get_selector_from_name;
+use starknet_rs_ff::FieldElement;
+use starknet_types::contract_class::{Cairo0ContractClass, ContractClass};
+use starknet_types::felt::{ClassHash, Felt};
+
+/// Felt types which are shown as hex values
+const CAIRO_1_FELT_TYPES: [&str; 5] = [
+    "core::felt252",
+    "core::starknet::contract_address::ContractAddress",
+    "core::starknet::class_hash::ClassHash",
+    "core::starknet::eth_address::EthAddress",
+    "core::starknet::storage_access::StorageAddress",
+];
+
+const CAIRO_1_UNSIGNED_TYPES: [&str; 6] = [
+    "core::integer::u8",
+    "core::integer::u16",
+    "core::integer::u32",
+    "core::integer::u64",
+    "core::integer::u128",
+    "core::integer::usize",
+];
+
+const CAIRO_1_SIGNED_TYPES: [&str; 5] = [
+    "core::integer::i8",
+    "core::integer::i16",
+    "core::integer::i32",
+    "core::integer::i64",
+    "core::integer::i128",
+];
+
+/// An entry of the ABI. The fields are a superset of the Cairo 0 and the Cairo 1 formats.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AbiEntry {
+    Function(FunctionAbi),
+    Constructor(FunctionAbi),
+    L1Handler(FunctionAbi),
+    Struct {
+        name: String,
+        members: Vec<Member>,
+    },
+    Enum {
+        name: String,
+        variants: Vec<Member>,
+    },
+    Event(EventAbi),
+    Interface {
+        items: Vec<AbiEntry>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct FunctionAbi {
+    name: String,
+    inputs: Vec<Member>,
+    #[serde(default)]
+    outputs: Vec<Member>,
+}
+
+/// A function input or output, a struct member, an enum variant or an event member
+#[derive(Deserialize)]
+struct Member {
+    /// Cairo 1 outputs are not named
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+    /// The kind of a Cairo 1 event member: `key`, `data`, `nested` or `flat`
+    #[serde(default)]
+    kind: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EventAbi {
+    name: String,
+    /// `struct` or `enum` for Cairo 1 events, none for Cairo 0 events
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    members: Vec<Member>,
+    #[serde(default)]
+    variants: Vec<Member>,
+    /// The data of a Cairo 0 event
+    #[serde(default)]
+    data: Vec<Member>,
+}
+
+pub struct AbiDecoder {
+    is_cairo_0: bool,
+    functions: HashMap<FieldElement, FunctionAbi>,
+    structs: HashMap<String, Vec<Member>>,
+    enums: HashMap<String, Vec<Member>>,
+    events: HashMap<String, EventAbi>,
+}
+
+impl AbiDecoder {
+    /// Returns `None` if the class has no ABI or the ABI can't be parsed
+    pub fn new(contract_class: &ContractClass) -> Option<Self> {
+        let (abi, is_cairo_0) = match contract_class {
+            ContractClass::Cairo0(Cairo0ContractClass::RawJson(json)) => {
+                (json.inner.get("abi")?.clone(), true)
+            }
+            ContractClass::Cairo0(Cairo0ContractClass::Rpc(class)) => {
+                (serde_json::to_value(&class.abi).ok()?, true)
+            }
+            ContractClass::Cairo1(class) => {
+                (serde_json::to_value(class.abi.as_ref()?).ok()?, false)
+            }
+        };
+        let entries: Vec<AbiEntry> = serde_json::from_value(abi).ok()?;
+
+        let mut decoder = Self {
+            is_cairo_0,
+            functions: HashMap::new(),
+            structs: HashMap::new(),
+            enums: HashMap::new(),
+            events: HashMap::new(),
+        };
+        decoder.add_entries(entries);
+
+        Some(decoder)
+    }
+
+    fn add_entries(&mut self, entries: Vec<AbiEntry>) {
+        for entry in entries {
+            match entry {
+                AbiEntry::Function(function)
+                | AbiEntry::Constructor(function)
+                | AbiEntry::L1Handler(function) => {
+                    if let Ok(selector) = get_selector_from_name(&function.name) {
+                        self.functions.insert(selector, function);
+                    }
+                }
+                AbiEntry::Struct { name, members } => {
+                    self.structs.insert(name, members);
+                }
+                AbiEntry::Enum { name, variants } => {
+                    self.enums.insert(name, variants);
+                }
+                AbiEntry::Event(event) => {
+                    self.events.insert(event.name.clone(), event);
+                }
+                AbiEntry::Interface { items } => self.add_entries(items),
+                AbiEntry::Other => {}
+            }
+        }
+    }
+
+    /// Returns `None` if the selector is not in the ABI
+    pub fn function_name(&self, selector: FieldElement) -> Option<&str> {
+        self.functions.get(&selector).map(|function| function.name.as_str())
+    }
+
+    /// Resolves the name of the function and decodes its calldata and result. The calldata and
+    /// the result are `null` if they don't match the ABI. Returns `None` if the selector is not
+    /// in the ABI.
+    pub fn decode_function(
+        &self,
+        selector: FieldElement,
+        calldata: &[FieldElement],
+        result: &[FieldElement],
+    ) -> Option<Value> {
+        let function = self.functions.get(&selector)?;
+
+        let calldata = self
+            .decode_all(&function.inputs, calldata)
+            .map(|members| Value::Object(members.into_iter().collect::<Map<String, Value>>()));
+        let result = self
+            .decode_all(&function.outputs, result)
+            .map(|members| Value::Array(members.into_iter().map(|(_, value)| value).collect()));
+
+        Some(json!({ "function": function.name, "calldata": calldata, "result": result }))
+    }
+
+    /// Resolves the name of the event from its first key and decodes its keys and data. Returns
+    /// `None` if the event is not in the ABI or doesn't match it.
+    pub fn decode_event(&self, keys: &[FieldElement], data: &[FieldElement]) -> Option<Value> {
+        let (name, fields) = if self.is_cairo_0 {
+            let (selector, []) = keys.split_first()? else {
+                return None;
+            };
+            let event = self.events.values().find(|event| {
+                get_selector_from_name(&event.name).map_or(false, |name| name == *selector)
+            })?;
+            (event.name.clone(), self.decode_all(&event.data, data)?)
+        } else {
+            // the event of the contract is the enum which is not a variant of any other event
+            self.events
+                .values()
+                .filter(|event| event.kind.as_deref() == Some("enum"))
+                .filter(|event| {
+                    !self
+                        .events
+                        .values()
+                        .any(|other| other.variants.iter().any(|variant| variant.ty == event.name))
+                })
+                .find_map(|event| {
+                    let mut keys = keys.iter().copied();
+                    let mut data = data.iter().copied();
+                    let decoded = self.decode_enum_event(event, &mut keys, &mut data)?;
+                    (keys.next().is_none() && data.next().is_none()).then_some(decoded)
+                })?
+        };
+
+        Some(json!({ "name": name, "fields": Value::Object(fields.into_iter().collect()) }))
+    }
+
+    /// Decodes a Cairo 1 enum event, whose variant is selected by the next key
+    fn decode_enum_event(
+        &self,
+        event: &EventAbi,
+        keys: &mut impl Iterator<Item = FieldElement>,
+        data: &mut impl Iterator<Item = FieldElement>,
+    ) -> Option<(String, Vec<(String, Value)>)> {
+        let selector = keys.next()?;
+        let variant_event = self.find_event_variant(event, selector)?;
+
+        match variant_event.kind.as_deref() {
+            Some("struct") => {
+                let mut fields = vec![];
+                for member in &variant_event.members {
+                    let value = match member.kind.as_deref() {
+                        Some("key") => self.decode_type(&member.ty, keys)?,
+                        Some("data") => self.decode_type(&member.ty, data)?,
+                        _ => return None,
+                    };
+                    fields.push((member.name.clone(), value));
+                }
+                Some((variant_event.name.clone(), fields))
+            }
+            Some("enum") => self.decode_enum_event(variant_event, keys, data),
+            _ => None,
+        }
+    }
+
+    /// Finds the event of the variant with the name of the selector. Flat variants don't have a
+    /// selector of their own, so the variants of their events are searched.
+    fn find_event_variant(&self, event: &EventAbi, selector: FieldElement) -> Option<&EventAbi> {
+        event.variants.iter().find_map(|variant| {
+            let variant_event = self.events.get(&variant.ty)?;
+            match variant.kind.as_deref() {
+                Some("flat") => self.find_event_variant(variant_event, selector),
+                _ => (get_selector_from_name(&variant.name).ok()? == selector)
+                    .then_some(variant_event),
+            }
+        })
+    }
+
+    /// Decodes the members from all of the felts. Returns `None` if they don't match.
+    fn decode_all(
+        &self,
+        members: &[Member],
+        felts: &[FieldElement],
+    ) -> Option<Vec<(String, Value)>> {
+        let mut felts = felts.iter().copied();
+        let decoded = self.decode_members(members, &mut felts)?;
+        felts.next().is_none().then_some(decoded)
+    }
+
+    fn decode_members(
+        &self,
+        members: &[Member],
+        felts: &mut impl Iterator<Item = FieldElement>,
+    ) -> Option<Vec<(String, Value)>> {
+        let mut decoded = vec![];
+        // a Cairo 0 array is preceded by its length
+        let mut last_felt: Option<FieldElement> = None;
+        for member in members {
+            let value = match member.ty.strip_suffix('*') {
+                Some(item_type) if self.is_cairo_0 => {
+                    let length: u64 = last_felt?.try_into().ok()?;
+                    let items = (0..length)
+                        .map(|_| self.decode_type(item_type, felts))
+                        .collect::<Option<Vec<_>>>()?;
+                    Value::Array(items)
+                }
+                _ if self.is_cairo_0 && member.ty == "felt" => {
+                    let felt = felts.next()?;
+                    last_felt = Some(felt);
+                    to_hex_value(felt)
+                }
+                _ => self.decode_type(&member.ty, felts)?,
+            };
+            decoded.push((member.name.clone(), value));
+        }
+
+        Some(decoded)
+    }
+
+    fn decode_type(
+        &self,
+        ty: &str,
+        felts: &mut impl Iterator<Item = FieldElement>,
+    ) -> Option<Value> {
+        if ty == "felt" || CAIRO_1_FELT_TYPES.contains(&ty) {
+            return felts.next().map(to_hex_value);
+        }
+        if CAIRO_1_UNSIGNED_TYPES.contains(&ty) {
+            return felts.next().map(|felt| Value::String(felt.to_string()));
+        }
+        if CAIRO_1_SIGNED_TYPES.contains(&ty) {
+            let felt = felts.next()?;
+            // negative values are represented as the field prime minus the absolute value
+            let decimal = if felt > FieldElement::MAX.floor_div(FieldElement::TWO) {
+                format!("-{}", FieldElement::ZERO - felt)
+            } else {
+                felt.to_string()
+            };
+            return Some(Value::String(decimal));
+        }
+
+        match ty {
+            "()" => return Some(Value::Null),
+            "core::bool" => return Some(Value::Bool(felts.next()? != FieldElement::ZERO)),
+            "core::integer::u256" => {
+                let low = u128::try_from(felts.next()?).ok()?;
+                let high = u128::try_from(felts.next()?).ok()?;
+                let value = (U256::from(high) << 128) | U256::from(low);
+                return Some(Value::String(value.to_string()));
+            }
+            "core::byte_array::ByteArray" => return decode_byte_array(felts).map(Value::String),
+            _ => {}
+        }
+
+        if let Some(item_type) = ["core::array::Array::<", "core::array::Span::<"]
+            .iter()
+            .find_map(|prefix| ty.strip_prefix(prefix)?.strip_suffix('>'))
+        {
+            let length: u64 = felts.next()?.try_into().ok()?;
+            let items = (0..length)
+                .map(|_| self.decode_type(item_type, felts))
+                .collect::<Option<Vec<_>>>()?;
+            return Some(Value::Array(items));
+        }
+
+        if let Some(tuple) = ty.strip_prefix('(').and_then(|ty| ty.strip_suffix(')')) {
+            let items = split_tuple_types(tuple)
+                .into_iter()
+                .map(|item_type| self.decode_type(item_type, felts))
+                .collect::<Option<Vec<_>>>()?;
+            return Some(Value::Array(items));
+        }
+
+        if let Some(members) = self.structs.get(ty) {
+            let decoded = self.decode_members(members, felts)?;
+            return Some(Value::Object(decoded.into_iter().collect()));
+        }
+
+        if let Some(variants) = self.enums.get(ty) {
+            let index: u64 = felts.next()?.try_into().ok()?;
+            let variant = variants.get(usize::try_from(index).ok()?)?;
+            return match self.decode_type(&variant.ty, felts)? {
+                Value::Null => Some(Value::String(variant.name.clone())),
+                value => Some(json!({ &variant.name: value })),
+            };
+        }
+
+        None
+    }
+}
+
+/// Adds the decoded function and events to each function invocation in the serialized trace.
+/// Invocations of classes without a usable ABI are left as they are.
+pub fn decode_trace(trace: &mut Value, contract_classes: &HashMap<ClassHash, ContractClass>) {
+    let mut decoders = HashMap::new();
+    decode_invocations(trace, contract_classes, &mut decoders);
+}
+
+fn decode_invocations(
+    value: &mut Value,
+    contract_classes: &HashMap<ClassHash, ContractClass>,
+    decoders: &mut HashMap<ClassHash, Option<AbiDecoder>>,
+) {
+    match value {
+        Value::Object(object) => {
+            if let Some(decoder) = get_invocation_class_hash(object).and_then(|class_hash| {
+                decoders
+                    .entry(class_hash)
+                    .or_insert_with(|| contract_classes.get(&class_hash).and_then(AbiDecoder::new))
+                    .as_ref()
+            }) {
+                decode_invocation(object, decoder);
+            }
+
+            for nested in object.values_mut() {
+                decode_invocations(nested, contract_classes, decoders);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                decode_invocations(item, contract_classes, decoders);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn get_invocation_class_hash(object: &Map<String, Value>) -> Option<ClassHash> {
+    if !object.contains_key("entry_point_selector") {
+        return None;
+    }
+
+    Felt::from_prefixed_hex_str(object.get("class_hash")?.as_str()?).ok()
+}
+
+fn decode_invocation(invocation: &mut Map<String, Value>, decoder: &AbiDecoder) {
+    let felt_of = |value: &Value| FieldElement::from_hex_be(value.as_str()?).ok();
+    let felts_of = |value: Option<&Value>| -> Option<Vec<FieldElement>> {
+        value?.as_array()?.iter().map(felt_of).collect()
+    };
+
+    if let (Some(selector), Some(calldata), Some(result)) = (
+        invocation.get("entry_point_selector").and_then(felt_of),
+        felts_of(invocation.get("calldata")),
+        felts_of(invocation.get("result")),
+    ) {
+        if let Some(decoded) = decoder.decode_function(selector, &calldata, &result) {
+            invocation.insert("decoded".to_string(), decoded);
+        }
+    }
+
+    if let Some(Value::Array(events)) = invocation.get_mut("events") {
+        for event in events.iter_mut().filter_map(Value::as_object_mut) {
+            let decoded = felts_of(event.get("keys"))
+                .zip(felts_of(event.get("data")))
+                .and_then(|(keys, data)| decoder.decode_event(&keys, &data));
+            if let Some(decoded) = decoded {
+                event.insert("decoded".to_string(), decoded);
+            }
+        }
+    }
+}
+
+/// Splits the types of a tuple on the commas which are not nested in generics or tuples
+fn split_tuple_types(tuple: &str) -> Vec<&str> {
+    let mut types = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, character) in tuple.char_indices() {
+        match character {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                types.push(tuple[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = tuple[start..].trim();
+    if !last.is_empty() {
+        types.push(last);
+    }
+
+    types
+}
+
+/// Decodes a serialized `ByteArray`: the number of full words, the full words, the pending word
+/// and its length
+fn decode_byte_array(felts: &mut impl Iterator<Item = FieldElement>) -> Option<String> {
+    let full_words: u64 = felts.next()?.try_into().ok()?;
+
+    let mut bytes = vec![];
+    for _ in 0..full_words {
+        bytes.extend_from_slice(&felts.next()?.to_bytes_be()[32 - BYTES_IN_WORD..]);
+    }
+    let pending_word = felts.next()?.to_bytes_be();
+    let pending_length = usize::from(u8::try_from(felts.next()?).ok()?);
+    if pending_length >= BYTES_IN_WORD {
+        return None;
+    }
+    bytes.extend_from_slice(&pending_word[32 - pending_length..]);
+
+    String::from_utf8(bytes).ok()
+}
+
+fn to_hex_value(felt: FieldElement) -> Value {
+    Value::String(format!("{felt:#x}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use cairo_lang_starknet::contract_class::ContractClass as SierraContractClass;
+    use serde_json::json;
+    use starknet_rs_core::utils::{cairo_short_string_to_felt, get_selector_from_name};
+    use starknet_rs_ff::FieldElement;
+    use starknet_types::contract_class::ContractClass;
+
+    use super::AbiDecoder;
+    use crate::constants::CAIRO_1_ERC20_CONTRACT_PATH;
+
+    fn erc20_decoder() -> AbiDecoder {
+        let json_str = std::fs::read_to_string(CAIRO_1_ERC20_CONTRACT_PATH).unwrap();
+        let sierra: SierraContractClass = serde_json::from_str(&json_str).unwrap();
+        AbiDecoder::new(&ContractClass::Cairo1(sierra)).unwrap()
+    }
+
+    #[test]
+    fn function_of_interface_is_decoded() {
+        let decoded = erc20_decoder()
+            .decode_function(
+                get_selector_from_name("transfer").unwrap(),
+                &[FieldElement::from(0x123u32), FieldElement::from(5u8), FieldElement::ONE],
+                &[FieldElement::ONE],
+            )
+            .unwrap();
+
+        assert_eq!(
+            decoded,
+            json!({
+                "function": "transfer",
+                "calldata": {
+                    "recipient": "0x123",
+                    "amount": "340282366920938463463374607431768211461"
+                },
+                "result": [true]
+            })
+        );
+    }
+
+    #[test]
+    fn calldata_not_matching_abi_is_not_decoded() {
+        let decoded = erc20_decoder()
+            .decode_function(
+                get_selector_from_name("transfer").unwrap(),
+                &[FieldElement::from(0x123u32)],
+                &[],
+            )
+            .unwrap();
+
+        assert_eq!(decoded, json!({ "function": "transfer", "calldata": null, "result": null }));
+        assert!(erc20_decoder().decode_function(FieldElement::ONE, &[], &[]).is_none());
+    }
+
+    #[test]
+    fn event_of_flat_component_is_decoded() {
+        let decoded = erc20_decoder()
+            .decode_event(
+                &[
+                    get_selector_from_name("Transfer").unwrap(),
+                    FieldElement::ONE,
+                    FieldElement::TWO,
+                ],
+                &[FieldElement::from(10u8), FieldElement::ZERO],
+            )
+            .unwrap();
+
+        assert_eq!(
+            decoded,
+            json!({
+                "name": "openzeppelin::token::erc20::erc20::ERC20Component::Transfer",
+                "fields": { "from": "0x1", "to": "0x2", "value": "10" }
+            })
+        );
+
+        // a missing key doesn't match the event
+        assert!(
+            erc20_decoder()
+                .decode_event(
+                    &[get_selector_from_name("Transfer").unwrap(), FieldElement::ONE],
+                    &[FieldElement::ONE]
+                )
+                .is_none()
+        );
+
+        // neither does an extra data felt
+        assert!(
+            erc20_decoder()
+                .decode_event(
+                    &[
+                        get_selector_from_name("Transfer").unwrap(),
+                        FieldElement::ONE,
+                        FieldElement::TWO,
+                    ],
+                    &[FieldElement::from(10u8), FieldElement::ZERO, FieldElement::ONE]
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn core_types_are_decoded() {
+        let decoder = erc20_decoder();
+        let decode = |ty: &str, felts: &[FieldElement]| {
+            decoder.decode_type(ty, &mut felts.iter().copied()).unwrap()
+        };
+
+        assert_eq!(
+            decode("core::integer::i8", &[FieldElement::ZERO - FieldElement::TWO]),
+            json!("-2")
+        );
+        assert_eq!(
+            decode(
+                "core::array::Span::<(core::felt252, core::integer::u32)>",
+                &[FieldElement::ONE, FieldElement::from(0xabu8), FieldElement::from(7u8)]
+            ),
+            json!([["0xab", "7"]])
+        );
+
+        let text = cairo_short_string_to_felt("byte array").unwrap();
+        assert_eq!(
+            decode("core::byte_array::ByteArray", &[FieldElement::ZERO, text, 10u8.into()]),
+            json!("byte array")
+        );
+    }
+}
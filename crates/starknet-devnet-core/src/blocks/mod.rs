@@ -3,7 +3,7 @@ use std::collections::{BTreeMap, HashMap};
 use starknet_api::block::{BlockHeader, BlockNumber, BlockStatus, BlockTimestamp};
 use starknet_api::hash::{pedersen_hash_array, StarkFelt};
 use starknet_api::stark_felt;
-use starknet_rs_core::types::BlockId;
+use starknet_rs_core::types::{BlockId, BlockTag};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::{BlockHash, Felt, TransactionHash};
 use starknet_types::rpc::block::{BlockHeader as TypesBlockHeader, ResourcePrice};
@@ -74,8 +74,8 @@ impl StarknetBlocks {
         match block_id {
             BlockId::Hash(hash) => self.get_by_hash(Felt::from(hash)),
             BlockId::Number(block_number) => self.num_to_block.get(&BlockNumber(*block_number)),
-            // latest and pending for now will return the latest one
-            BlockId::Tag(_) => {
+            BlockId::Tag(BlockTag::Pending) => Some(&self.pending_block),
+            BlockId::Tag(BlockTag::Latest) => {
                 if let Some(hash) = self.last_block_hash {
                     self.get_by_hash(hash)
                 } else {
@@ -86,7 +86,7 @@ impl StarknetBlocks {
     }
 
     /// Returns the block number from a block id, by finding the block by the block id
-    fn block_number_from_block_id(&self, block_id: &BlockId) -> Option<BlockNumber> {
+    pub(crate) fn block_number_from_block_id(&self, block_id: &BlockId) -> Option<BlockNumber> {
         self.get_by_block_id(block_id).map(|block| block.block_number())
     }
 
@@ -138,6 +138,12 @@ impl StarknetBlocks {
                 filtered_blocks.insert(*block_number, block);
             });
 
+        // the pending block is not part of the sealed blocks, so it is included only if the range
+        // ends with it
+        if let Some(BlockId::Tag(BlockTag::Pending)) = to {
+            filtered_blocks.insert(self.pending_block.block_number(), &self.pending_block);
+        }
+
         Ok(filtered_blocks.into_values().collect())
     }
 }
@@ -292,7 +298,7 @@ mod tests {
         let mut blocks = StarknetBlocks::default();
         let mut block_to_insert = StarknetBlock::create_pending_block();
 
-        // latest/pending block returns none, because collection is empty
+        // latest block returns none, because collection is empty
         assert!(
             blocks
                 .block_number_from_block_id(&BlockId::Tag(
@@ -300,12 +306,13 @@ mod tests {
                 ))
                 .is_none()
         );
+        // pending block is always present
         assert!(
             blocks
                 .block_number_from_block_id(&BlockId::Tag(
                     starknet_rs_core::types::BlockTag::Pending
                 ))
-                .is_none()
+                .is_some()
         );
 
         let block_hash = block_to_insert.generate_hash().unwrap();
@@ -346,6 +353,7 @@ mod tests {
             block_to_insert.header.block_hash = Felt::from(block_number as u128).into();
             blocks.insert(block_to_insert, StateDiff::default());
         }
+        blocks.pending_block.header.block_number = BlockNumber(12);
 
         // check blocks len
         assert!(blocks.num_to_block.len() == 10);
@@ -367,9 +375,10 @@ mod tests {
         );
         // from filter using tag
         assert_eq!(blocks.get_blocks(Some(BlockId::Tag(BlockTag::Latest)), None).unwrap().len(), 1);
+        // pending block is not sealed, so it is included only if it is the end of the range
         assert_eq!(
             blocks.get_blocks(Some(BlockId::Tag(BlockTag::Pending)), None).unwrap().len(),
-            1
+            0
         );
 
         // 3. None, Some
@@ -391,7 +400,7 @@ mod tests {
         );
         assert_eq!(
             blocks.get_blocks(None, Some(BlockId::Tag(BlockTag::Pending))).unwrap().len(),
-            10
+            11
         );
         // First block as to_block query param, should return empty collection
         assert_eq!(blocks.get_blocks(None, Some(BlockId::Number(2))).unwrap().len(), 1);
@@ -412,7 +421,7 @@ mod tests {
                 .len(),
             8
         );
-        // from first block to latest/pending, should return all blocks
+        // from first block to latest/pending, should return all blocks (and the pending one)
         assert_eq!(
             blocks
                 .get_blocks(Some(BlockId::Number(2)), Some(BlockId::Tag(BlockTag::Latest)))
@@ -425,7 +434,7 @@ mod tests {
                 .get_blocks(Some(BlockId::Number(2)), Some(BlockId::Tag(BlockTag::Pending)))
                 .unwrap()
                 .len(),
-            10
+            11
         );
 
         // from last block to first block should return empty result
@@ -435,7 +444,7 @@ mod tests {
                 .unwrap()
                 .is_empty()
         );
-        // from last block to latest/pending, should return 1 block
+        // from last block to latest/pending, should return 1 block (and the pending one)
         assert_eq!(
             blocks
                 .get_blocks(Some(BlockId::Number(11)), Some(BlockId::Tag(BlockTag::Latest)))
@@ -448,7 +457,7 @@ mod tests {
                 .get_blocks(Some(BlockId::Number(11)), Some(BlockId::Tag(BlockTag::Pending)))
                 .unwrap()
                 .len(),
-            1
+            2
         );
 
         // bigger range than actual blocks in the collection, should return err
@@ -509,7 +518,7 @@ mod tests {
                 )
                 .unwrap()
                 .len(),
-            1
+            2
         );
 
         // from tag to tag
@@ -531,7 +540,7 @@ mod tests {
                 )
                 .unwrap()
                 .len(),
-            1
+            2
         );
         assert_eq!(
             blocks
@@ -541,7 +550,7 @@ mod tests {
                 )
                 .unwrap()
                 .len(),
-            1
+            0
         );
         assert_eq!(
             blocks
@@ -577,7 +586,7 @@ mod tests {
                 .get_blocks(Some(BlockId::Tag(BlockTag::Pending)), Some(BlockId::Number(11)))
                 .unwrap()
                 .len(),
-            1
+            0
         );
         assert_eq!(
             blocks
@@ -587,7 +596,7 @@ mod tests {
                 )
                 .unwrap()
                 .len(),
-            1
+            0
         );
         assert!(
             blocks
@@ -630,7 +639,7 @@ mod tests {
         let extracted_block = blocks
             .get_by_block_id(&BlockId::Tag(starknet_rs_core::types::BlockTag::Pending))
             .unwrap();
-        assert!(block_to_insert == extracted_block.clone());
+        assert!(blocks.pending_block == extracted_block.clone());
 
         match blocks.get_by_block_id(&BlockId::Number(11)) {
             None => (),
@@ -69,6 +69,10 @@ pub const DEVNET_DEFAULT_HOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
 pub const DEVNET_DEFAULT_PORT: u16 = 5050;
 pub const DEVNET_DEFAULT_TIMEOUT: u16 = 120;
 pub const DEVNET_DEFAULT_CHAIN_ID: ChainId = ChainId::Testnet;
+/// Maximum number of transactions waiting in the mempool
+pub const MEMPOOL_CAPACITY: usize = 1000;
+/// Maximum number of rejected transactions whose reason is kept
+pub const REJECTED_TRANSACTIONS_CAPACITY: usize = 1000;
 
 pub const SUPPORTED_TX_VERSION: u32 = 1;
 pub const QUERY_VERSION_BASE: FieldElement = FieldElement::from_mont([
@@ -1,10 +1,15 @@
+pub mod abi_decoder;
 pub mod account;
 mod blocks;
 pub mod constants;
 pub mod error;
+pub mod mempool;
 pub mod messaging;
+pub mod metrics;
 mod predeployed_accounts;
+mod profiler;
 pub mod raw_execution;
+pub mod revert_reason;
 pub mod starknet;
 mod state;
 mod system_contract;
@@ -0,0 +1,141 @@
+use indexmap::IndexMap;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::felt::{Nonce, TransactionHash};
+use starknet_types::rpc::transactions::{BroadcastedTransaction, TransactionType};
+
+use crate::constants::MEMPOOL_CAPACITY;
+
+/// A transaction received with a nonce ahead of the current nonce of its sender
+#[derive(Debug, Clone)]
+pub struct MempoolTransaction {
+    pub transaction_hash: TransactionHash,
+    pub sender_address: ContractAddress,
+    pub nonce: Nonce,
+    pub(crate) transaction: BroadcastedTransaction,
+}
+
+impl MempoolTransaction {
+    pub fn get_type(&self) -> TransactionType {
+        self.transaction.get_type()
+    }
+}
+
+/// Transactions waiting for the nonce of their sender to catch up with their own nonce, in the
+/// order of arrival
+#[derive(Debug, Default)]
+pub struct Mempool(IndexMap<TransactionHash, MempoolTransaction>);
+
+impl Mempool {
+    /// Returns false, without inserting the transaction, if the mempool is full
+    pub(crate) fn insert(&mut self, transaction: MempoolTransaction) -> bool {
+        if self.0.len() >= MEMPOOL_CAPACITY {
+            return false;
+        }
+
+        self.0.insert(transaction.transaction_hash, transaction);
+        true
+    }
+
+    pub fn get(&self, transaction_hash: &TransactionHash) -> Option<&MempoolTransaction> {
+        self.0.get(transaction_hash)
+    }
+
+    pub fn iter(&self) -> indexmap::map::Values<'_, TransactionHash, MempoolTransaction> {
+        self.0.values()
+    }
+
+    /// Removes and returns the first received transaction of the sender whose nonce is not ahead
+    /// of the given one. Transactions with a lower nonce can no longer be executed, but are
+    /// returned as well, so that they leave the mempool.
+    pub(crate) fn take(
+        &mut self,
+        sender_address: &ContractAddress,
+        nonce: Nonce,
+    ) -> Option<MempoolTransaction> {
+        let index = self.0.values().position(|transaction| {
+            &transaction.sender_address == sender_address && transaction.nonce <= nonce
+        })?;
+
+        self.0.shift_remove_index(index).map(|(_, transaction)| transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_api::transaction::Fee;
+    use starknet_types::felt::Felt;
+    use starknet_types::rpc::transactions::broadcasted_invoke_transaction_v1::BroadcastedInvokeTransactionV1;
+    use starknet_types::rpc::transactions::{BroadcastedInvokeTransaction, BroadcastedTransaction};
+
+    use super::{Mempool, MempoolTransaction};
+    use crate::constants::MEMPOOL_CAPACITY;
+    use crate::utils::test_utils::dummy_contract_address;
+
+    fn mempool_transaction(transaction_hash: u128, nonce: u128) -> MempoolTransaction {
+        MempoolTransaction {
+            transaction_hash: Felt::from(transaction_hash),
+            sender_address: dummy_contract_address(),
+            nonce: Felt::from(nonce),
+            transaction: BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(
+                BroadcastedInvokeTransactionV1::new(
+                    dummy_contract_address(),
+                    Fee(1),
+                    &vec![],
+                    Felt::from(nonce),
+                    &vec![],
+                    Felt::from(1),
+                ),
+            )),
+        }
+    }
+
+    #[test]
+    fn take_removes_only_the_transaction_with_the_nonce() {
+        let mut mempool = Mempool::default();
+        mempool.insert(mempool_transaction(1, 3));
+        mempool.insert(mempool_transaction(2, 2));
+
+        assert!(mempool.take(&dummy_contract_address(), Felt::from(1)).is_none());
+
+        let taken = mempool.take(&dummy_contract_address(), Felt::from(2)).unwrap();
+        assert_eq!(taken.transaction_hash, Felt::from(2));
+        assert!(mempool.get(&Felt::from(2)).is_none());
+        assert_eq!(
+            mempool.iter().map(|transaction| transaction.transaction_hash).collect::<Vec<_>>(),
+            vec![Felt::from(1)]
+        );
+    }
+
+    #[test]
+    fn take_returns_the_first_received_of_transactions_with_the_same_nonce() {
+        let mut mempool = Mempool::default();
+        mempool.insert(mempool_transaction(1, 2));
+        mempool.insert(mempool_transaction(2, 2));
+
+        let taken = mempool.take(&dummy_contract_address(), Felt::from(2)).unwrap();
+        assert_eq!(taken.transaction_hash, Felt::from(1));
+        assert!(mempool.get(&Felt::from(2)).is_some());
+    }
+
+    #[test]
+    fn take_returns_transactions_with_a_passed_nonce() {
+        let mut mempool = Mempool::default();
+        mempool.insert(mempool_transaction(1, 1));
+        mempool.insert(mempool_transaction(2, 3));
+
+        let taken = mempool.take(&dummy_contract_address(), Felt::from(2)).unwrap();
+        assert_eq!(taken.transaction_hash, Felt::from(1));
+        assert!(mempool.take(&dummy_contract_address(), Felt::from(2)).is_none());
+    }
+
+    #[test]
+    fn insert_fails_if_the_mempool_is_full() {
+        let mut mempool = Mempool::default();
+        for transaction_hash in 0..MEMPOOL_CAPACITY as u128 {
+            assert!(mempool.insert(mempool_transaction(transaction_hash, 1)));
+        }
+
+        assert!(!mempool.insert(mempool_transaction(MEMPOOL_CAPACITY as u128, 1)));
+        assert!(mempool.get(&Felt::from(MEMPOOL_CAPACITY as u128)).is_none());
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use prometheus::{
+    register_histogram_vec, register_int_counter, register_int_counter_vec, register_int_gauge,
+    HistogramVec, IntCounter, IntCounterVec, IntGauge,
+};
+
+/// Metrics of the Starknet logic. They are registered in the default prometheus registry, so
+/// gathering the default registry includes them.
+pub struct StarknetMetrics {
+    /// Added transactions, labeled by status: accepted, reverted or rejected
+    pub transactions: IntCounterVec,
+    pub blocks_produced: IntCounter,
+    /// Time spent in the blockifier, labeled by the kind of execution
+    pub execution_duration: HistogramVec,
+    /// Number of storage entries of the current state
+    pub state_storage_entries: IntGauge,
+    /// Estimated memory occupied by the states archived for querying old blocks
+    pub state_archive_bytes: IntGauge,
+}
+
+impl StarknetMetrics {
+    fn register() -> prometheus::Result<Self> {
+        Ok(Self {
+            transactions: register_int_counter_vec!(
+                "devnet_transactions_total",
+                "Number of added transactions by status",
+                &["status"]
+            )?,
+            blocks_produced: register_int_counter!(
+                "devnet_blocks_produced_total",
+                "Number of produced blocks"
+            )?,
+            execution_duration: register_histogram_vec!(
+                "devnet_execution_duration_seconds",
+                "Time spent executing in the blockifier, by kind of execution",
+                &["kind"]
+            )?,
+            state_storage_entries: register_int_gauge!(
+                "devnet_state_storage_entries",
+                "Number of storage entries of the current state"
+            )?,
+            state_archive_bytes: register_int_gauge!(
+                "devnet_state_archive_bytes",
+                "Estimated memory occupied by the archived states"
+            )?,
+        })
+    }
+}
+
+/// Kinds of execution by the blockifier, used as the label of the execution duration
+#[derive(Clone, Copy)]
+pub(crate) enum ExecutionKind {
+    Transaction,
+    Call,
+    Estimation,
+    Simulation,
+}
+
+impl ExecutionKind {
+    fn label(&self) -> &'static str {
+        match self {
+            ExecutionKind::Transaction => "transaction",
+            ExecutionKind::Call => "call",
+            ExecutionKind::Estimation => "estimation",
+            ExecutionKind::Simulation => "simulation",
+        }
+    }
+}
+
+pub fn metrics() -> &'static StarknetMetrics {
+    static METRICS: OnceLock<StarknetMetrics> = OnceLock::new();
+    METRICS.get_or_init(|| {
+        StarknetMetrics::register().expect("Starknet metrics should be registered only once")
+    })
+}
+
+/// Runs `execute` and observes its duration as the execution time of `kind`
+pub(crate) fn time_execution<T>(kind: ExecutionKind, execute: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = execute();
+    metrics()
+        .execution_duration
+        .with_label_values(&[kind.label()])
+        .observe(start.elapsed().as_secs_f64());
+
+    result
+}
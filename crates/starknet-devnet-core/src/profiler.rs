@@ -0,0 +1,283 @@
+//! Profiling of the execution of transactions. The call tree of the execution is walked and the
+//! steps, builtins and traced syscalls of each call are attributed to its contract and entry
+//! point. The resources reported by the VM include the inner calls, so they are subtracted to get
+//! the resources used by the call itself. Only the syscalls which leave a trace in the call tree
+//! can be counted, since the VM reports the syscalls of the whole transaction.
+
+use std::collections::{BTreeMap, HashMap};
+
+use blockifier::execution::call_info::CallInfo;
+use blockifier::execution::entry_point::CallType;
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use starknet_api::deprecated_contract_class::EntryPointType;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::ContractClass;
+use starknet_types::felt::{ClassHash, Felt};
+use starknet_types::rpc::execution_profile::{EntryPointProfile, ExecutionProfile};
+use starknet_types::traits::ToHexString;
+
+use crate::abi_decoder::AbiDecoder;
+
+/// Profiles the validation, the execution and the fee transfer of a transaction. These are the
+/// root frames of the folded stacks. Entry points are named from the classes the calls were
+/// executed with, so the profile doesn't depend on the state the transaction was executed on.
+pub(crate) fn profile_execution(
+    execution_info: &TransactionExecutionInfo,
+    contract_classes: &HashMap<ClassHash, ContractClass>,
+) -> ExecutionProfile {
+    let mut profiler = Profiler {
+        contract_classes,
+        decoders: HashMap::new(),
+        entry_points: HashMap::new(),
+        stacks: BTreeMap::new(),
+    };
+
+    for (phase, call_info) in [
+        ("validate", &execution_info.validate_call_info),
+        ("execute", &execution_info.execute_call_info),
+        ("fee_transfer", &execution_info.fee_transfer_call_info),
+    ] {
+        if let Some(call_info) = call_info {
+            profiler.visit(call_info, phase);
+        }
+    }
+
+    profiler.into_profile()
+}
+
+struct Profiler<'a> {
+    contract_classes: &'a HashMap<ClassHash, ContractClass>,
+    decoders: HashMap<ClassHash, Option<AbiDecoder>>,
+    entry_points: HashMap<(ContractAddress, Felt), EntryPointProfile>,
+    /// Self steps per call stack
+    stacks: BTreeMap<String, usize>,
+}
+
+impl Profiler<'_> {
+    fn visit(&mut self, call_info: &CallInfo, parent_stack: &str) {
+        let contract_address = ContractAddress::from(call_info.call.storage_address);
+        let selector = Felt::from(call_info.call.entry_point_selector.0);
+        let entry_point = self.entry_point_name(call_info, selector);
+        let stack = format!("{parent_stack};{contract_address:#x}::{entry_point}");
+
+        let resources = &call_info.vm_resources;
+        let mut steps = resources.n_steps;
+        let mut memory_holes = resources.n_memory_holes;
+        let mut builtins: BTreeMap<String, usize> =
+            resources.builtin_instance_counter.clone().into_iter().collect();
+        let mut syscalls = BTreeMap::new();
+
+        for inner_call in &call_info.inner_calls {
+            let inner_resources = &inner_call.vm_resources;
+            steps = steps.saturating_sub(inner_resources.n_steps);
+            memory_holes = memory_holes.saturating_sub(inner_resources.n_memory_holes);
+            for (builtin, count) in &inner_resources.builtin_instance_counter {
+                if let Some(own_count) = builtins.get_mut(builtin) {
+                    *own_count = own_count.saturating_sub(*count);
+                }
+            }
+
+            let syscall = match (inner_call.call.entry_point_type, inner_call.call.call_type) {
+                (EntryPointType::Constructor, _) => "deploy",
+                (_, CallType::Call) => "call_contract",
+                (_, CallType::Delegate) => "library_call",
+            };
+            *syscalls.entry(syscall.to_string()).or_default() += 1;
+        }
+        builtins.retain(|_, count| *count > 0);
+
+        for (syscall, count) in [
+            ("emit_event", call_info.execution.events.len()),
+            ("send_message_to_l1", call_info.execution.l2_to_l1_messages.len()),
+            ("storage_read", call_info.storage_read_values.len()),
+        ] {
+            if count > 0 {
+                syscalls.insert(syscall.to_string(), count);
+            }
+        }
+
+        if steps > 0 {
+            *self.stacks.entry(stack.clone()).or_default() += steps;
+        }
+
+        let profile = self.entry_points.entry((contract_address, selector)).or_insert_with(|| {
+            EntryPointProfile {
+                contract_address,
+                entry_point_selector: selector,
+                entry_point,
+                calls: 0,
+                steps: 0,
+                memory_holes: 0,
+                builtins: BTreeMap::new(),
+                traced_syscalls: BTreeMap::new(),
+            }
+        });
+        profile.calls += 1;
+        profile.steps += steps;
+        profile.memory_holes += memory_holes;
+        for (builtin, count) in builtins {
+            *profile.builtins.entry(builtin).or_default() += count;
+        }
+        for (syscall, count) in syscalls {
+            *profile.traced_syscalls.entry(syscall).or_default() += count;
+        }
+
+        for inner_call in &call_info.inner_calls {
+            self.visit(inner_call, &stack);
+        }
+    }
+
+    /// The name of the entry point in the ABI of the executed class, or the selector if the class
+    /// or its ABI are not known
+    fn entry_point_name(&mut self, call_info: &CallInfo, selector: Felt) -> String {
+        // the class hash of each executed call is filled in by the execution, also if it was
+        // deduced from the address, so it is the class at the time of the execution; declared
+        // classes are never removed, so it is always among the contract classes
+        let contract_classes = self.contract_classes;
+        call_info
+            .call
+            .class_hash
+            .map(Felt::from)
+            .and_then(|class_hash| {
+                self.decoders
+                    .entry(class_hash)
+                    .or_insert_with(|| contract_classes.get(&class_hash).and_then(AbiDecoder::new))
+                    .as_ref()
+            })
+            .and_then(|decoder| decoder.function_name(selector.into()))
+            .map(String::from)
+            .unwrap_or_else(|| selector.to_prefixed_hex_str())
+    }
+
+    fn into_profile(self) -> ExecutionProfile {
+        let mut entry_points: Vec<EntryPointProfile> = self.entry_points.into_values().collect();
+        entry_points.sort_by(|a, b| {
+            b.steps.cmp(&a.steps).then_with(|| a.entry_point.cmp(&b.entry_point)).then_with(|| {
+                a.contract_address
+                    .to_prefixed_hex_str()
+                    .cmp(&b.contract_address.to_prefixed_hex_str())
+            })
+        });
+
+        let folded_stacks =
+            self.stacks.iter().map(|(stack, steps)| format!("{stack} {steps}\n")).collect();
+
+        ExecutionProfile { entry_points, folded_stacks }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use blockifier::execution::call_info::{CallInfo, OrderedEvent};
+    use blockifier::execution::entry_point::CallType;
+    use blockifier::transaction::objects::TransactionExecutionInfo;
+    use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, PatriciaKey};
+    use starknet_api::hash::StarkFelt;
+    use starknet_rs_core::utils::get_selector_from_name;
+    use starknet_types::felt::Felt;
+
+    use super::profile_execution;
+    use crate::utils::test_utils::dummy_cairo_1_contract_class;
+
+    fn call_info(
+        address: u64,
+        selector: u64,
+        steps: usize,
+        inner_calls: Vec<CallInfo>,
+    ) -> CallInfo {
+        let mut call_info = CallInfo { inner_calls, ..Default::default() };
+        call_info.call.storage_address =
+            ContractAddress(PatriciaKey::try_from(StarkFelt::from(address)).unwrap());
+        call_info.call.entry_point_selector = EntryPointSelector(StarkFelt::from(selector));
+        call_info.vm_resources.n_steps = steps;
+
+        call_info
+    }
+
+    #[test]
+    fn resources_of_inner_calls_are_not_attributed_to_the_caller() {
+        let mut transfer = call_info(0x2, 0xaa, 30, vec![]);
+        transfer.vm_resources.builtin_instance_counter.insert("range_check_builtin".into(), 2);
+        transfer.storage_read_values = vec![StarkFelt::ONE, StarkFelt::TWO];
+        transfer.execution.events = vec![OrderedEvent::default()];
+
+        let mut execute = call_info(0x1, 0xbb, 100, vec![transfer]);
+        execute.vm_resources.builtin_instance_counter.insert("range_check_builtin".into(), 5);
+
+        let execution_info =
+            TransactionExecutionInfo { execute_call_info: Some(execute), ..Default::default() };
+        let profile = profile_execution(&execution_info, &HashMap::new());
+
+        assert_eq!(profile.entry_points.len(), 2);
+        let caller = &profile.entry_points[0];
+        assert_eq!(caller.entry_point, "0xbb");
+        assert_eq!(caller.steps, 70);
+        assert_eq!(caller.builtins.get("range_check_builtin"), Some(&3));
+        assert_eq!(caller.traced_syscalls.get("call_contract"), Some(&1));
+
+        let callee = &profile.entry_points[1];
+        assert_eq!(callee.entry_point, "0xaa");
+        assert_eq!(callee.steps, 30);
+        assert_eq!(callee.traced_syscalls.get("storage_read"), Some(&2));
+        assert_eq!(callee.traced_syscalls.get("emit_event"), Some(&1));
+
+        assert_eq!(profile.folded_stacks, "execute;0x1::0xbb 70\nexecute;0x1::0xbb;0x2::0xaa 30\n");
+    }
+
+    #[test]
+    fn repeated_calls_are_aggregated() {
+        let mut library_call = call_info(0x1, 0xaa, 10, vec![]);
+        library_call.call.call_type = CallType::Delegate;
+        let execute = call_info(
+            0x1,
+            0xbb,
+            60,
+            vec![call_info(0x2, 0xaa, 10, vec![]), call_info(0x2, 0xaa, 10, vec![]), library_call],
+        );
+        let validate = call_info(0x1, 0xcc, 20, vec![]);
+
+        let execution_info = TransactionExecutionInfo {
+            validate_call_info: Some(validate),
+            execute_call_info: Some(execute),
+            ..Default::default()
+        };
+        let profile = profile_execution(&execution_info, &HashMap::new());
+
+        // the calls of 0xaa are to two different contracts
+        assert_eq!(profile.entry_points.len(), 4);
+        let execute_profile = &profile.entry_points[0];
+        assert_eq!(execute_profile.entry_point, "0xbb");
+        assert_eq!(execute_profile.steps, 30);
+        assert_eq!(execute_profile.traced_syscalls.get("call_contract"), Some(&2));
+        assert_eq!(execute_profile.traced_syscalls.get("library_call"), Some(&1));
+
+        let repeated_profile = &profile.entry_points[1];
+        assert_eq!(repeated_profile.calls, 2);
+        assert_eq!(repeated_profile.steps, 20);
+
+        assert_eq!(
+            profile.folded_stacks,
+            "execute;0x1::0xbb 30\nexecute;0x1::0xbb;0x1::0xaa 10\nexecute;0x1::0xbb;0x2::0xaa \
+             20\nvalidate;0x1::0xcc 20\n"
+        );
+    }
+
+    #[test]
+    fn entry_points_are_named_from_the_executed_class() {
+        let class_hash = Felt::from(0x123);
+        let contract_classes = HashMap::from([(class_hash, dummy_cairo_1_contract_class().into())]);
+
+        let selector = get_selector_from_name("test_emit_event").unwrap();
+        let mut execute = call_info(0x1, 0, 10, vec![]);
+        execute.call.entry_point_selector = EntryPointSelector(selector.into());
+        execute.call.class_hash = Some(ClassHash(class_hash.into()));
+
+        let execution_info =
+            TransactionExecutionInfo { execute_call_info: Some(execute), ..Default::default() };
+        let profile = profile_execution(&execution_info, &contract_classes);
+
+        assert_eq!(profile.entry_points[0].entry_point, "test_emit_event");
+    }
+}
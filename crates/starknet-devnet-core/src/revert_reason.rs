@@ -0,0 +1,205 @@
+//! Decoding of the panic data of failed Cairo 1 executions. The blockifier formats the panic data
+//! into the revert error as e.g. `Failure reason: (0x616263 ('abc'), "a byte array")`, deep in
+//! the error of the outermost call. Decoding puts the reason and the raw panic felts in front.
+
+use cairo_lang_utils::byte_array::{BYTES_IN_WORD, BYTE_ARRAY_MAGIC};
+use starknet_rs_ff::FieldElement;
+
+const FAILURE_REASON_PREFIX: &str = "Failure reason: ";
+
+/// An item of the panic data, as formatted by the blockifier
+#[derive(Debug, PartialEq)]
+enum PanicItem {
+    /// A felt which is not a short string
+    Felt(FieldElement),
+    ShortString {
+        raw: FieldElement,
+        text: String,
+    },
+    /// A serialized `ByteArray`, of which the blockifier shows only the text
+    ByteArray(String),
+}
+
+impl PanicItem {
+    fn reason(&self) -> String {
+        match self {
+            PanicItem::Felt(felt) => format!("{felt:#x}"),
+            PanicItem::ShortString { text, .. } => format!("'{text}'"),
+            PanicItem::ByteArray(text) => format!("\"{text}\""),
+        }
+    }
+
+    fn raw_felts(&self) -> Vec<FieldElement> {
+        match self {
+            PanicItem::Felt(felt) | PanicItem::ShortString { raw: felt, .. } => vec![*felt],
+            PanicItem::ByteArray(text) => serialize_byte_array(text),
+        }
+    }
+}
+
+/// Prepends the decoded panic reason and the raw panic data to `revert_error`, if it contains
+/// the panic data of a Cairo 1 execution, otherwise returns it unchanged. The innermost panic is
+/// decoded, since it is the original cause of the failure.
+pub fn decode_revert_reason(revert_error: &str) -> String {
+    let Some(items) = revert_error
+        .rfind(FAILURE_REASON_PREFIX)
+        .and_then(|start| parse_panic_data(&revert_error[start + FAILURE_REASON_PREFIX.len()..]))
+    else {
+        return revert_error.to_string();
+    };
+
+    let reason = items.iter().map(PanicItem::reason).collect::<Vec<_>>().join(", ");
+    let raw_felts = items
+        .iter()
+        .flat_map(PanicItem::raw_felts)
+        .map(|felt| format!("{felt:#x}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("Panic reason: {reason}\nPanic data: [{raw_felts}]\n{revert_error}")
+}
+
+/// Parses the panic data formatted as a single item followed by `.`, or as a parenthesized list
+fn parse_panic_data(formatted: &str) -> Option<Vec<PanicItem>> {
+    let Some(mut rest) = formatted.strip_prefix('(') else {
+        let (item, _) = parse_item(formatted, &["."])?;
+        return Some(vec![item]);
+    };
+
+    let mut items = vec![];
+    loop {
+        let (item, after_item) = parse_item(rest, &[", ", ")"])?;
+        items.push(item);
+        match after_item.strip_prefix(", ") {
+            Some(next) => rest = next,
+            None => return Some(items),
+        }
+    }
+}
+
+/// Parses an item at the start of `formatted`. Returns the item and the rest of the input,
+/// starting with one of the `terminators`.
+fn parse_item<'a>(formatted: &'a str, terminators: &[&str]) -> Option<(PanicItem, &'a str)> {
+    // the text of strings is not escaped, so it ends at the first quote followed by a terminator
+    let split_quoted = |quoted: &'a str, closing_quote: &str| {
+        quoted.match_indices(closing_quote).find_map(|(index, _)| {
+            let after = &quoted[index + closing_quote.len()..];
+            terminators
+                .iter()
+                .any(|terminator| after.starts_with(terminator))
+                .then(|| (quoted[..index].to_string(), after))
+        })
+    };
+
+    if let Some(quoted) = formatted.strip_prefix('"') {
+        let (text, rest) = split_quoted(quoted, "\"")?;
+        return Some((PanicItem::ByteArray(text), rest));
+    }
+
+    let hex_digits = formatted.strip_prefix("0x")?;
+    let hex_len = hex_digits.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(hex_digits.len());
+    let raw = FieldElement::from_hex_be(&formatted[..hex_len + 2]).ok()?;
+    let rest = &hex_digits[hex_len..];
+
+    match rest.strip_prefix(" ('") {
+        Some(quoted) => {
+            let (text, rest) = split_quoted(quoted, "')")?;
+            Some((PanicItem::ShortString { raw, text }, rest))
+        }
+        None => Some((PanicItem::Felt(raw), rest)),
+    }
+}
+
+/// Serializes the text as the `ByteArray` panic data: the magic value, the number of full words,
+/// the full words, the pending word and its length
+fn serialize_byte_array(text: &str) -> Vec<FieldElement> {
+    let to_felt = |bytes: &[u8]| {
+        let mut word = [0u8; 32];
+        word[32 - bytes.len()..].copy_from_slice(bytes);
+        FieldElement::from_bytes_be(&word).expect("A word is shorter than a felt")
+    };
+
+    let chunks: Vec<&[u8]> = text.as_bytes().chunks(BYTES_IN_WORD).collect();
+    let (full_words, pending_word) = match chunks.split_last() {
+        Some((last, full)) if last.len() < BYTES_IN_WORD => (full, *last),
+        _ => (&chunks[..], &[][..]),
+    };
+
+    let mut felts = vec![
+        FieldElement::from_hex_be(BYTE_ARRAY_MAGIC).expect("The magic value is a valid felt"),
+        FieldElement::from(full_words.len()),
+    ];
+    felts.extend(full_words.iter().map(|word| to_felt(word)));
+    felts.push(to_felt(pending_word));
+    felts.push(FieldElement::from(pending_word.len()));
+
+    felts
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_rs_core::utils::cairo_short_string_to_felt;
+    use starknet_rs_ff::FieldElement;
+
+    use super::{decode_revert_reason, parse_panic_data, serialize_byte_array, PanicItem};
+
+    #[test]
+    fn revert_error_without_panic_data_is_unchanged() {
+        let revert_error = "Insufficient max fee: max_fee: 1, actual_fee: 2";
+        assert_eq!(decode_revert_reason(revert_error), revert_error);
+    }
+
+    #[test]
+    fn innermost_short_string_panic_is_decoded() {
+        let revert_error = "Error in the called contract (0x1):\nError at pc=0:4835:\nGot an \
+                            exception while executing a hint: Custom Hint Error: Execution \
+                            failed. Failure reason: 0x496e73756666696369656e742062616c616e6365 \
+                            ('Insufficient balance').\nCairo traceback (most recent call last):\n";
+
+        assert_eq!(
+            decode_revert_reason(revert_error),
+            format!(
+                "Panic reason: 'Insufficient balance'\nPanic data: \
+                 [0x496e73756666696369656e742062616c616e6365]\n{revert_error}"
+            )
+        );
+    }
+
+    #[test]
+    fn list_of_felts_strings_and_byte_arrays_is_decoded() {
+        let items = parse_panic_data(
+            "(0x61 ('a'), 0x0, \"it's a (long), \"quoted\" text\", \
+             0x454e545259504f494e545f4641494c4544 ('ENTRYPOINT_FAILED')).",
+        )
+        .unwrap();
+
+        assert_eq!(
+            items,
+            vec![
+                PanicItem::ShortString { raw: FieldElement::from(0x61u8), text: "a".into() },
+                PanicItem::Felt(FieldElement::ZERO),
+                PanicItem::ByteArray("it's a (long), \"quoted\" text".into()),
+                PanicItem::ShortString {
+                    raw: cairo_short_string_to_felt("ENTRYPOINT_FAILED").unwrap(),
+                    text: "ENTRYPOINT_FAILED".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn byte_array_is_serialized_into_words() {
+        let text = "This text is longer than thirty one bytes";
+        let felts = serialize_byte_array(text);
+
+        assert_eq!(felts.len(), 5);
+        assert_eq!(felts[1], FieldElement::ONE);
+        assert_eq!(felts[2], cairo_short_string_to_felt(&text[..31]).unwrap());
+        assert_eq!(felts[3], cairo_short_string_to_felt(&text[31..]).unwrap());
+        assert_eq!(felts[4], FieldElement::from(text.len() - 31));
+
+        // a text of full words has an empty pending word
+        let felts = serialize_byte_array(&text[..31]);
+        assert_eq!(felts[3..], [FieldElement::ZERO, FieldElement::ZERO]);
+    }
+}
@@ -5,27 +5,44 @@ use starknet_types::rpc::transactions::broadcasted_declare_transaction_v2::Broad
 use starknet_types::rpc::transactions::broadcasted_declare_transaction_v3::BroadcastedDeclareTransactionV3;
 use starknet_types::rpc::transactions::declare_transaction_v3::DeclareTransactionV3;
 use starknet_types::rpc::transactions::{
-    BroadcastedDeclareTransaction, DeclareTransaction, Transaction,
+    BroadcastedDeclareTransaction, BroadcastedTransaction, DeclareTransaction, Transaction,
 };
 
 use super::dump::DumpEvent;
 use crate::error::{DevnetResult, Error};
+use crate::metrics::{time_execution, ExecutionKind};
 use crate::starknet::Starknet;
 
 pub fn add_declare_transaction_v3(
     starknet: &mut Starknet,
     broadcasted_declare_transaction: BroadcastedDeclareTransactionV3,
 ) -> DevnetResult<(TransactionHash, ClassHash)> {
-    if broadcasted_declare_transaction.common.is_max_fee_zero_value() {
-        return Err(Error::MaxFeeZeroError { tx_type: "declare transaction v3".to_string() });
-    }
-
     let blockifier_declare_transaction = broadcasted_declare_transaction
         .create_blockifier_declare(starknet.chain_id().to_felt(), false)?;
 
     let transaction_hash = blockifier_declare_transaction.tx_hash().0.into();
     let class_hash = blockifier_declare_transaction.class_hash().0.into();
 
+    if broadcasted_declare_transaction.common.is_max_fee_zero_value() {
+        return Err(starknet.reject_transaction(
+            transaction_hash,
+            Error::MaxFeeZeroError { tx_type: "declare transaction v3".to_string() },
+        ));
+    }
+
+    if starknet.add_to_mempool_if_nonce_ahead(
+        transaction_hash,
+        broadcasted_declare_transaction.sender_address,
+        broadcasted_declare_transaction.common.nonce,
+        || {
+            BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V3(Box::new(
+                broadcasted_declare_transaction.clone(),
+            )))
+        },
+    ) {
+        return Ok((transaction_hash, class_hash));
+    }
+
     let transaction =
         Transaction::Declare(DeclareTransaction::Version3(DeclareTransactionV3::new(
             broadcasted_declare_transaction.clone(),
@@ -33,11 +50,12 @@ pub fn add_declare_transaction_v3(
             transaction_hash,
         )));
 
-    let blockifier_execution_result =
+    let blockifier_execution_result = time_execution(ExecutionKind::Transaction, || {
         blockifier::transaction::account_transaction::AccountTransaction::Declare(
             blockifier_declare_transaction,
         )
-        .execute(&mut starknet.state.state, &starknet.block_context, true, true);
+        .execute(&mut starknet.state.state, &starknet.block_context, true, true)
+    });
 
     starknet.handle_transaction_result(
         transaction,
@@ -55,25 +73,42 @@ pub fn add_declare_transaction_v2(
     starknet: &mut Starknet,
     broadcasted_declare_transaction: BroadcastedDeclareTransactionV2,
 ) -> DevnetResult<(TransactionHash, ClassHash)> {
-    if broadcasted_declare_transaction.common.max_fee.0 == 0 {
-        return Err(Error::MaxFeeZeroError { tx_type: "declare transaction v2".into() });
-    }
-
     let blockifier_declare_transaction =
         broadcasted_declare_transaction.create_blockifier_declare(starknet.chain_id().to_felt())?;
 
     let transaction_hash = blockifier_declare_transaction.tx_hash().0.into();
     let class_hash = blockifier_declare_transaction.class_hash().0.into();
 
+    if broadcasted_declare_transaction.common.max_fee.0 == 0 {
+        return Err(starknet.reject_transaction(
+            transaction_hash,
+            Error::MaxFeeZeroError { tx_type: "declare transaction v2".into() },
+        ));
+    }
+
+    if starknet.add_to_mempool_if_nonce_ahead(
+        transaction_hash,
+        broadcasted_declare_transaction.sender_address,
+        broadcasted_declare_transaction.common.nonce,
+        || {
+            BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V2(Box::new(
+                broadcasted_declare_transaction.clone(),
+            )))
+        },
+    ) {
+        return Ok((transaction_hash, class_hash));
+    }
+
     let transaction = Transaction::Declare(DeclareTransaction::Version2(
         broadcasted_declare_transaction.create_declare(class_hash, transaction_hash),
     ));
 
-    let blockifier_execution_result =
+    let blockifier_execution_result = time_execution(ExecutionKind::Transaction, || {
         blockifier::transaction::account_transaction::AccountTransaction::Declare(
             blockifier_declare_transaction,
         )
-        .execute(&mut starknet.state.state, &starknet.block_context, true, true);
+        .execute(&mut starknet.state.state, &starknet.block_context, true, true)
+    });
 
     starknet.handle_transaction_result(
         transaction,
@@ -91,14 +126,30 @@ pub fn add_declare_transaction_v1(
     starknet: &mut Starknet,
     broadcasted_declare_transaction: BroadcastedDeclareTransactionV1,
 ) -> DevnetResult<(TransactionHash, ClassHash)> {
-    if broadcasted_declare_transaction.common.max_fee.0 == 0 {
-        return Err(Error::MaxFeeZeroError { tx_type: "declare transaction v1".into() });
-    }
-
     let class_hash = broadcasted_declare_transaction.generate_class_hash()?;
     let transaction_hash = broadcasted_declare_transaction
         .calculate_transaction_hash(&starknet.config.chain_id.to_felt(), &class_hash)?;
 
+    if broadcasted_declare_transaction.common.max_fee.0 == 0 {
+        return Err(starknet.reject_transaction(
+            transaction_hash,
+            Error::MaxFeeZeroError { tx_type: "declare transaction v1".into() },
+        ));
+    }
+
+    if starknet.add_to_mempool_if_nonce_ahead(
+        transaction_hash,
+        broadcasted_declare_transaction.sender_address,
+        broadcasted_declare_transaction.common.nonce,
+        || {
+            BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V1(Box::new(
+                broadcasted_declare_transaction.clone(),
+            )))
+        },
+    ) {
+        return Ok((transaction_hash, class_hash));
+    }
+
     let declare_transaction =
         broadcasted_declare_transaction.create_declare(class_hash, transaction_hash);
     let transaction = Transaction::Declare(DeclareTransaction::Version1(declare_transaction));
@@ -106,11 +157,12 @@ pub fn add_declare_transaction_v1(
     let blockifier_declare_transaction =
         broadcasted_declare_transaction.create_blockifier_declare(class_hash, transaction_hash)?;
 
-    let blockifier_execution_result =
+    let blockifier_execution_result = time_execution(ExecutionKind::Transaction, || {
         blockifier::transaction::account_transaction::AccountTransaction::Declare(
             blockifier_declare_transaction,
         )
-        .execute(&mut starknet.state.state, &starknet.block_context, true, true);
+        .execute(&mut starknet.state.state, &starknet.block_context, true, true)
+    });
 
     starknet.handle_transaction_result(
         transaction,
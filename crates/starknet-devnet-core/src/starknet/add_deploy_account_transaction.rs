@@ -11,26 +11,33 @@ use starknet_types::rpc::transactions::{
 use super::dump::DumpEvent;
 use super::Starknet;
 use crate::error::{DevnetResult, Error};
+use crate::metrics::{time_execution, ExecutionKind};
 use crate::traits::StateExtractor;
 
 pub fn add_deploy_account_transaction_v3(
     starknet: &mut Starknet,
     broadcasted_deploy_account_transaction: BroadcastedDeployAccountTransactionV3,
 ) -> DevnetResult<(TransactionHash, ContractAddress)> {
+    let blockifier_deploy_account_transaction = broadcasted_deploy_account_transaction
+        .create_blockifier_deploy_account(starknet.chain_id().to_felt(), false)?;
+
+    let transaction_hash = blockifier_deploy_account_transaction.tx_hash.0.into();
+
     if broadcasted_deploy_account_transaction.common.is_max_fee_zero_value() {
-        return Err(Error::MaxFeeZeroError { tx_type: "deploy account transaction v3".into() });
+        return Err(starknet.reject_transaction(
+            transaction_hash,
+            Error::MaxFeeZeroError { tx_type: "deploy account transaction v3".into() },
+        ));
     }
 
     if !starknet.state.is_contract_declared(&broadcasted_deploy_account_transaction.class_hash) {
-        return Err(Error::StateError(crate::error::StateError::NoneClassHash(
-            broadcasted_deploy_account_transaction.class_hash,
-        )));
+        return Err(starknet.reject_transaction(
+            transaction_hash,
+            Error::StateError(crate::error::StateError::NoneClassHash(
+                broadcasted_deploy_account_transaction.class_hash,
+            )),
+        ));
     }
-
-    let blockifier_deploy_account_transaction = broadcasted_deploy_account_transaction
-        .create_blockifier_deploy_account(starknet.chain_id().to_felt(), false)?;
-
-    let transaction_hash = blockifier_deploy_account_transaction.tx_hash.0.into();
     let address: ContractAddress = blockifier_deploy_account_transaction.contract_address.into();
     let deploy_account_transaction_v3 = DeployAccountTransactionV3::new(
         broadcasted_deploy_account_transaction.clone(),
@@ -42,11 +49,12 @@ pub fn add_deploy_account_transaction_v3(
         deploy_account_transaction_v3,
     )));
 
-    let blockifier_execution_result =
+    let blockifier_execution_result = time_execution(ExecutionKind::Transaction, || {
         blockifier::transaction::account_transaction::AccountTransaction::DeployAccount(
             blockifier_deploy_account_transaction,
         )
-        .execute(&mut starknet.state.state, &starknet.block_context, true, true);
+        .execute(&mut starknet.state.state, &starknet.block_context, true, true)
+    });
 
     starknet.handle_transaction_result(transaction, None, blockifier_execution_result)?;
     starknet.handle_dump_event(DumpEvent::AddDeployAccountTransaction(
@@ -60,20 +68,26 @@ pub fn add_deploy_account_transaction_v1(
     starknet: &mut Starknet,
     broadcasted_deploy_account_transaction: BroadcastedDeployAccountTransactionV1,
 ) -> DevnetResult<(TransactionHash, ContractAddress)> {
+    let blockifier_deploy_account_transaction = broadcasted_deploy_account_transaction
+        .create_blockifier_deploy_account(starknet.chain_id().to_felt(), false)?;
+
+    let transaction_hash = blockifier_deploy_account_transaction.tx_hash.0.into();
+
     if broadcasted_deploy_account_transaction.common.max_fee.0 == 0 {
-        return Err(Error::MaxFeeZeroError { tx_type: "deploy account transaction".into() });
+        return Err(starknet.reject_transaction(
+            transaction_hash,
+            Error::MaxFeeZeroError { tx_type: "deploy account transaction".into() },
+        ));
     }
 
     if !starknet.state.is_contract_declared(&broadcasted_deploy_account_transaction.class_hash) {
-        return Err(Error::StateError(crate::error::StateError::NoneClassHash(
-            broadcasted_deploy_account_transaction.class_hash,
-        )));
+        return Err(starknet.reject_transaction(
+            transaction_hash,
+            Error::StateError(crate::error::StateError::NoneClassHash(
+                broadcasted_deploy_account_transaction.class_hash,
+            )),
+        ));
     }
-
-    let blockifier_deploy_account_transaction = broadcasted_deploy_account_transaction
-        .create_blockifier_deploy_account(starknet.chain_id().to_felt(), false)?;
-
-    let transaction_hash = blockifier_deploy_account_transaction.tx_hash.0.into();
     let address: ContractAddress = blockifier_deploy_account_transaction.contract_address.into();
     let deploy_account_transaction_v1 = broadcasted_deploy_account_transaction
         .compile_deploy_account_transaction_v1(&transaction_hash, address);
@@ -82,11 +96,12 @@ pub fn add_deploy_account_transaction_v1(
         deploy_account_transaction_v1,
     )));
 
-    let blockifier_execution_result =
+    let blockifier_execution_result = time_execution(ExecutionKind::Transaction, || {
         blockifier::transaction::account_transaction::AccountTransaction::DeployAccount(
             blockifier_deploy_account_transaction,
         )
-        .execute(&mut starknet.state.state, &starknet.block_context, true, true);
+        .execute(&mut starknet.state.state, &starknet.block_context, true, true)
+    });
 
     starknet.handle_transaction_result(transaction, None, blockifier_execution_result)?;
     starknet.handle_dump_event(DumpEvent::AddDeployAccountTransaction(
@@ -4,34 +4,52 @@ use starknet_types::rpc::transactions::broadcasted_invoke_transaction_v1::Broadc
 use starknet_types::rpc::transactions::broadcasted_invoke_transaction_v3::BroadcastedInvokeTransactionV3;
 use starknet_types::rpc::transactions::invoke_transaction_v3::InvokeTransactionV3;
 use starknet_types::rpc::transactions::{
-    BroadcastedInvokeTransaction, InvokeTransaction, Transaction,
+    BroadcastedInvokeTransaction, BroadcastedTransaction, InvokeTransaction, Transaction,
 };
 
 use super::dump::DumpEvent;
 use super::Starknet;
 use crate::error::{DevnetResult, Error};
+use crate::metrics::{time_execution, ExecutionKind};
 
 pub fn add_invoke_transaction_v1(
     starknet: &mut Starknet,
     broadcasted_invoke_transaction: BroadcastedInvokeTransactionV1,
 ) -> DevnetResult<TransactionHash> {
-    if broadcasted_invoke_transaction.common.max_fee.0 == 0 {
-        return Err(Error::MaxFeeZeroError { tx_type: "invoke transaction".into() });
-    }
-
     let blockifier_invoke_transaction = broadcasted_invoke_transaction
         .create_blockifier_invoke_transaction(starknet.chain_id().to_felt(), false)?;
     let transaction_hash = blockifier_invoke_transaction.tx_hash.0.into();
 
+    if broadcasted_invoke_transaction.common.max_fee.0 == 0 {
+        return Err(starknet.reject_transaction(
+            transaction_hash,
+            Error::MaxFeeZeroError { tx_type: "invoke transaction".into() },
+        ));
+    }
+
+    if starknet.add_to_mempool_if_nonce_ahead(
+        transaction_hash,
+        broadcasted_invoke_transaction.sender_address,
+        broadcasted_invoke_transaction.common.nonce,
+        || {
+            BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(
+                broadcasted_invoke_transaction.clone(),
+            ))
+        },
+    ) {
+        return Ok(transaction_hash);
+    }
+
     let invoke_transaction =
         broadcasted_invoke_transaction.create_invoke_transaction(transaction_hash);
     let transaction = Transaction::Invoke(InvokeTransaction::Version1(invoke_transaction));
 
-    let blockifier_execution_result =
+    let blockifier_execution_result = time_execution(ExecutionKind::Transaction, || {
         blockifier::transaction::account_transaction::AccountTransaction::Invoke(
             blockifier_invoke_transaction,
         )
-        .execute(&mut starknet.state.state, &starknet.block_context, true, true);
+        .execute(&mut starknet.state.state, &starknet.block_context, true, true)
+    });
 
     starknet.handle_transaction_result(transaction, None, blockifier_execution_result)?;
     starknet.handle_dump_event(DumpEvent::AddInvokeTransaction(
@@ -45,20 +63,37 @@ pub fn add_invoke_transaction_v3(
     starknet: &mut Starknet,
     broadcasted_invoke_transaction: BroadcastedInvokeTransactionV3,
 ) -> DevnetResult<TransactionHash> {
-    if broadcasted_invoke_transaction.common.is_max_fee_zero_value() {
-        return Err(Error::MaxFeeZeroError { tx_type: "invoke transaction v3".into() });
-    }
-
     let blockifier_invoke_transaction = broadcasted_invoke_transaction
         .create_blockifier_invoke_transaction(starknet.chain_id().to_felt(), false)?;
 
     let transaction_hash = blockifier_invoke_transaction.tx_hash.0.into();
 
-    let blockifier_execution_result =
+    if broadcasted_invoke_transaction.common.is_max_fee_zero_value() {
+        return Err(starknet.reject_transaction(
+            transaction_hash,
+            Error::MaxFeeZeroError { tx_type: "invoke transaction v3".into() },
+        ));
+    }
+
+    if starknet.add_to_mempool_if_nonce_ahead(
+        transaction_hash,
+        broadcasted_invoke_transaction.sender_address,
+        broadcasted_invoke_transaction.common.nonce,
+        || {
+            BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V3(
+                broadcasted_invoke_transaction.clone(),
+            ))
+        },
+    ) {
+        return Ok(transaction_hash);
+    }
+
+    let blockifier_execution_result = time_execution(ExecutionKind::Transaction, || {
         blockifier::transaction::account_transaction::AccountTransaction::Invoke(
             blockifier_invoke_transaction,
         )
-        .execute(&mut starknet.state.state, &starknet.block_context, true, true);
+        .execute(&mut starknet.state.state, &starknet.block_context, true, true)
+    });
 
     let transaction = Transaction::Invoke(InvokeTransaction::Version3(InvokeTransactionV3::new(
         broadcasted_invoke_transaction.clone(),
@@ -78,16 +113,20 @@ mod tests {
 
     use starknet_api::hash::StarkFelt;
     use starknet_api::transaction::{Fee, Tip};
-    use starknet_rs_core::types::{TransactionExecutionStatus, TransactionFinalityStatus};
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, TransactionExecutionStatus, TransactionFinalityStatus, TransactionStatus,
+    };
     use starknet_rs_core::utils::get_selector_from_name;
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::contract_class::{Cairo0ContractClass, ContractClass};
     use starknet_types::contract_storage_key::ContractStorageKey;
     use starknet_types::felt::Felt;
+    use starknet_types::rpc::block_override::BlockOverride;
     use starknet_types::rpc::transactions::broadcasted_invoke_transaction_v1::BroadcastedInvokeTransactionV1;
     use starknet_types::rpc::transactions::broadcasted_invoke_transaction_v3::BroadcastedInvokeTransactionV3;
     use starknet_types::rpc::transactions::{
-        BroadcastedTransactionCommonV3, ResourceBoundsWrapper,
+        BroadcastedInvokeTransaction, BroadcastedTransaction, BroadcastedTransactionCommonV3,
+        ResourceBoundsWrapper,
     };
     use starknet_types::traits::{HashProducer, ToDecimalString};
 
@@ -219,6 +258,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn estimate_of_invoke_transaction_v3_uses_the_overridden_strk_gas_price() {
+        let (starknet, account, contract_address, increase_balance_selector, _) = setup();
+        let invoke_transaction = BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V3(
+            test_invoke_transaction_v3(
+                account.get_address(),
+                contract_address,
+                increase_balance_selector,
+                Felt::from(10),
+                0,
+                10000,
+            ),
+        ));
+        let estimate = |block_override: BlockOverride| {
+            let estimates = starknet
+                .estimate_fee(
+                    &BlockId::Tag(BlockTag::Latest),
+                    &[invoke_transaction.clone()],
+                    &[],
+                    None,
+                    Some(&block_override),
+                )
+                .unwrap();
+            serde_json::to_value(&estimates[0]).unwrap()
+        };
+
+        let default_estimate = estimate(BlockOverride::default());
+        let estimate = estimate(BlockOverride {
+            eth_l1_gas_price: Some(Felt::from(3)),
+            strk_l1_gas_price: Some(Felt::from(7)),
+            ..Default::default()
+        });
+
+        assert_eq!(estimate["unit"], "FRI");
+        assert_eq!(estimate["gas_price"], "0x7");
+        assert_eq!(estimate["gas_consumed"], default_estimate["gas_consumed"]);
+        let gas_consumed =
+            u64::from_str_radix(&estimate["gas_consumed"].as_str().unwrap()[2..], 16).unwrap();
+        assert_eq!(estimate["overall_fee"], format!("{:#x}", gas_consumed * 7));
+    }
+
     #[test]
     fn invoke_transaction_successful_execution() {
         let (mut starknet, account, contract_address, increase_balance_selector, _) = setup();
@@ -344,6 +424,82 @@ mod tests {
                 panic!("Wrong error type: {:?}", err);
             }
         }
+        // resending doesn't reject the accepted transaction
+        assert_eq!(
+            starknet.get_transaction_status(transaction_hash).unwrap(),
+            TransactionStatus::AcceptedOnL2(TransactionExecutionStatus::Succeeded)
+        );
+    }
+
+    #[test]
+    fn invoke_transaction_with_invalid_nonce_is_kept_as_rejected() {
+        let (mut starknet, account, contract_address, increase_balance_selector, _) = setup();
+        let account_address = account.get_address();
+
+        let invoke_transaction = test_invoke_transaction_v1(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            Felt::from(10),
+            0,
+        );
+        starknet.add_invoke_transaction_v1(invoke_transaction).unwrap();
+
+        // a different transaction with the already used nonce
+        let invoke_transaction = test_invoke_transaction_v1(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            Felt::from(15),
+            0,
+        );
+        let transaction_hash = invoke_transaction
+            .create_blockifier_invoke_transaction(DEVNET_DEFAULT_CHAIN_ID.to_felt(), false)
+            .unwrap()
+            .tx_hash
+            .0
+            .into();
+        starknet.add_invoke_transaction_v1(invoke_transaction).unwrap_err();
+
+        assert_eq!(
+            starknet.get_transaction_status(transaction_hash).unwrap(),
+            TransactionStatus::Rejected
+        );
+        assert_eq!(
+            starknet.get_rejection_reason(&transaction_hash).unwrap(),
+            "Account transaction nonce is invalid."
+        );
+        assert!(starknet.transactions.get(&transaction_hash).is_none());
+    }
+
+    #[test]
+    fn invoke_transaction_with_max_fee_zero_is_kept_as_rejected() {
+        let (mut starknet, account, contract_address, increase_balance_selector, _) = setup();
+
+        let mut invoke_transaction = test_invoke_transaction_v1(
+            account.get_address(),
+            contract_address,
+            increase_balance_selector,
+            Felt::from(10),
+            0,
+        );
+        invoke_transaction.common.max_fee = Fee(0);
+        let transaction_hash = invoke_transaction
+            .create_blockifier_invoke_transaction(DEVNET_DEFAULT_CHAIN_ID.to_felt(), false)
+            .unwrap()
+            .tx_hash
+            .0
+            .into();
+        starknet.add_invoke_transaction_v1(invoke_transaction).unwrap_err();
+
+        assert_eq!(
+            starknet.get_transaction_status(transaction_hash).unwrap(),
+            TransactionStatus::Rejected
+        );
+        assert_eq!(
+            starknet.get_rejection_reason(&transaction_hash).unwrap(),
+            "invoke transaction: max_fee cannot be zero"
+        );
     }
 
     #[test]
@@ -380,6 +536,57 @@ mod tests {
         assert_eq!(nonce_after_reverted, Felt::from(1));
     }
 
+    #[test]
+    fn invoke_transaction_with_future_nonce_is_executed_once_the_gap_is_filled() {
+        let (
+            mut starknet,
+            account,
+            contract_address,
+            increase_balance_selector,
+            balance_var_storage_address,
+        ) = setup();
+        let account_address = account.get_address();
+
+        let future_invoke_transaction = test_invoke_transaction_v1(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            Felt::from(10),
+            1,
+        );
+        let future_transaction_hash =
+            starknet.add_invoke_transaction_v1(future_invoke_transaction).unwrap();
+        assert_eq!(
+            starknet.get_transaction_status(future_transaction_hash).unwrap(),
+            TransactionStatus::Received
+        );
+
+        // nothing is executed while the nonce 0 is missing
+        assert_eq!(starknet.get_mempool().iter().count(), 1);
+        assert_eq!(starknet.state.get_nonce(&account_address).unwrap(), Felt::from(0));
+        assert_eq!(starknet.state.get_storage(balance_var_storage_address).unwrap(), Felt::from(0));
+
+        let invoke_transaction = test_invoke_transaction_v1(
+            account_address,
+            contract_address,
+            increase_balance_selector,
+            Felt::from(15),
+            0,
+        );
+        starknet.add_invoke_transaction_v1(invoke_transaction).unwrap();
+
+        assert!(starknet.get_mempool().iter().next().is_none());
+        assert_eq!(starknet.state.get_nonce(&account_address).unwrap(), Felt::from(2));
+        assert_eq!(
+            starknet.state.get_storage(balance_var_storage_address).unwrap(),
+            Felt::from(25)
+        );
+        assert_eq!(
+            starknet.get_transaction_status(future_transaction_hash).unwrap(),
+            TransactionStatus::AcceptedOnL2(TransactionExecutionStatus::Succeeded)
+        );
+    }
+
     /// Initialize starknet object with: erc20 contract, account contract and  simple contract that
     /// has a function increase_balance
     fn setup() -> (Starknet, Account, ContractAddress, Felt, ContractStorageKey) {
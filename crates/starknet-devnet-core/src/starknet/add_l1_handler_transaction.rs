@@ -5,6 +5,7 @@ use tracing::trace;
 
 use super::Starknet;
 use crate::error::DevnetResult;
+use crate::metrics::{time_execution, ExecutionKind};
 use crate::starknet::dump::DumpEvent;
 
 pub fn add_l1_handler_transaction(
@@ -22,12 +23,14 @@ pub fn add_l1_handler_transaction(
     let charge_fee = false;
     let validate = true;
 
-    let blockifier_execution_result = blockifier_transaction.execute(
-        &mut starknet.state.state,
-        &starknet.block_context,
-        charge_fee,
-        validate,
-    );
+    let blockifier_execution_result = time_execution(ExecutionKind::Transaction, || {
+        blockifier_transaction.execute(
+            &mut starknet.state.state,
+            &starknet.block_context,
+            charge_fee,
+            validate,
+        )
+    });
 
     starknet.handle_transaction_result(
         Transaction::L1Handler(transaction.clone()),
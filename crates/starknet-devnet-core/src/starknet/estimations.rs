@@ -1,17 +1,21 @@
 use blockifier::fee::fee_utils::{calculate_l1_gas_by_vm_usage, extract_l1_gas_and_vm_usage};
+use blockifier::state::cached_state::CachedState;
 use blockifier::transaction::account_transaction::AccountTransaction;
 use blockifier::transaction::objects::HasRelatedFeeType;
 use blockifier::transaction::transactions::ExecutableTransaction;
 use starknet_rs_core::types::{BlockId, MsgFromL1};
 use starknet_types::contract_address::ContractAddress;
+use starknet_types::rpc::block_override::BlockOverride;
 use starknet_types::rpc::estimate_message_fee::{
     EstimateMessageFeeRequestWrapper, FeeEstimateWrapper,
 };
+use starknet_types::rpc::state_override::StateOverride;
 use starknet_types::rpc::transactions::BroadcastedTransaction;
 
 use crate::error::{DevnetResult, Error};
+use crate::metrics::{time_execution, ExecutionKind};
 use crate::starknet::Starknet;
-use crate::state::StarknetState;
+use crate::state::state_overlay::StateOverlay;
 
 pub fn estimate_fee(
     starknet: &Starknet,
@@ -19,8 +23,14 @@ pub fn estimate_fee(
     transactions: &[BroadcastedTransaction],
     charge_fee: Option<bool>,
     validate: Option<bool>,
+    state_override: Option<&StateOverride>,
+    block_override: Option<&BlockOverride>,
 ) -> DevnetResult<Vec<FeeEstimateWrapper>> {
-    let mut state = starknet.get_state_at(block_id)?.clone();
+    let mut state = starknet.get_state_at(block_id)?.overlay();
+    if let Some(state_override) = state_override {
+        state.state.apply_state_override(state_override)?;
+    }
+    let block_context = starknet.block_context_with_override(block_override)?;
     let chain_id = starknet.chain_id().to_felt();
 
     let transactions = transactions
@@ -33,7 +43,7 @@ pub fn estimate_fee(
         .map(|transaction| {
             estimate_transaction_fee(
                 &mut state,
-                &starknet.block_context,
+                &block_context,
                 blockifier::transaction::transaction_execution::Transaction::AccountTransaction(
                     transaction,
                 ),
@@ -50,7 +60,7 @@ pub fn estimate_message_fee(
     message: MsgFromL1,
 ) -> DevnetResult<FeeEstimateWrapper> {
     let estimate_message_fee = EstimateMessageFeeRequestWrapper::new(*block_id, message);
-    let mut state = starknet.get_state_at(block_id)?.clone();
+    let mut state = starknet.get_state_at(block_id)?.overlay();
 
     match starknet
         .get_class_hash_at(block_id, ContractAddress::new(estimate_message_fee.get_to_address())?)
@@ -73,7 +83,7 @@ pub fn estimate_message_fee(
 }
 
 fn estimate_transaction_fee(
-    state: &mut StarknetState,
+    state: &mut CachedState<StateOverlay<'_>>,
     block_context: &blockifier::block_context::BlockContext,
     transaction: blockifier::transaction::transaction_execution::Transaction,
     charge_fee: Option<bool>,
@@ -88,12 +98,14 @@ fn estimate_transaction_fee(
         ) => tx.fee_type(),
     };
 
-    let transaction_execution_info = transaction.execute(
-        &mut state.state,
-        block_context,
-        charge_fee.unwrap_or(false),
-        validate.unwrap_or(true),
-    )?;
+    let transaction_execution_info = time_execution(ExecutionKind::Estimation, || {
+        transaction.execute(
+            state,
+            block_context,
+            charge_fee.unwrap_or(false),
+            validate.unwrap_or(true),
+        )
+    })?;
 
     if let Some(revert_error) = transaction_execution_info.revert_error {
         return Err(Error::ExecutionError { revert_error });
@@ -105,14 +117,14 @@ fn estimate_transaction_fee(
     let total_l1_gas_usage = l1_gas_usage as f64 + l1_gas_by_vm_usage;
     let total_l1_gas_usage = total_l1_gas_usage.ceil() as u64;
 
-    let gas_price = block_context.gas_prices.eth_l1_gas_price as u64;
-
     Ok(match fee_type {
-        blockifier::transaction::objects::FeeType::Strk => {
-            FeeEstimateWrapper::new_in_strk_units(total_l1_gas_usage, gas_price)
-        }
-        blockifier::transaction::objects::FeeType::Eth => {
-            FeeEstimateWrapper::new_in_wei_units(total_l1_gas_usage, gas_price)
-        }
+        blockifier::transaction::objects::FeeType::Strk => FeeEstimateWrapper::new_in_strk_units(
+            total_l1_gas_usage,
+            block_context.gas_prices.strk_l1_gas_price as u64,
+        ),
+        blockifier::transaction::objects::FeeType::Eth => FeeEstimateWrapper::new_in_wei_units(
+            total_l1_gas_usage,
+            block_context.gas_prices.eth_l1_gas_price as u64,
+        ),
     })
 }
@@ -1,14 +1,231 @@
-use starknet_rs_core::types::BlockId;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use starknet_api::block::{BlockNumber, BlockStatus};
+use starknet_rs_core::types::{BlockId, BlockTag};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::emitted_event::{EmittedEvent, Event};
-use starknet_types::felt::Felt;
+use starknet_types::felt::{BlockHash, Felt, TransactionHash};
+use tracing::error;
 
 use super::Starknet;
+use crate::blocks::StarknetBlock;
 use crate::error::{DevnetResult, Error};
 use crate::traits::HashIdentified;
+use crate::transactions::StarknetTransactions;
+
+/// Position of the next event to be returned by a paginated events query. Positions are based on
+/// the block number, the index of the transaction in the block and the index of the event in the
+/// transaction, so they are not affected by blocks added after the token was issued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventsContinuationToken {
+    pub block_number: u64,
+    pub transaction_index: usize,
+    pub event_index: usize,
+}
+
+impl EventsContinuationToken {
+    /// Checks if the token points past the given position, i.e. the event at the position was
+    /// already processed
+    fn is_after(&self, block_number: u64, transaction_index: usize, event_index: usize) -> bool {
+        (block_number, transaction_index, event_index)
+            < (self.block_number, self.transaction_index, self.event_index)
+    }
+}
+
+impl Display for EventsContinuationToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}-{}", self.block_number, self.transaction_index, self.event_index)
+    }
+}
 
-/// The method returns transaction events, based on query and if there are more results to be
-/// fetched in the form of a tuple (events, has_more).
+impl FromStr for EventsContinuationToken {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split('-').collect::<Vec<&str>>();
+        match parts.as_slice() {
+            [block_number, transaction_index, event_index] => Ok(Self {
+                block_number: block_number.parse().map_err(|_| Error::FormatError)?,
+                transaction_index: transaction_index.parse().map_err(|_| Error::FormatError)?,
+                event_index: event_index.parse().map_err(|_| Error::FormatError)?,
+            }),
+            _ => Err(Error::FormatError),
+        }
+    }
+}
+
+/// Event of a transaction together with its position in the chain
+#[derive(Debug, Clone)]
+struct IndexedEvent {
+    block_number: BlockNumber,
+    block_hash: Option<BlockHash>,
+    transaction_index: usize,
+    event_index: usize,
+    transaction_hash: TransactionHash,
+    event: Event,
+}
+
+impl IndexedEvent {
+    fn position(&self) -> (u64, usize, usize) {
+        (self.block_number.0, self.transaction_index, self.event_index)
+    }
+}
+
+impl From<&IndexedEvent> for EmittedEvent {
+    fn from(indexed_event: &IndexedEvent) -> Self {
+        // the pending block has no hash, so is the block number omitted for its events
+        let block_number = indexed_event.block_hash.map(|_| indexed_event.block_number);
+
+        Self {
+            transaction_hash: indexed_event.transaction_hash,
+            block_hash: indexed_event.block_hash,
+            block_number,
+            keys: indexed_event.event.keys.clone(),
+            from_address: indexed_event.event.from_address,
+            data: indexed_event.event.data.clone(),
+        }
+    }
+}
+
+/// Append-only index of the events of sealed blocks. Events are stored in the order of their
+/// position, and are additionally referenced by the emitting address and by the first key, so
+/// filtered queries only visit the events that can match the filter.
+#[derive(Default)]
+pub(crate) struct EventIndex {
+    events: Vec<IndexedEvent>,
+    by_address: HashMap<ContractAddress, Vec<usize>>,
+    by_first_key: HashMap<Felt, Vec<usize>>,
+    /// events without keys match every keys filter
+    without_keys: Vec<usize>,
+}
+
+impl EventIndex {
+    /// Appends the events of a sealed block to the index. Blocks have to be indexed in ascending
+    /// order of their block numbers.
+    pub(crate) fn index_block(
+        &mut self,
+        block: &StarknetBlock,
+        transactions: &StarknetTransactions,
+    ) {
+        for indexed_event in collect_block_events(block, transactions) {
+            let position = self.events.len();
+
+            self.by_address.entry(indexed_event.event.from_address).or_default().push(position);
+            match indexed_event.event.keys.first() {
+                Some(first_key) => self.by_first_key.entry(*first_key).or_default().push(position),
+                None => self.without_keys.push(position),
+            }
+
+            self.events.push(indexed_event);
+        }
+    }
+
+    /// Returns the events, starting from the `start` position up to and including the block
+    /// `to_block`, that are candidates for matching the address and the first key of the filter.
+    /// The events are returned in the order of their position.
+    fn candidates<'a>(
+        &'a self,
+        start: (u64, usize, usize),
+        to_block: Option<u64>,
+        address: &Option<ContractAddress>,
+        keys_filter: &Option<Vec<Vec<Felt>>>,
+    ) -> Box<dyn Iterator<Item = &'a IndexedEvent> + 'a> {
+        let start_idx = self.events.partition_point(|event| event.position() < start);
+        let end_idx = match to_block {
+            Some(to_block) => self.events.partition_point(|event| event.block_number.0 <= to_block),
+            None => self.events.len(),
+        };
+
+        if start_idx >= end_idx {
+            return Box::new(std::iter::empty());
+        }
+
+        // restricts the sorted positions to the ones in the requested range
+        let in_range = |positions: &'a [usize]| -> &'a [usize] {
+            let from = positions.partition_point(|position| *position < start_idx);
+            let to = positions.partition_point(|position| *position < end_idx);
+            &positions[from..to]
+        };
+
+        let by_address = address.map(|address| match self.by_address.get(&address) {
+            Some(positions) => in_range(positions),
+            None => &[],
+        });
+
+        let by_first_key = match keys_filter.as_ref().and_then(|keys_filter| keys_filter.first()) {
+            Some(first_keys) if !first_keys.is_empty() => {
+                let mut positions = in_range(&self.without_keys).to_vec();
+                let mut first_keys = first_keys.clone();
+                first_keys.sort();
+                first_keys.dedup();
+                for first_key in first_keys {
+                    if let Some(key_positions) = self.by_first_key.get(&first_key) {
+                        positions.extend_from_slice(in_range(key_positions));
+                    }
+                }
+                positions.sort_unstable();
+                Some(positions)
+            }
+            _ => None,
+        };
+
+        // both lists are supersets of the matching events, so the shorter one is used
+        match (by_address, by_first_key) {
+            (Some(by_address), Some(by_first_key)) if by_first_key.len() < by_address.len() => {
+                Box::new(by_first_key.into_iter().map(|position| &self.events[position]))
+            }
+            (Some(by_address), _) => {
+                Box::new(by_address.iter().map(|position| &self.events[*position]))
+            }
+            (None, Some(by_first_key)) => {
+                Box::new(by_first_key.into_iter().map(|position| &self.events[position]))
+            }
+            (None, None) => Box::new(self.events[start_idx..end_idx].iter()),
+        }
+    }
+}
+
+/// Collects the events of all transactions of the block in the order of their position
+fn collect_block_events(
+    block: &StarknetBlock,
+    transactions: &StarknetTransactions,
+) -> Vec<IndexedEvent> {
+    let block_hash = match block.status() {
+        BlockStatus::Pending => None,
+        _ => Some(block.block_hash()),
+    };
+
+    let mut events = vec![];
+    for (transaction_index, transaction_hash) in block.get_transactions().iter().enumerate() {
+        let transaction = match transactions.get_by_hash(*transaction_hash) {
+            Some(transaction) => transaction,
+            None => {
+                error!("Transaction is not present in the transactions collection");
+                continue;
+            }
+        };
+
+        for (event_index, event) in transaction.get_events().into_iter().enumerate() {
+            events.push(IndexedEvent {
+                block_number: block.block_number(),
+                block_hash,
+                transaction_index,
+                event_index,
+                transaction_hash: *transaction_hash,
+                event,
+            });
+        }
+    }
+
+    events
+}
+
+/// The method returns transaction events, based on query and the continuation token of the next
+/// event, if there are more results to be fetched, in the form of a tuple (events,
+/// continuation_token). Events of sealed blocks are read from the event index, only the events of
+/// the pending block are collected from its transactions.
 ///
 /// # Arguments
 ///
@@ -16,7 +233,7 @@ use crate::traits::HashIdentified;
 /// * `to_block` - Optional. The block id to end the query at.
 /// * `contract_address` - Optional. The contract address to filter the events by.
 /// * `keys_filter` - Optional. The keys to filter the events by.
-/// * `skip` - The number of elements to skip.
+/// * `continuation_token` - Optional. The position from which to resume the query.
 /// * `limit` - Optional. The maximum number of elements to return.
 pub(crate) fn get_events(
     starknet: &Starknet,
@@ -24,61 +241,62 @@ pub(crate) fn get_events(
     to_block: Option<BlockId>,
     contract_address: Option<ContractAddress>,
     keys_filter: Option<Vec<Vec<Felt>>>,
-    mut skip: usize,
+    continuation_token: Option<EventsContinuationToken>,
     limit: Option<usize>,
-) -> DevnetResult<(Vec<EmittedEvent>, bool)> {
-    let blocks = starknet.blocks.get_blocks(from_block, to_block)?;
+) -> DevnetResult<(Vec<EmittedEvent>, Option<EventsContinuationToken>)> {
+    let block_number_of = |block_id: BlockId| {
+        starknet.blocks.block_number_from_block_id(&block_id).ok_or(Error::NoBlock)
+    };
+    let from_block_number = from_block.map(block_number_of).transpose()?.unwrap_or_default();
+    let to_block_number = to_block.map(block_number_of).transpose()?;
+
+    let start = match continuation_token {
+        Some(token) if token.is_after(from_block_number.0, 0, 0) => {
+            (token.block_number, token.transaction_index, token.event_index)
+        }
+        _ => (from_block_number.0, 0, 0),
+    };
+
+    // the pending block is not part of the sealed blocks, so it is included only if the range
+    // ends with it
+    let pending_events = match to_block {
+        Some(BlockId::Tag(BlockTag::Pending)) => {
+            collect_block_events(&starknet.blocks.pending_block, &starknet.transactions)
+        }
+        _ => vec![],
+    };
+
+    let candidates = starknet
+        .event_index
+        .candidates(
+            start,
+            to_block_number.map(|block_number| block_number.0),
+            &contract_address,
+            &keys_filter,
+        )
+        .chain(pending_events.iter().filter(|event| event.position() >= start));
+
     let mut events: Vec<EmittedEvent> = Vec::new();
-    let mut elements_added = 0;
-
-    // iterate over each block and get the transactions for each one
-    // then iterate over each transaction events and filter them
-    for block in blocks {
-        for transaction_hash in block.get_transactions() {
-            let transaction =
-                starknet.transactions.get_by_hash(*transaction_hash).ok_or(Error::NoTransaction)?;
-
-            // filter the events from the transaction
-            let filtered_transaction_events = transaction
-                .get_events()
-                .into_iter()
-                .filter(|event| {
-                    check_if_filter_applies_for_event(&contract_address, &keys_filter, event)
-                })
-                .skip_while(|_| {
-                    if skip > 0 {
-                        skip -= 1;
-                        true
-                    } else {
-                        false
-                    }
-                });
-
-            // produce an emitted event for each filtered transaction event
-            for transaction_event in filtered_transaction_events {
-                // check if there are more elements to fetch
-                if let Some(limit) = limit {
-                    if elements_added == limit {
-                        return Ok((events, true));
-                    }
-                }
+    for indexed_event in candidates {
+        if !check_if_filter_applies_for_event(&contract_address, &keys_filter, &indexed_event.event)
+        {
+            continue;
+        }
 
-                let emitted_event = EmittedEvent {
-                    transaction_hash: *transaction_hash,
-                    block_hash: block.block_hash(),
-                    block_number: block.block_number(),
-                    keys: transaction_event.keys,
-                    from_address: transaction_event.from_address,
-                    data: transaction_event.data,
-                };
-
-                events.push(emitted_event);
-                elements_added += 1;
+        // check if there are more elements to fetch
+        if let Some(limit) = limit {
+            if events.len() == limit {
+                let (block_number, transaction_index, event_index) = indexed_event.position();
+                let next_token =
+                    EventsContinuationToken { block_number, transaction_index, event_index };
+                return Ok((events, Some(next_token)));
             }
         }
+
+        events.push(EmittedEvent::from(indexed_event));
     }
 
-    Ok((events, false))
+    Ok((events, None))
 }
 
 /// This method checks if the event applies to the provided filters and returns true or false
@@ -132,7 +350,7 @@ mod tests {
     use starknet_types::felt::Felt;
     use starknet_types::rpc::transactions::{DeclareTransaction, Transaction};
 
-    use super::{check_if_filter_applies_for_event, get_events};
+    use super::{check_if_filter_applies_for_event, get_events, EventsContinuationToken};
     use crate::starknet::events::check_if_filter_applies_for_event_keys;
     use crate::starknet::starknet_config::StarknetConfig;
     use crate::starknet::Starknet;
@@ -283,50 +501,54 @@ mod tests {
         let starknet = setup();
 
         // no pagination to the latest block events
-        let (events, has_more) = get_events(
+        let (events, continuation_token) = get_events(
             &starknet,
             Some(BlockId::Tag(starknet_rs_core::types::BlockTag::Latest)),
             Some(BlockId::Tag(starknet_rs_core::types::BlockTag::Latest)),
             None,
             None,
-            0,
+            None,
             None,
         )
         .unwrap();
 
         assert_eq!(events.len(), 5);
-        assert!(!has_more);
+        assert!(continuation_token.is_none());
 
         // limit the result to only events, should be left 2 more
-        let (events, has_more) = get_events(
+        let (events, continuation_token) = get_events(
             &starknet,
             Some(BlockId::Tag(starknet_rs_core::types::BlockTag::Latest)),
             Some(BlockId::Tag(starknet_rs_core::types::BlockTag::Latest)),
             None,
             None,
-            0,
+            None,
             Some(3),
         )
         .unwrap();
 
         assert_eq!(events.len(), 3);
-        assert!(has_more);
-
-        // skip 3 events and return maximum 3, but the result should be 2, because the total amount
-        // of events for the latest block is 5
-        let (events, has_more) = get_events(
+        assert_eq!(
+            continuation_token,
+            Some(EventsContinuationToken { block_number: 4, transaction_index: 0, event_index: 3 })
+        );
+
+        // continue after the 3 returned events and return maximum 3, but the result should be 2,
+        // because the total amount of events for the latest block is 5
+        let (events, continuation_token) = get_events(
             &starknet,
             Some(BlockId::Tag(starknet_rs_core::types::BlockTag::Latest)),
             Some(BlockId::Tag(starknet_rs_core::types::BlockTag::Latest)),
             None,
             None,
-            3,
+            continuation_token,
             Some(3),
         )
         .unwrap();
 
         assert_eq!(events.len(), 2);
-        assert!(!has_more);
+        assert_eq!(events[0].keys[0], Felt::from(14));
+        assert!(continuation_token.is_none());
     }
 
     #[test]
@@ -334,29 +556,140 @@ mod tests {
         let starknet = setup();
 
         // returns all events from all blocks
-        let (events, has_more) = get_events(&starknet, None, None, None, None, 0, None).unwrap();
+        let (events, continuation_token) =
+            get_events(&starknet, None, None, None, None, None, None).unwrap();
 
         assert_eq!(events.len(), 15);
-        assert!(!has_more);
+        assert!(continuation_token.is_none());
 
-        // returns all events from all blocks, skip 3, but limit the result to 10
-        let (events, has_more) =
-            get_events(&starknet, None, None, None, None, 3, Some(10)).unwrap();
+        // returns the events of the first 2 blocks, the next event is the first one of block 2
+        let (events, continuation_token) =
+            get_events(&starknet, None, None, None, None, None, Some(3)).unwrap();
+        assert_eq!(events.len(), 3);
+        let continuation_token = continuation_token.unwrap();
+        assert_eq!(
+            continuation_token,
+            EventsContinuationToken { block_number: 2, transaction_index: 0, event_index: 0 }
+        );
+
+        // continues from block 2, but limits the result to 10
+        let (events, next_continuation_token) =
+            get_events(&starknet, None, None, None, None, Some(continuation_token), Some(10))
+                .unwrap();
         assert_eq!(events.len(), 10);
-        assert!(has_more);
+        assert_eq!(events[0].transaction_hash, Felt::from(102));
+        assert!(next_continuation_token.is_some());
 
-        // returns all events from the first 2 blocks, skip 3, but limit the result to 1
-        let (events, has_more) =
-            get_events(&starknet, None, Some(BlockId::Number(1)), None, None, 3, Some(1)).unwrap();
+        // continues from block 2, but the query ends at block 1
+        let (events, next_continuation_token) = get_events(
+            &starknet,
+            None,
+            Some(BlockId::Number(1)),
+            None,
+            None,
+            Some(continuation_token),
+            Some(1),
+        )
+        .unwrap();
         assert_eq!(events.len(), 0);
-        assert!(!has_more);
+        assert!(next_continuation_token.is_none());
+    }
 
-        // returns all events from the first 2 blocks, skip 1, but limit the result to 1, it should
-        // return 1 event and should be more
-        let (events, has_more) =
-            get_events(&starknet, None, Some(BlockId::Number(1)), None, None, 1, Some(1)).unwrap();
-        assert_eq!(events.len(), 1);
-        assert!(has_more);
+    #[test]
+    fn continuation_token_is_not_affected_by_new_blocks() {
+        let mut starknet = setup();
+
+        let (_, continuation_token) =
+            get_events(&starknet, None, None, None, None, None, Some(12)).unwrap();
+        let continuation_token = continuation_token.unwrap();
+
+        add_transaction_with_events(&mut starknet, 105, 6);
+
+        let (events, next_continuation_token) =
+            get_events(&starknet, None, None, None, None, Some(continuation_token), Some(3))
+                .unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].transaction_hash, Felt::from(104));
+        assert_eq!(events[0].keys[0], Felt::from(13));
+        assert_eq!(
+            next_continuation_token,
+            Some(EventsContinuationToken { block_number: 5, transaction_index: 0, event_index: 0 })
+        );
+    }
+
+    #[test]
+    fn continuation_token_is_resumed_with_filter() {
+        let starknet = setup();
+
+        // only the first event of each transaction has key 11
+        let keys_filter = Some(vec![vec![Felt::from(11)]]);
+        let (events, continuation_token) =
+            get_events(&starknet, None, None, None, keys_filter.clone(), None, Some(2)).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            continuation_token,
+            Some(EventsContinuationToken { block_number: 2, transaction_index: 0, event_index: 0 })
+        );
+
+        let (events, continuation_token) =
+            get_events(&starknet, None, None, None, keys_filter, continuation_token, Some(5))
+                .unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(continuation_token.is_none());
+    }
+
+    #[test]
+    fn event_index_returns_only_candidates_of_the_filter() {
+        let starknet = setup();
+
+        // only the first event of each transaction has key 11
+        let keys_filter = Some(vec![vec![Felt::from(11)]]);
+        assert_eq!(
+            starknet.event_index.candidates((0, 0, 0), None, &None, &keys_filter).count(),
+            5
+        );
+
+        // none of the events is emitted by this address
+        let address = Some(ContractAddress::new(Felt::from(1)).unwrap());
+        assert_eq!(starknet.event_index.candidates((0, 0, 0), None, &address, &None).count(), 0);
+
+        // the last 2 events of block 2 and all 4 events of block 3
+        assert_eq!(starknet.event_index.candidates((2, 0, 1), Some(3), &None, &None).count(), 6);
+    }
+
+    #[test]
+    fn continuation_token_conversion() {
+        let token =
+            EventsContinuationToken { block_number: 12, transaction_index: 3, event_index: 7 };
+        assert_eq!(token.to_string(), "12-3-7");
+        assert_eq!("12-3-7".parse::<EventsContinuationToken>().unwrap(), token);
+
+        for invalid_token in ["", "12", "12-3", "12-3-7-1", "0x1-0-0", "a-b-c", "-1-0-0"] {
+            assert!(invalid_token.parse::<EventsContinuationToken>().is_err());
+        }
+    }
+
+    #[test]
+    fn events_of_pending_block_are_returned_without_block_hash_and_number() {
+        let mut starknet = setup();
+        // accepted, but not yet sealed transaction with 5 events
+        starknet.blocks.pending_block.add_transaction(Felt::from(104));
+
+        let pending = Some(BlockId::Tag(starknet_rs_core::types::BlockTag::Pending));
+        let (events, _) =
+            get_events(&starknet, pending, pending, None, None, None, None).unwrap();
+        assert_eq!(events.len(), 5);
+        for event in events {
+            assert_eq!(event.transaction_hash, Felt::from(104));
+            assert!(event.block_hash.is_none());
+            assert!(event.block_number.is_none());
+        }
+
+        // events up to latest don't include the pending block
+        let latest = Some(BlockId::Tag(starknet_rs_core::types::BlockTag::Latest));
+        let (events, _) = get_events(&starknet, None, latest, None, None, None, None).unwrap();
+        assert_eq!(events.len(), 15);
+        assert!(events.iter().all(|event| event.block_hash.is_some()));
     }
 
     #[test]
@@ -365,7 +698,7 @@ mod tests {
 
         // events with key 15 should be only 1 in the 5th transaction
         let (events, _) =
-            get_events(&starknet, None, None, None, Some(vec![vec![Felt::from(15)]]), 0, None)
+            get_events(&starknet, None, None, None, Some(vec![vec![Felt::from(15)]]), None, None)
                 .unwrap();
 
         assert_eq!(events.len(), 1);
@@ -375,7 +708,7 @@ mod tests {
         assert_eq!(events[0].data[0], Felt::from(25));
 
         let (events, _) =
-            get_events(&starknet, None, None, None, Some(vec![vec![Felt::from(12)]]), 0, None)
+            get_events(&starknet, None, None, None, Some(vec![vec![Felt::from(12)]]), None, None)
                 .unwrap();
 
         assert_eq!(events.len(), 4);
@@ -395,18 +728,7 @@ mod tests {
         let mut starknet = Starknet::new(&StarknetConfig::default()).unwrap();
 
         for idx in 0..5 {
-            let transaction =
-                Transaction::Declare(DeclareTransaction::Version1(dummy_declare_transaction_v1()));
-
-            let txn_info = blockifier::transaction::objects::TransactionExecutionInfo {
-                execute_call_info: Some(dummy_call_info(idx + 1)),
-                ..Default::default()
-            };
-            let transaction_hash = Felt::from(idx as u128 + 100);
-
-            starknet
-                .handle_accepted_transaction(&transaction_hash, &transaction, txn_info)
-                .unwrap();
+            add_transaction_with_events(&mut starknet, idx as u128 + 100, idx + 1);
         }
 
         assert_eq!(starknet.blocks.get_blocks(None, None).unwrap().len(), 5);
@@ -417,6 +739,24 @@ mod tests {
         starknet
     }
 
+    fn add_transaction_with_events(
+        starknet: &mut Starknet,
+        transaction_hash: u128,
+        events_count: usize,
+    ) {
+        let transaction =
+            Transaction::Declare(DeclareTransaction::Version1(dummy_declare_transaction_v1()));
+
+        let txn_info = blockifier::transaction::objects::TransactionExecutionInfo {
+            execute_call_info: Some(dummy_call_info(events_count)),
+            ..Default::default()
+        };
+
+        starknet
+            .handle_accepted_transaction(&Felt::from(transaction_hash), &transaction, txn_info)
+            .unwrap();
+    }
+
     fn dummy_call_info(events_count: usize) -> CallInfo {
         let mut call_info = CallInfo::default();
 
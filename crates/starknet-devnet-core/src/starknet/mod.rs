@@ -1,16 +1,17 @@
 use std::collections::HashMap;
 
 use blockifier::block_context::BlockContext;
+use blockifier::execution::call_info::CallInfo;
 use blockifier::execution::entry_point::CallEntryPoint;
+use blockifier::state::cached_state::CachedState;
 use blockifier::state::state_api::StateReader;
 use blockifier::transaction::errors::TransactionPreValidationError;
 use blockifier::transaction::objects::TransactionExecutionInfo;
 use blockifier::transaction::transactions::ExecutableTransaction;
+use indexmap::IndexMap;
 use starknet_api::block::{BlockNumber, BlockStatus, BlockTimestamp, GasPrice};
 use starknet_api::transaction::Fee;
-use starknet_rs_core::types::{
-    BlockId, MsgFromL1, TransactionExecutionStatus, TransactionFinalityStatus,
-};
+use starknet_rs_core::types::{BlockId, MsgFromL1, TransactionFinalityStatus, TransactionStatus};
 use starknet_rs_core::utils::get_selector_from_name;
 use starknet_rs_ff::FieldElement;
 use starknet_rs_signers::Signer;
@@ -27,8 +28,11 @@ use starknet_types::emitted_event::EmittedEvent;
 use starknet_types::felt::{ClassHash, Felt, TransactionHash};
 use starknet_types::patricia_key::PatriciaKey;
 use starknet_types::rpc::block::{Block, BlockHeader};
+use starknet_types::rpc::block_override::BlockOverride;
 use starknet_types::rpc::estimate_message_fee::FeeEstimateWrapper;
+use starknet_types::rpc::execution_profile::ExecutionProfile;
 use starknet_types::rpc::state::ThinStateDiff;
+use starknet_types::rpc::state_override::StateOverride;
 use starknet_types::rpc::transaction_receipt::TransactionReceipt;
 use starknet_types::rpc::transactions::broadcasted_declare_transaction_v1::BroadcastedDeclareTransactionV1;
 use starknet_types::rpc::transactions::broadcasted_declare_transaction_v2::BroadcastedDeclareTransactionV2;
@@ -38,29 +42,36 @@ use starknet_types::rpc::transactions::broadcasted_deploy_account_transaction_v3
 use starknet_types::rpc::transactions::broadcasted_invoke_transaction_v1::BroadcastedInvokeTransactionV1;
 use starknet_types::rpc::transactions::broadcasted_invoke_transaction_v3::BroadcastedInvokeTransactionV3;
 use starknet_types::rpc::transactions::{
-    BlockTransactionTrace, BlockTransactionTraces, BroadcastedTransaction,
-    BroadcastedTransactionCommon, DeclareTransaction, L1HandlerTransaction, SimulatedTransaction,
-    SimulationFlag, Transaction, TransactionTrace, Transactions,
+    BlockTransactionTrace, BlockTransactionTraces, BroadcastedDeclareTransaction,
+    BroadcastedInvokeTransaction, BroadcastedTransaction, BroadcastedTransactionCommon,
+    DeclareTransaction, FunctionCall, FunctionInvocation, L1HandlerTransaction,
+    SimulatedTransaction, SimulationFlag, Transaction, TransactionTrace, Transactions,
 };
-use starknet_types::traits::HashProducer;
+use starknet_types::traits::{HashProducer, ToHexString};
 use tracing::{error, info};
 
 use self::dump::DumpEvent;
+use self::events::{EventIndex, EventsContinuationToken};
 use self::predeployed::initialize_erc20_at_address;
 use self::starknet_config::{DumpOn, StarknetConfig, StateArchiveCapacity};
 use self::transaction_trace::create_trace;
+use crate::abi_decoder;
 use crate::account::Account;
 use crate::blocks::{StarknetBlock, StarknetBlocks};
 use crate::constants::{
     CHARGEABLE_ACCOUNT_ADDRESS, CHARGEABLE_ACCOUNT_PRIVATE_KEY, DEVNET_DEFAULT_CHAIN_ID,
-    ETH_ERC20_CONTRACT_ADDRESS, ETH_ERC20_NAME, ETH_ERC20_SYMBOL, STRK_ERC20_CONTRACT_ADDRESS,
-    STRK_ERC20_NAME, STRK_ERC20_SYMBOL,
+    ETH_ERC20_CONTRACT_ADDRESS, ETH_ERC20_NAME, ETH_ERC20_SYMBOL, REJECTED_TRANSACTIONS_CAPACITY,
+    STRK_ERC20_CONTRACT_ADDRESS, STRK_ERC20_NAME, STRK_ERC20_SYMBOL,
 };
 use crate::error::{DevnetResult, Error, TransactionValidationError};
+use crate::mempool::{Mempool, MempoolTransaction};
 use crate::messaging::MessagingBroker;
+use crate::metrics::{self, time_execution, ExecutionKind};
 use crate::predeployed_accounts::PredeployedAccounts;
+use crate::profiler;
 use crate::raw_execution::{Call, RawExecution};
 use crate::state::state_diff::StateDiff;
+use crate::state::state_overlay::StateOverlay;
 use crate::state::state_update::StateUpdate;
 use crate::state::StarknetState;
 use crate::traits::{
@@ -75,7 +86,7 @@ mod add_invoke_transaction;
 mod add_l1_handler_transaction;
 pub mod dump;
 mod estimations;
-mod events;
+pub mod events;
 mod get_class_impls;
 mod predeployed;
 pub mod starknet_config;
@@ -89,7 +100,12 @@ pub struct Starknet {
     // To avoid repeating some logic related to blocks,
     // having `blocks` public allows to re-use functions like `get_blocks()`.
     pub(crate) blocks: StarknetBlocks,
+    pub(in crate::starknet) event_index: EventIndex,
     pub transactions: StarknetTransactions,
+    /// Transactions waiting for the nonce of their sender to catch up
+    mempool: Mempool,
+    /// The reasons of the latest rejections, by transaction hash
+    rejected_transactions: IndexMap<TransactionHash, String>,
     pub config: StarknetConfig,
     pub pending_block_timestamp_shift: i64,
     pub(crate) messaging: MessagingBroker,
@@ -108,7 +124,10 @@ impl Default for Starknet {
             state: Default::default(),
             predeployed_accounts: Default::default(),
             blocks: Default::default(),
+            event_index: Default::default(),
             transactions: Default::default(),
+            mempool: Default::default(),
+            rejected_transactions: Default::default(),
             config: Default::default(),
             pending_block_timestamp_shift: 0,
             messaging: Default::default(),
@@ -182,7 +201,10 @@ impl Starknet {
                 config.chain_id,
             ),
             blocks: StarknetBlocks::default(),
+            event_index: EventIndex::default(),
             transactions: StarknetTransactions::default(),
+            mempool: Mempool::default(),
+            rejected_transactions: IndexMap::new(),
             config: config.clone(),
             pending_block_timestamp_shift: 0,
             messaging: Default::default(),
@@ -204,6 +226,21 @@ impl Starknet {
         Ok(this)
     }
 
+    /// Updates the metrics describing the size of the current state and of the state archive
+    pub fn update_state_metrics(&self) {
+        let metrics = metrics::metrics();
+        metrics.state_storage_entries.set(self.state.state.state.address_to_storage.len() as i64);
+
+        let mut class_sizes = HashMap::new();
+        let state_archive_bytes: usize = self
+            .blocks
+            .num_to_state
+            .values()
+            .map(|state| state.estimated_size(&mut class_sizes))
+            .sum();
+        metrics.state_archive_bytes.set(state_archive_bytes as i64);
+    }
+
     pub fn restart(&mut self) -> DevnetResult<()> {
         self.config.re_execute_on_init = false;
         *self = Starknet::new(&self.config)?;
@@ -242,10 +279,7 @@ impl Starknet {
         // set block timestamp and context block timestamp for contract execution
         let block_timestamp = match timestamp {
             Some(timestamp) => BlockTimestamp(timestamp),
-            None => BlockTimestamp(
-                (Starknet::get_unix_timestamp_as_seconds() as i64
-                    + self.pending_block_timestamp_shift) as u64,
-            ),
+            None => self.pending_block_timestamp(),
         };
         new_block.set_timestamp(block_timestamp);
         self.block_context.block_timestamp = block_timestamp;
@@ -263,6 +297,8 @@ impl Starknet {
             }
         });
 
+        self.event_index.index_block(&new_block, &self.transactions);
+
         // insert pending block in the blocks collection and connect it to the state diff
         self.blocks.insert(new_block, state_diff);
         // save into blocks state archive
@@ -273,6 +309,7 @@ impl Starknet {
         }
 
         self.generate_pending_block()?;
+        metrics::metrics().blocks_produced.inc();
 
         Ok(new_block_number)
     }
@@ -347,39 +384,61 @@ impl Starknet {
                 /// utility to avoid duplication
                 fn match_tx_fee_error(
                     err: blockifier::transaction::errors::TransactionFeeError,
-                ) -> DevnetResult<()> {
+                ) -> Error {
                     match err {
                         blockifier::transaction::errors::TransactionFeeError::FeeTransferError { .. }
-                        | blockifier::transaction::errors::TransactionFeeError::MaxFeeTooLow { .. } => Err(
-                            TransactionValidationError::InsufficientMaxFee.into()
-                        ),
-                        blockifier::transaction::errors::TransactionFeeError::MaxFeeExceedsBalance { .. } | blockifier::transaction::errors::TransactionFeeError::L1GasBoundsExceedBalance { .. } => Err(
-                            TransactionValidationError::InsufficientAccountBalance.into()
-                        ),
-                        _ => Err(err.into())
+                        | blockifier::transaction::errors::TransactionFeeError::MaxFeeTooLow { .. } =>
+                            TransactionValidationError::InsufficientMaxFee.into(),
+                        blockifier::transaction::errors::TransactionFeeError::MaxFeeExceedsBalance { .. } | blockifier::transaction::errors::TransactionFeeError::L1GasBoundsExceedBalance { .. } =>
+                            TransactionValidationError::InsufficientAccountBalance.into(),
+                        _ => err.into()
                     }
                 }
 
                 // based on this https://community.starknet.io/t/efficient-utilization-of-sequencer-capacity-in-starknet-v0-12-1/95607#the-validation-phase-in-the-gateway-5
                 // we should not save transactions that failed with one of the following errors
-                match tx_err {
+                // in a block; they are only kept as rejected, with the error as the reason
+                let err: Error = match tx_err {
                     blockifier::transaction::errors::TransactionExecutionError::TransactionPreValidationError(
                         TransactionPreValidationError::InvalidNonce { .. }
-                    ) => Err(TransactionValidationError::InvalidTransactionNonce.into()),
+                    ) => TransactionValidationError::InvalidTransactionNonce.into(),
                     blockifier::transaction::errors::TransactionExecutionError::FeeCheckError { .. } =>
-                        Err(TransactionValidationError::InsufficientMaxFee.into()),
+                        TransactionValidationError::InsufficientMaxFee.into(),
                     blockifier::transaction::errors::TransactionExecutionError::TransactionPreValidationError(
                         TransactionPreValidationError::TransactionFeeError(err)
                     ) => match_tx_fee_error(err),
                     blockifier::transaction::errors::TransactionExecutionError::TransactionFeeError(err)
                       => match_tx_fee_error(err),
                     blockifier::transaction::errors::TransactionExecutionError::ValidateTransactionError(err) => {
-                        Err(TransactionValidationError::ValidationFailure { reason: err.to_string() }.into())
+                        TransactionValidationError::ValidationFailure { reason: err.to_string() }.into()
                     }
-                    _ => Err(tx_err.into())
-                }
+                    _ => tx_err.into()
+                };
+
+                Err(self.reject_transaction(transaction_hash, err))
+            }
+        }
+    }
+
+    /// Keeps the error as the reason of the rejection of the transaction and returns it. Only the
+    /// latest `REJECTED_TRANSACTIONS_CAPACITY` rejected transactions are kept.
+    pub(crate) fn reject_transaction(
+        &mut self,
+        transaction_hash: TransactionHash,
+        err: Error,
+    ) -> Error {
+        metrics::metrics().transactions.with_label_values(&["rejected"]).inc();
+
+        // resending an accepted transaction fails on its nonce, but doesn't reject it
+        if self.transactions.get(&transaction_hash).is_none() {
+            self.rejected_transactions.shift_remove(&transaction_hash);
+            if self.rejected_transactions.len() >= REJECTED_TRANSACTIONS_CAPACITY {
+                self.rejected_transactions.shift_remove_index(0);
             }
+            self.rejected_transactions.insert(transaction_hash, err.to_string());
         }
+
+        err
     }
 
     /// Handles suceeded and reverted transactions.
@@ -399,6 +458,8 @@ impl Starknet {
             &tx_info,
             state_diff.clone().into(),
         )?;
+        let status = if tx_info.is_reverted() { "reverted" } else { "accepted" };
+        metrics::metrics().transactions.with_label_values(&[status]).inc();
         let transaction_to_add = StarknetTransaction::create_accepted(transaction, tx_info, trace);
 
         // add accepted transaction to pending block
@@ -459,6 +520,49 @@ impl Starknet {
         }
     }
 
+    /// Returns a copy of the block context with the fields of `block_override` replaced
+    fn block_context_with_override(
+        &self,
+        block_override: Option<&BlockOverride>,
+    ) -> DevnetResult<BlockContext> {
+        let mut block_context = self.block_context.clone();
+        let Some(block_override) = block_override else {
+            return Ok(block_context);
+        };
+
+        if let Some(block_number) = block_override.block_number {
+            block_context.block_number = BlockNumber(block_number);
+        }
+        if let Some(timestamp) = block_override.timestamp {
+            block_context.block_timestamp = BlockTimestamp(timestamp);
+        }
+        if let Some(sequencer_address) = block_override.sequencer_address {
+            block_context.sequencer_address = sequencer_address.try_into()?;
+        }
+        if let Some(gas_price) = block_override.eth_l1_gas_price {
+            block_context.gas_prices.eth_l1_gas_price = Self::gas_price_from_override(gas_price)?;
+        }
+        if let Some(gas_price) = block_override.strk_l1_gas_price {
+            block_context.gas_prices.strk_l1_gas_price = Self::gas_price_from_override(gas_price)?;
+        }
+
+        Ok(block_context)
+    }
+
+    /// Fees are estimated with gas prices of u64, so larger overridden prices are rejected
+    fn gas_price_from_override(gas_price: Felt) -> DevnetResult<u128> {
+        let gas_price = u128::try_from(gas_price)?;
+        if gas_price > u64::MAX as u128 {
+            return Err(Error::TypesError(starknet_types::error::Error::ConversionError(
+                starknet_types::error::ConversionError::OutOfRangeError(
+                    "Gas price is too large to be converted into u64 value".to_string(),
+                ),
+            )));
+        }
+
+        Ok(gas_price)
+    }
+
     /// Update block context block_number with the next one
     /// # Arguments
     /// * `block_context` - BlockContext to be updated
@@ -470,6 +574,14 @@ impl Starknet {
         &self.blocks.pending_block
     }
 
+    /// The timestamp the pending block would get if it was sealed now
+    fn pending_block_timestamp(&self) -> BlockTimestamp {
+        BlockTimestamp(
+            (Starknet::get_unix_timestamp_as_seconds() as i64 + self.pending_block_timestamp_shift)
+                as u64,
+        )
+    }
+
     /// Restarts pending block with information from block_context
     fn restart_pending_block(&mut self) -> DevnetResult<()> {
         let mut block = StarknetBlock::create_pending_block();
@@ -477,6 +589,9 @@ impl Starknet {
         block.header.block_number = self.block_context.block_number;
         block.header.eth_l1_gas_price = GasPrice(self.block_context.gas_prices.eth_l1_gas_price);
         block.header.sequencer = self.block_context.sequencer_address;
+        if let Some(last_block_hash) = self.blocks.last_block_hash {
+            block.header.parent_hash = last_block_hash.into();
+        }
 
         self.blocks.pending_block = block;
 
@@ -485,6 +600,8 @@ impl Starknet {
 
     fn get_state_at(&self, block_id: &BlockId) -> DevnetResult<&StarknetState> {
         match block_id {
+            // a transaction is added to the pending block only together with sealing it, so the
+            // state is the one of the latest block, which the pending block has too
             BlockId::Tag(_) => Ok(&self.state),
             _ => {
                 if self.config.state_archive == StateArchiveCapacity::None {
@@ -532,10 +649,76 @@ impl Starknet {
         contract_address: Felt,
         entrypoint_selector: Felt,
         calldata: Vec<Felt>,
+        state_override: Option<&StateOverride>,
     ) -> DevnetResult<Vec<Felt>> {
-        let state = self.get_state_at(block_id)?;
+        let (call_info, _) = self.execute_call(
+            block_id,
+            contract_address,
+            entrypoint_selector,
+            calldata,
+            state_override,
+        )?;
+
+        Ok(call_info.execution.retdata.0.into_iter().map(Felt::from).collect())
+    }
 
-        if !state.is_contract_deployed(&ContractAddress::new(contract_address)?) {
+    /// Executes the call like `call` and returns its trace, with the nested calls, events,
+    /// messages and execution resources
+    pub fn trace_call(
+        &self,
+        block_id: &BlockId,
+        contract_address: Felt,
+        entrypoint_selector: Felt,
+        calldata: Vec<Felt>,
+        state_override: Option<&StateOverride>,
+    ) -> DevnetResult<FunctionInvocation> {
+        let (call_info, mut state) = self.execute_call(
+            block_id,
+            contract_address,
+            entrypoint_selector,
+            calldata,
+            state_override,
+        )?;
+
+        Ok(FunctionInvocation::try_from_call_info(&call_info, &mut state)?)
+    }
+
+    /// Executes the call on a copy of the state at `block_id`, with the overrides applied.
+    /// Returns the call info and the state after the execution.
+    fn execute_call(
+        &self,
+        block_id: &BlockId,
+        contract_address: Felt,
+        entrypoint_selector: Felt,
+        calldata: Vec<Felt>,
+        state_override: Option<&StateOverride>,
+    ) -> DevnetResult<(CallInfo, CachedState<StateOverlay<'_>>)> {
+        let mut state = self.get_state_at(block_id)?.overlay();
+        if let Some(state_override) = state_override {
+            state.state.apply_state_override(state_override)?;
+        }
+
+        let call_info = self.execute_call_on_state(
+            &mut state,
+            contract_address,
+            entrypoint_selector,
+            calldata,
+        )?;
+
+        Ok((call_info, state))
+    }
+
+    /// Executes the call on the given state, which may have changes that are not committed
+    fn execute_call_on_state(
+        &self,
+        state: &mut CachedState<StateOverlay<'_>>,
+        contract_address: Felt,
+        entrypoint_selector: Felt,
+        calldata: Vec<Felt>,
+    ) -> DevnetResult<CallInfo> {
+        let storage_address: starknet_api::core::ContractAddress =
+            ContractAddress::new(contract_address)?.try_into()?;
+        if state.get_class_hash_at(storage_address)? == starknet_api::core::ClassHash::default() {
             return Err(Error::ContractNotFound);
         }
 
@@ -543,7 +726,7 @@ impl Starknet {
             calldata: starknet_api::transaction::Calldata(std::sync::Arc::new(
                 calldata.iter().map(|f| f.into()).collect(),
             )),
-            storage_address: starknet_api::hash::StarkFelt::from(contract_address).try_into()?,
+            storage_address,
             entry_point_selector: starknet_api::core::EntryPointSelector(
                 entrypoint_selector.into(),
             ),
@@ -563,13 +746,14 @@ impl Starknet {
                 blockifier::execution::common_hints::ExecutionMode::Execute,
                 true,
             )?;
-        let res = call
-            .execute(&mut state.clone().state, &mut execution_resources, &mut execution_context)
-            .map_err(|err| {
-                Error::BlockifierTransactionError(blockifier::transaction::errors::TransactionExecutionError::EntryPointExecutionError(err))
-            })?;
+        let call_info = time_execution(ExecutionKind::Call, || {
+            call.execute(state, &mut execution_resources, &mut execution_context)
+        })
+        .map_err(|err| {
+            Error::BlockifierTransactionError(blockifier::transaction::errors::TransactionExecutionError::EntryPointExecutionError(err))
+        })?;
 
-        Ok(res.execution.retdata.0.into_iter().map(Felt::from).collect())
+        Ok(call_info)
     }
 
     pub fn estimate_fee(
@@ -577,6 +761,8 @@ impl Starknet {
         block_id: &BlockId,
         transactions: &[BroadcastedTransaction],
         simulation_flags: &[SimulationFlag],
+        state_override: Option<&StateOverride>,
+        block_override: Option<&BlockOverride>,
     ) -> DevnetResult<Vec<FeeEstimateWrapper>> {
         let mut skip_validate = false;
         for flag in simulation_flags.iter() {
@@ -584,7 +770,15 @@ impl Starknet {
                 skip_validate = true;
             }
         }
-        estimations::estimate_fee(self, block_id, transactions, None, Some(!skip_validate))
+        estimations::estimate_fee(
+            self,
+            block_id,
+            transactions,
+            None,
+            Some(!skip_validate),
+            state_override,
+            block_override,
+        )
     }
 
     pub fn estimate_message_fee(
@@ -599,21 +793,36 @@ impl Starknet {
         &mut self,
         declare_transaction: BroadcastedDeclareTransactionV1,
     ) -> DevnetResult<(TransactionHash, ClassHash)> {
-        add_declare_transaction::add_declare_transaction_v1(self, declare_transaction)
+        let sender_address = declare_transaction.sender_address;
+        let result =
+            add_declare_transaction::add_declare_transaction_v1(self, declare_transaction)?;
+        self.execute_mempool_transactions(sender_address);
+
+        Ok(result)
     }
 
     pub fn add_declare_transaction_v2(
         &mut self,
         declare_transaction: BroadcastedDeclareTransactionV2,
     ) -> DevnetResult<(TransactionHash, ClassHash)> {
-        add_declare_transaction::add_declare_transaction_v2(self, declare_transaction)
+        let sender_address = declare_transaction.sender_address;
+        let result =
+            add_declare_transaction::add_declare_transaction_v2(self, declare_transaction)?;
+        self.execute_mempool_transactions(sender_address);
+
+        Ok(result)
     }
 
     pub fn add_declare_transaction_v3(
         &mut self,
         declare_transaction: BroadcastedDeclareTransactionV3,
     ) -> DevnetResult<(TransactionHash, ClassHash)> {
-        add_declare_transaction::add_declare_transaction_v3(self, declare_transaction)
+        let sender_address = declare_transaction.sender_address;
+        let result =
+            add_declare_transaction::add_declare_transaction_v3(self, declare_transaction)?;
+        self.execute_mempool_transactions(sender_address);
+
+        Ok(result)
     }
 
     /// returning the chain id as object
@@ -645,14 +854,100 @@ impl Starknet {
         &mut self,
         invoke_transaction: BroadcastedInvokeTransactionV1,
     ) -> DevnetResult<TransactionHash> {
-        add_invoke_transaction::add_invoke_transaction_v1(self, invoke_transaction)
+        let sender_address = invoke_transaction.sender_address;
+        let result = add_invoke_transaction::add_invoke_transaction_v1(self, invoke_transaction)?;
+        self.execute_mempool_transactions(sender_address);
+
+        Ok(result)
     }
 
     pub fn add_invoke_transaction_v3(
         &mut self,
         invoke_transaction: BroadcastedInvokeTransactionV3,
     ) -> DevnetResult<TransactionHash> {
-        add_invoke_transaction::add_invoke_transaction_v3(self, invoke_transaction)
+        let sender_address = invoke_transaction.sender_address;
+        let result = add_invoke_transaction::add_invoke_transaction_v3(self, invoke_transaction)?;
+        self.execute_mempool_transactions(sender_address);
+
+        Ok(result)
+    }
+
+    /// Puts the transaction in the mempool if its nonce is ahead of the current nonce of the
+    /// sender, so that it is executed once the transactions with the missing nonces arrive.
+    /// Returns whether the transaction was put in the mempool. Transactions of senders that are
+    /// not deployed, or received while the mempool is full, are left to the usual validation.
+    pub(crate) fn add_to_mempool_if_nonce_ahead(
+        &mut self,
+        transaction_hash: TransactionHash,
+        sender_address: ContractAddress,
+        nonce: Felt,
+        transaction: impl FnOnce() -> BroadcastedTransaction,
+    ) -> bool {
+        match self.state.get_nonce(&sender_address) {
+            Ok(current_nonce) if nonce > current_nonce => self.mempool.insert(MempoolTransaction {
+                transaction_hash,
+                sender_address,
+                nonce,
+                transaction: transaction(),
+            }),
+            _ => false,
+        }
+    }
+
+    /// Executes the transactions of the mempool which are next in line for the sender, until a
+    /// nonce is missing again. Transactions whose nonce has been passed, e.g. by another
+    /// transaction with the same nonce, are executed as well, so that they are rejected. Failing
+    /// transactions leave the mempool and are kept as rejected, like when they are added directly.
+    fn execute_mempool_transactions(&mut self, sender_address: ContractAddress) {
+        while let Some(mempool_transaction) = self
+            .state
+            .get_nonce(&sender_address)
+            .ok()
+            .and_then(|nonce| self.mempool.take(&sender_address, nonce))
+        {
+            let result = match mempool_transaction.transaction {
+                BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(invoke_v1)) => {
+                    add_invoke_transaction::add_invoke_transaction_v1(self, invoke_v1).map(|_| ())
+                }
+                BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V3(invoke_v3)) => {
+                    add_invoke_transaction::add_invoke_transaction_v3(self, invoke_v3).map(|_| ())
+                }
+                BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V1(declare_v1)) => {
+                    add_declare_transaction::add_declare_transaction_v1(self, *declare_v1)
+                        .map(|_| ())
+                }
+                BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V2(declare_v2)) => {
+                    add_declare_transaction::add_declare_transaction_v2(self, *declare_v2)
+                        .map(|_| ())
+                }
+                BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V3(declare_v3)) => {
+                    add_declare_transaction::add_declare_transaction_v3(self, *declare_v3)
+                        .map(|_| ())
+                }
+                // deploy account transactions are never put in the mempool
+                BroadcastedTransaction::DeployAccount(_) => Ok(()),
+            };
+
+            if let Err(err) = result {
+                let transaction_hash = mempool_transaction.transaction_hash;
+                error!(
+                    "Transaction {} from the mempool failed: {err}",
+                    transaction_hash.to_prefixed_hex_str()
+                );
+
+                // failures in validation are already recorded, the others are recorded here, so
+                // that the transaction doesn't go missing
+                if self.transactions.get(&transaction_hash).is_none()
+                    && self.get_rejection_reason(&transaction_hash).is_none()
+                {
+                    self.reject_transaction(transaction_hash, err);
+                }
+            }
+        }
+    }
+
+    pub fn get_mempool(&self) -> &Mempool {
+        &self.mempool
     }
 
     pub fn add_l1_handler_transaction(
@@ -749,13 +1044,19 @@ impl Starknet {
         state.get_storage(ContractStorageKey::new(contract_address, storage_key))
     }
 
+    /// Returns the block by its id. If `block_id` is the pending tag, the returned block has the
+    /// status `Pending` and the timestamp it would have if it was sealed now.
     pub fn get_block(&self, block_id: &BlockId) -> DevnetResult<StarknetBlock> {
-        let block = self.blocks.get_by_block_id(block_id).ok_or(Error::NoBlock)?;
-        Ok(block.clone())
+        let mut block = self.blocks.get_by_block_id(block_id).ok_or(Error::NoBlock)?.clone();
+        if block.status == BlockStatus::Pending {
+            block.set_timestamp(self.pending_block_timestamp());
+        }
+
+        Ok(block)
     }
 
     pub fn get_block_with_transactions(&self, block_id: &BlockId) -> DevnetResult<Block> {
-        let block = self.blocks.get_by_block_id(block_id).ok_or(Error::NoBlock)?;
+        let block = self.get_block(block_id)?;
         let transactions = block
             .get_transactions()
             .iter()
@@ -769,7 +1070,7 @@ impl Starknet {
 
         Ok(Block {
             status: *block.status(),
-            header: BlockHeader::from(block),
+            header: BlockHeader::from(&block),
             transactions: Transactions::Full(transactions),
         })
     }
@@ -810,10 +1111,10 @@ impl Starknet {
         to_block: Option<BlockId>,
         address: Option<ContractAddress>,
         keys: Option<Vec<Vec<Felt>>>,
-        skip: usize,
+        continuation_token: Option<EventsContinuationToken>,
         limit: Option<usize>,
-    ) -> DevnetResult<(Vec<EmittedEvent>, bool)> {
-        events::get_events(self, from_block, to_block, address, keys, skip, limit)
+    ) -> DevnetResult<(Vec<EmittedEvent>, Option<EventsContinuationToken>)> {
+        events::get_events(self, from_block, to_block, address, keys, continuation_token, limit)
     }
 
     pub fn get_transaction_receipt_by_hash(
@@ -834,6 +1135,31 @@ impl Starknet {
         tx.get_trace().ok_or(Error::NoTransactionTrace)
     }
 
+    /// Returns the trace of the transaction, in which the function invocations and the events are
+    /// decoded using the ABI of the declared classes
+    pub fn get_decoded_transaction_trace_by_hash(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> DevnetResult<serde_json::Value> {
+        let trace = self.get_transaction_trace_by_hash(transaction_hash)?;
+        let mut trace_json = serde_json::to_value(trace)
+            .map_err(|err| Error::SerializationError { origin: err.to_string() })?;
+        abi_decoder::decode_trace(&mut trace_json, &self.state.contract_classes);
+
+        Ok(trace_json)
+    }
+
+    /// Returns the steps, builtins and traced syscalls used by the transaction, per contract and
+    /// entry point
+    pub fn profile_transaction(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> DevnetResult<ExecutionProfile> {
+        let transaction = self.transactions.get(&transaction_hash).ok_or(Error::NoTransaction)?;
+
+        Ok(profiler::profile_execution(&transaction.execution_info, &self.state.contract_classes))
+    }
+
     pub fn get_transaction_traces_from_block(
         &self,
         block_id: &BlockId,
@@ -855,54 +1181,69 @@ impl Starknet {
         Ok(BlockTransactionTraces { traces })
     }
 
-    pub fn get_transaction_execution_and_finality_status(
+    /// Transactions waiting in the mempool are reported as received, and transactions which
+    /// failed validation as rejected
+    pub fn get_transaction_status(
         &self,
         transaction_hash: TransactionHash,
-    ) -> DevnetResult<(TransactionExecutionStatus, TransactionFinalityStatus)> {
-        let transaction = self.transactions.get(&transaction_hash).ok_or(Error::NoTransaction)?;
+    ) -> DevnetResult<TransactionStatus> {
+        if let Some(transaction) = self.transactions.get(&transaction_hash) {
+            let execution_status = transaction.execution_result.status();
+            return Ok(match transaction.finality_status {
+                TransactionFinalityStatus::AcceptedOnL2 => {
+                    TransactionStatus::AcceptedOnL2(execution_status)
+                }
+                TransactionFinalityStatus::AcceptedOnL1 => {
+                    TransactionStatus::AcceptedOnL1(execution_status)
+                }
+            });
+        }
+
+        if self.mempool.get(&transaction_hash).is_some() {
+            return Ok(TransactionStatus::Received);
+        }
+
+        match self.rejected_transactions.get(&transaction_hash) {
+            Some(_) => Ok(TransactionStatus::Rejected),
+            None => Err(Error::NoTransaction),
+        }
+    }
 
-        Ok((transaction.execution_result.status(), transaction.finality_status))
+    /// Returns the error the transaction was rejected with, if it is among the latest
+    /// `REJECTED_TRANSACTIONS_CAPACITY` rejected transactions
+    pub fn get_rejection_reason(&self, transaction_hash: &TransactionHash) -> Option<&str> {
+        self.rejected_transactions.get(transaction_hash).map(String::as_str)
     }
 
     pub fn simulate_transactions(
-        &mut self,
+        &self,
         block_id: &BlockId,
         transactions: &[BroadcastedTransaction],
         simulation_flags: Vec<SimulationFlag>,
+        state_override: Option<&StateOverride>,
+        block_override: Option<&BlockOverride>,
     ) -> DevnetResult<Vec<SimulatedTransaction>> {
-        let mut state = self.get_state_at(block_id)?.clone();
-        let chain_id = self.chain_id().to_felt();
-
-        let mut skip_validate = false;
-        let mut skip_fee_charge = false;
-        for flag in simulation_flags.iter() {
-            match flag {
-                SimulationFlag::SkipValidate => {
-                    skip_validate = true;
-                }
-                SimulationFlag::SkipFeeCharge => skip_fee_charge = true,
-            }
+        let base_state = self.get_state_at(block_id)?;
+        let mut state = base_state.overlay();
+        if let Some(state_override) = state_override {
+            state.state.apply_state_override(state_override)?;
         }
+        let block_context = self.block_context_with_override(block_override)?;
+        let (skip_validate, skip_fee_charge) = Self::get_simulation_skips(&simulation_flags);
 
+        let executions = self.execute_simulated_transactions(
+            &mut state,
+            &block_context,
+            transactions,
+            skip_validate,
+            skip_fee_charge,
+        )?;
         let mut transactions_traces: Vec<TransactionTrace> = vec![];
-
-        for broadcasted_transaction in transactions.iter() {
-            let blockifier_transaction =
-                broadcasted_transaction.to_blockifier_account_transaction(chain_id, true)?;
-            let tx_execution_info = blockifier_transaction.execute(
-                &mut state.state,
-                &self.block_context,
-                !skip_fee_charge,
-                !skip_validate,
-            )?;
-
-            let state_diff: ThinStateDiff = state.extract_state_diff_from_pending_state()?.into();
-            let trace = create_trace(
-                &mut state.state,
-                broadcasted_transaction.get_type(),
-                &tx_execution_info,
-                state_diff,
-            )?;
+        let mut profiles: Vec<Option<ExecutionProfile>> = vec![];
+        for (tx_execution_info, trace) in executions {
+            profiles.push(self.config.profile.then(|| {
+                profiler::profile_execution(&tx_execution_info, &base_state.contract_classes)
+            }));
             transactions_traces.push(trace);
         }
 
@@ -912,6 +1253,8 @@ impl Starknet {
             transactions,
             Some(!skip_fee_charge),
             Some(!skip_validate),
+            state_override,
+            block_override,
         )?;
 
         // if the underlying simulation is correct, this should never be the case
@@ -929,15 +1272,115 @@ impl Starknet {
         let simulation_results = transactions_traces
             .into_iter()
             .zip(estimated)
-            .map(|(trace, fee_estimation)| SimulatedTransaction {
+            .zip(profiles)
+            .map(|((trace, fee_estimation), profile)| SimulatedTransaction {
                 transaction_trace: trace,
                 fee_estimation,
+                profile,
             })
             .collect();
 
         Ok(simulation_results)
     }
 
+    /// Executes the transactions in sequence on a copy of the state at `block_id`, like
+    /// `simulate_transactions`, and then the calls on the resulting state. Returns the traces of
+    /// the transactions and the results of the calls. Nothing is committed.
+    pub fn call_bundle(
+        &self,
+        block_id: &BlockId,
+        transactions: &[BroadcastedTransaction],
+        simulation_flags: &[SimulationFlag],
+        calls: Vec<FunctionCall>,
+    ) -> DevnetResult<(Vec<TransactionTrace>, Vec<Vec<Felt>>)> {
+        let mut state = self.get_state_at(block_id)?.overlay();
+        let (skip_validate, skip_fee_charge) = Self::get_simulation_skips(simulation_flags);
+
+        let transactions_traces = self
+            .execute_simulated_transactions(
+                &mut state,
+                &self.block_context,
+                transactions,
+                skip_validate,
+                skip_fee_charge,
+            )?
+            .into_iter()
+            .map(|(_, trace)| trace)
+            .collect();
+
+        let calls_results = calls
+            .into_iter()
+            .map(|call| {
+                let call_info = self.execute_call_on_state(
+                    &mut state,
+                    call.contract_address.into(),
+                    call.entry_point_selector,
+                    call.calldata,
+                )?;
+
+                Ok(call_info.execution.retdata.0.into_iter().map(Felt::from).collect())
+            })
+            .collect::<DevnetResult<_>>()?;
+
+        Ok((transactions_traces, calls_results))
+    }
+
+    /// Returns whether the validation and the fee charge are skipped
+    fn get_simulation_skips(simulation_flags: &[SimulationFlag]) -> (bool, bool) {
+        let mut skip_validate = false;
+        let mut skip_fee_charge = false;
+        for flag in simulation_flags.iter() {
+            match flag {
+                SimulationFlag::SkipValidate => {
+                    skip_validate = true;
+                }
+                SimulationFlag::SkipFeeCharge => skip_fee_charge = true,
+            }
+        }
+
+        (skip_validate, skip_fee_charge)
+    }
+
+    /// Executes the transactions in sequence on the state without committing them. Returns the
+    /// execution info and the trace of each transaction.
+    fn execute_simulated_transactions(
+        &self,
+        state: &mut CachedState<StateOverlay<'_>>,
+        block_context: &BlockContext,
+        transactions: &[BroadcastedTransaction],
+        skip_validate: bool,
+        skip_fee_charge: bool,
+    ) -> DevnetResult<Vec<(TransactionExecutionInfo, TransactionTrace)>> {
+        let chain_id = self.chain_id().to_felt();
+
+        transactions
+            .iter()
+            .map(|broadcasted_transaction| {
+                let blockifier_transaction =
+                    broadcasted_transaction.to_blockifier_account_transaction(chain_id, true)?;
+                let tx_execution_info = time_execution(ExecutionKind::Simulation, || {
+                    blockifier_transaction.execute(
+                        state,
+                        block_context,
+                        !skip_fee_charge,
+                        !skip_validate,
+                    )
+                })?;
+
+                let state_diff: ThinStateDiff =
+                    StateDiff::difference_between_old_and_new_state(state)?.into();
+                let trace = create_trace(
+                    state,
+                    broadcasted_transaction.get_type(),
+                    &tx_execution_info,
+                    state_diff,
+                )?;
+
+                Ok((tx_execution_info, trace))
+            })
+            .collect()
+    }
+
     /// create new block from pending one
     pub fn create_block(&mut self, timestamp: Option<u64>) -> DevnetResult<(), Error> {
         self.generate_new_block(StateDiff::default(), timestamp)?;
@@ -991,24 +1434,28 @@ impl Starknet {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::thread;
     use std::time::Duration;
 
     use blockifier::state::state_api::State;
     use blockifier::transaction::errors::TransactionExecutionError;
     use starknet_api::block::{BlockHash, BlockNumber, BlockStatus, BlockTimestamp, GasPrice};
-    use starknet_rs_core::types::{BlockId, BlockTag};
+    use starknet_rs_core::types::{BlockId, BlockTag, TransactionStatus};
     use starknet_types::contract_address::ContractAddress;
     use starknet_types::felt::Felt;
+    use starknet_types::rpc::block_override::BlockOverride;
+    use starknet_types::rpc::state_override::{ContractStateOverride, StateOverride};
+    use starknet_types::rpc::transactions::FunctionCall;
 
     use super::Starknet;
     use crate::account::FeeToken;
     use crate::blocks::StarknetBlock;
     use crate::constants::{
         DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_INITIAL_BALANCE, ETH_ERC20_CONTRACT_ADDRESS,
-        STRK_ERC20_CONTRACT_ADDRESS,
+        REJECTED_TRANSACTIONS_CAPACITY, STRK_ERC20_CONTRACT_ADDRESS,
     };
-    use crate::error::{DevnetResult, Error};
+    use crate::error::{DevnetResult, Error, StateError};
     use crate::starknet::starknet_config::{StarknetConfig, StateArchiveCapacity};
     use crate::state::state_diff::StateDiff;
     use crate::traits::{Accounted, StateChanger, StateExtractor};
@@ -1119,6 +1566,37 @@ mod tests {
         assert_eq!(starknet.pending_block().header.sequencer, initial_sequencer);
     }
 
+    #[test]
+    fn block_context_with_override_replaces_only_the_given_fields() {
+        let config = StarknetConfig::default();
+        let starknet = Starknet::new(&config).unwrap();
+
+        let block_override = BlockOverride {
+            timestamp: Some(1_000_000),
+            eth_l1_gas_price: Some(Felt::from(7)),
+            ..Default::default()
+        };
+        let block_context = starknet.block_context_with_override(Some(&block_override)).unwrap();
+
+        assert_eq!(block_context.block_timestamp, BlockTimestamp(1_000_000));
+        assert_eq!(block_context.gas_prices.eth_l1_gas_price, 7);
+        assert_eq!(block_context.block_number, starknet.block_context.block_number);
+        assert_eq!(block_context.sequencer_address, starknet.block_context.sequencer_address);
+        assert_eq!(
+            block_context.gas_prices.strk_l1_gas_price,
+            starknet.block_context.gas_prices.strk_l1_gas_price
+        );
+        assert_ne!(starknet.block_context.block_timestamp, BlockTimestamp(1_000_000));
+
+        for too_large_gas_price in ["0x10000000000000000", "0x1000000000000000000000000000000000"] {
+            let block_override = BlockOverride {
+                strk_l1_gas_price: Some(Felt::from_prefixed_hex_str(too_large_gas_price).unwrap()),
+                ..Default::default()
+            };
+            assert!(starknet.block_context_with_override(Some(&block_override)).is_err());
+        }
+    }
+
     #[test]
     fn correct_block_context_update() {
         let mut block_ctx = Starknet::init_block_context(
@@ -1201,6 +1679,7 @@ mod tests {
             undeployed_address,
             entry_point_selector.into(),
             vec![],
+            None,
         ) {
             Err(Error::ContractNotFound) => (),
             unexpected => panic!("Should have failed; got {unexpected:?}"),
@@ -1221,6 +1700,7 @@ mod tests {
             Felt::from_prefixed_hex_str(ETH_ERC20_CONTRACT_ADDRESS).unwrap(),
             entry_point_selector.into(),
             vec![Felt::from(predeployed_account.account_address)],
+            None,
         ) {
             Err(Error::BlockifierTransactionError(
                 TransactionExecutionError::EntryPointExecutionError(
@@ -1245,6 +1725,7 @@ mod tests {
             Felt::from_prefixed_hex_str(ETH_ERC20_CONTRACT_ADDRESS)?,
             entry_point_selector.into(),
             vec![Felt::from(contract_address)],
+            None,
         )
     }
 
@@ -1276,6 +1757,131 @@ mod tests {
         assert_eq!(result, expected_balance_uint256);
     }
 
+    #[test]
+    fn getting_balance_with_state_override() {
+        let config = StarknetConfig::default();
+        let starknet = Starknet::new(&config).unwrap();
+
+        let predeployed_account = &starknet.predeployed_accounts.get_accounts()[0];
+        let overridden_balance =
+            Felt::from_prefixed_hex_str("0x200000000000000000000000000000001").unwrap();
+        let state_override = StateOverride::from([(
+            predeployed_account.account_address,
+            ContractStateOverride { eth_balance: Some(overridden_balance), ..Default::default() },
+        )]);
+
+        let entry_point_selector =
+            starknet_rs_core::utils::get_selector_from_name("balanceOf").unwrap();
+        let result = starknet
+            .call(
+                &BlockId::Tag(BlockTag::Latest),
+                Felt::from_prefixed_hex_str(ETH_ERC20_CONTRACT_ADDRESS).unwrap(),
+                entry_point_selector.into(),
+                vec![Felt::from(predeployed_account.account_address)],
+                Some(&state_override),
+            )
+            .unwrap();
+        assert_eq!(result, vec![Felt::from(1), Felt::from(2)]);
+
+        // the override is not committed
+        let result = get_balance_at(&starknet, predeployed_account.account_address).unwrap();
+        assert_eq!(result, vec![Felt::from(DEVNET_DEFAULT_INITIAL_BALANCE), Felt::from(0)]);
+    }
+
+    #[test]
+    fn calling_undeployed_contract_with_class_hash_override() {
+        let config = StarknetConfig::default();
+        let starknet = Starknet::new(&config).unwrap();
+
+        let undeployed_address =
+            ContractAddress::new(Felt::from_prefixed_hex_str("0x1234").unwrap()).unwrap();
+        let erc20_address =
+            ContractAddress::new(Felt::from_prefixed_hex_str(ETH_ERC20_CONTRACT_ADDRESS).unwrap())
+                .unwrap();
+        let erc20_class_hash =
+            starknet.get_class_hash_at(&BlockId::Tag(BlockTag::Latest), erc20_address).unwrap();
+        let holder = Felt::from(5);
+        let balance_key =
+            crate::utils::get_storage_var_address("ERC20_balances", &[holder]).unwrap();
+        let state_override = StateOverride::from([(
+            undeployed_address,
+            ContractStateOverride {
+                class_hash: Some(erc20_class_hash),
+                storage: HashMap::from([(balance_key.to_felt(), Felt::from(42))]),
+                ..Default::default()
+            },
+        )]);
+
+        let entry_point_selector =
+            starknet_rs_core::utils::get_selector_from_name("balanceOf").unwrap();
+        let result = starknet
+            .call(
+                &BlockId::Tag(BlockTag::Latest),
+                undeployed_address.into(),
+                entry_point_selector.into(),
+                vec![holder],
+                Some(&state_override),
+            )
+            .unwrap();
+        assert_eq!(result, vec![Felt::from(42), Felt::from(0)]);
+
+        let undeclared_override = StateOverride::from([(
+            undeployed_address,
+            ContractStateOverride { class_hash: Some(Felt::from(1)), ..Default::default() },
+        )]);
+        match starknet.call(
+            &BlockId::Tag(BlockTag::Latest),
+            undeployed_address.into(),
+            entry_point_selector.into(),
+            vec![holder],
+            Some(&undeclared_override),
+        ) {
+            Err(Error::StateError(StateError::NoneClassHash(_))) => (),
+            unexpected => panic!("Should have failed; got {unexpected:?}"),
+        }
+    }
+
+    #[test]
+    fn calls_of_bundle_are_executed_in_order() {
+        let config = StarknetConfig::default();
+        let starknet = Starknet::new(&config).unwrap();
+
+        let predeployed_account = &starknet.predeployed_accounts.get_accounts()[0];
+        let balance_call = FunctionCall {
+            contract_address: ContractAddress::new(
+                Felt::from_prefixed_hex_str(ETH_ERC20_CONTRACT_ADDRESS).unwrap(),
+            )
+            .unwrap(),
+            entry_point_selector: starknet_rs_core::utils::get_selector_from_name("balanceOf")
+                .unwrap()
+                .into(),
+            calldata: vec![Felt::from(predeployed_account.account_address)],
+        };
+        let undeployed_call = FunctionCall {
+            contract_address: ContractAddress::new(Felt::from(0x1234)).unwrap(),
+            ..balance_call.clone()
+        };
+
+        let (traces, results) = starknet
+            .call_bundle(&BlockId::Tag(BlockTag::Latest), &[], &[], vec![balance_call.clone()])
+            .unwrap();
+        assert!(traces.is_empty());
+        assert_eq!(
+            results,
+            vec![get_balance_at(&starknet, predeployed_account.account_address).unwrap()]
+        );
+
+        match starknet.call_bundle(
+            &BlockId::Tag(BlockTag::Latest),
+            &[],
+            &[],
+            vec![balance_call, undeployed_call],
+        ) {
+            Err(Error::ContractNotFound) => (),
+            unexpected => panic!("Should have failed; got {unexpected:?}"),
+        }
+    }
+
     #[test]
     fn correct_latest_block() {
         let config = StarknetConfig::default();
@@ -1300,6 +1906,21 @@ mod tests {
         assert_eq!(block_number2.0, added_block2.header.block_number.0);
     }
 
+    #[test]
+    fn pending_block_follows_latest_block() {
+        let config = StarknetConfig::default();
+        let mut starknet = Starknet::new(&config).unwrap();
+
+        starknet.generate_new_block(StateDiff::default(), None).unwrap();
+        let latest_block = starknet.get_latest_block().unwrap();
+
+        let pending_block = starknet.get_block(&BlockId::Tag(BlockTag::Pending)).unwrap();
+        assert_eq!(pending_block.status, BlockStatus::Pending);
+        assert_eq!(pending_block.parent_hash(), latest_block.block_hash());
+        assert_eq!(pending_block.block_number(), latest_block.block_number().next());
+        assert!(pending_block.timestamp() >= latest_block.timestamp());
+    }
+
     #[test]
     fn gets_block_txs_count() {
         let config = StarknetConfig::default();
@@ -1426,4 +2047,24 @@ mod tests {
         // ----(pending block timestamp)----(sleep)----(new block timestamp)
         assert!(pending_block_timestamp.0 + sleep_duration_secs <= block_timestamp.0);
     }
+
+    #[test]
+    fn only_the_latest_rejected_transactions_are_kept() {
+        let mut starknet = Starknet::default();
+        for transaction_hash in 0..=REJECTED_TRANSACTIONS_CAPACITY as u128 {
+            starknet.reject_transaction(Felt::from(transaction_hash), Error::NoTransaction);
+        }
+
+        assert!(starknet.get_rejection_reason(&Felt::from(0)).is_none());
+        assert_eq!(
+            starknet.get_rejection_reason(&Felt::from(1)).unwrap(),
+            Error::NoTransaction.to_string()
+        );
+        assert_eq!(
+            starknet
+                .get_transaction_status(Felt::from(REJECTED_TRANSACTIONS_CAPACITY as u128))
+                .unwrap(),
+            TransactionStatus::Rejected
+        );
+    }
 }
@@ -1,9 +1,10 @@
 use std::net::IpAddr;
 
+use serde::{Serialize, Serializer};
 use starknet_types::chain_id::ChainId;
 use starknet_types::contract_class::ContractClass;
 use starknet_types::felt::Felt;
-use starknet_types::traits::HashProducer;
+use starknet_types::traits::{HashProducer, ToDecimalString};
 
 use crate::constants::{
     CAIRO_1_ACCOUNT_CONTRACT_SIERRA_PATH, DEVNET_DEFAULT_CHAIN_ID, DEVNET_DEFAULT_GAS_PRICE,
@@ -11,13 +12,15 @@ use crate::constants::{
     DEVNET_DEFAULT_TEST_SEED, DEVNET_DEFAULT_TIMEOUT, DEVNET_DEFAULT_TOTAL_ACCOUNTS,
 };
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DumpOn {
     Exit,
     Transaction,
 }
 
-#[derive(Default, Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum)]
+#[derive(Default, Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum StateArchiveCapacity {
     #[default]
     #[clap(name = "none")]
@@ -26,12 +29,15 @@ pub enum StateArchiveCapacity {
     Full,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct StarknetConfig {
     pub seed: u32,
     pub total_accounts: u8,
+    /// Not serialized, the class is identified by `account_contract_class_hash`
+    #[serde(skip_serializing)]
     pub account_contract_class: ContractClass,
     pub account_contract_class_hash: Felt,
+    #[serde(serialize_with = "serialize_decimal")]
     pub predeployed_accounts_initial_balance: Felt,
     pub host: IpAddr,
     pub port: u16,
@@ -42,8 +48,19 @@ pub struct StarknetConfig {
     pub dump_on: Option<DumpOn>,
     pub dump_path: Option<String>,
     /// on initialization, re-execute loaded txs (if any)
+    #[serde(skip_serializing)]
     pub re_execute_on_init: bool,
     pub state_archive: StateArchiveCapacity,
+    /// profile the execution of simulated transactions
+    pub profile: bool,
+}
+
+/// Serializes the felt as a decimal string, the way balances are reported
+fn serialize_decimal<S>(value: &Felt, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&value.to_decimal_string())
 }
 
 impl Default for StarknetConfig {
@@ -67,6 +84,7 @@ impl Default for StarknetConfig {
             dump_path: None,
             re_execute_on_init: true,
             state_archive: StateArchiveCapacity::default(),
+            profile: false,
         }
     }
 }
@@ -1,4 +1,4 @@
-use starknet_rs_core::types::BlockId;
+use starknet_rs_core::types::{BlockId, BlockTag};
 
 use super::Starknet;
 use crate::error::DevnetResult;
@@ -8,6 +8,11 @@ pub fn state_update_by_block_id(
     starknet: &Starknet,
     block_id: &BlockId,
 ) -> DevnetResult<StateUpdate> {
+    // state updates are stored per sealed block, so the pending tag refers to the latest one
+    let block_id = match block_id {
+        BlockId::Tag(BlockTag::Pending) => &BlockId::Tag(BlockTag::Latest),
+        other => other,
+    };
     let block = starknet.blocks.get_by_block_id(block_id).ok_or(crate::error::Error::NoBlock)?;
     let state_diff =
         starknet.blocks.num_to_state_diff.get(&block.block_number()).cloned().unwrap_or_default();
@@ -10,6 +10,7 @@ use starknet_types::rpc::transactions::{
 };
 
 use crate::error::{DevnetResult, Error};
+use crate::revert_reason::decode_revert_reason;
 
 fn get_execute_call_info<S: StateReader>(
     state: &mut CachedState<S>,
@@ -23,14 +24,14 @@ fn get_execute_call_info<S: StateReader>(
             true => ExecutionInvocation::Reverted(starknet_types::rpc::transactions::Reversion {
                 revert_reason: execution_info
                     .revert_error
-                    .clone()
-                    .unwrap_or("Revert reason not found".into()),
+                    .as_deref()
+                    .map_or("Revert reason not found".into(), decode_revert_reason),
             }),
         },
-        None => match execution_info.revert_error.clone() {
-            Some(revert_reason) => {
+        None => match &execution_info.revert_error {
+            Some(revert_error) => {
                 ExecutionInvocation::Reverted(starknet_types::rpc::transactions::Reversion {
-                    revert_reason,
+                    revert_reason: decode_revert_reason(revert_error),
                 })
             }
             None => {
@@ -9,10 +9,12 @@ use starknet_types::contract_storage_key::ContractStorageKey;
 use starknet_types::felt::{ClassHash, CompiledClassHash, Felt};
 
 use self::state_diff::StateDiff;
+use self::state_overlay::StateOverlay;
 use crate::error::{DevnetResult, Error, StateError};
 use crate::traits::{DevnetStateReader, StateChanger, StateExtractor};
 
 pub(crate) mod state_diff;
+pub(crate) mod state_overlay;
 pub mod state_update;
 
 pub(crate) struct StarknetState {
@@ -38,6 +40,22 @@ pub(crate) struct DevnetState {
     pub class_hash_to_compiled_class_hash: HashMap<ClassHash, CompiledClassHash>,
 }
 
+impl DevnetState {
+    /// Estimates the memory occupied by the entries of the state, excluding contract classes
+    pub(crate) fn estimated_size(&self) -> usize {
+        self.address_to_class_hash.len() * std::mem::size_of::<(ContractAddress, ClassHash)>()
+            + self.address_to_nonce.len() * std::mem::size_of::<(ContractAddress, Felt)>()
+            + self.address_to_storage.len() * std::mem::size_of::<(ContractStorageKey, Felt)>()
+            + self.class_hash_to_compiled_class_hash.len()
+                * std::mem::size_of::<(ClassHash, CompiledClassHash)>()
+    }
+}
+
+/// Estimates the memory occupied by the contract class as the length of its JSON form
+fn estimated_class_size(contract_class: &ContractClass) -> usize {
+    serde_json::to_vec(contract_class).map_or(0, |json| json.len())
+}
+
 impl crate::traits::DevnetStateReader for DevnetState {
     fn compiled_class_hash_at(&self, class_hash: &ClassHash) -> ClassHash {
         self.class_hash_to_compiled_class_hash.get(class_hash).cloned().unwrap_or_default()
@@ -70,70 +88,115 @@ impl crate::traits::DevnetStateReader for DevnetState {
                 .cloned()
         }
     }
-}
 
-impl blockifier::state::state_api::StateReader for DevnetState {
-    fn get_storage_at(
-        &mut self,
-        contract_address: starknet_api::core::ContractAddress,
-        key: starknet_api::state::StorageKey,
-    ) -> blockifier::state::state_api::StateResult<starknet_api::hash::StarkFelt> {
-        let storage = crate::traits::DevnetStateReader::storage_at(
-            self,
-            &ContractStorageKey::new(contract_address.into(), key.0.into()),
-        );
-        Ok(storage.into())
+    fn is_class_declared(&self, class_hash: &ClassHash) -> bool {
+        self.class_hash_to_compiled_class_hash.contains_key(class_hash)
+            || self.class_hash_to_compiled_class.contains_key(class_hash)
     }
+}
 
-    fn get_nonce_at(
-        &mut self,
-        contract_address: starknet_api::core::ContractAddress,
-    ) -> blockifier::state::state_api::StateResult<starknet_api::core::Nonce> {
-        let nonce = crate::traits::DevnetStateReader::nonce_at(self, &contract_address.into());
-        Ok(starknet_api::core::Nonce(nonce.into()))
-    }
+/// Implements the blockifier `StateReader` of the type through its `DevnetStateReader`
+macro_rules! impl_blockifier_state_reader {
+    ($state:ty) => {
+        impl blockifier::state::state_api::StateReader for $state {
+            fn get_storage_at(
+                &mut self,
+                contract_address: starknet_api::core::ContractAddress,
+                key: starknet_api::state::StorageKey,
+            ) -> blockifier::state::state_api::StateResult<starknet_api::hash::StarkFelt> {
+                let storage = crate::traits::DevnetStateReader::storage_at(
+                    self,
+                    &ContractStorageKey::new(contract_address.into(), key.0.into()),
+                );
+                Ok(storage.into())
+            }
 
-    fn get_class_hash_at(
-        &mut self,
-        contract_address: starknet_api::core::ContractAddress,
-    ) -> blockifier::state::state_api::StateResult<starknet_api::core::ClassHash> {
-        let class_hash =
-            crate::traits::DevnetStateReader::class_hash_at(self, &contract_address.into());
-        Ok(starknet_api::core::ClassHash(class_hash.into()))
-    }
+            fn get_nonce_at(
+                &mut self,
+                contract_address: starknet_api::core::ContractAddress,
+            ) -> blockifier::state::state_api::StateResult<starknet_api::core::Nonce> {
+                let nonce =
+                    crate::traits::DevnetStateReader::nonce_at(self, &contract_address.into());
+                Ok(starknet_api::core::Nonce(nonce.into()))
+            }
 
-    fn get_compiled_contract_class(
-        &mut self,
-        class_hash: &starknet_api::core::ClassHash,
-    ) -> blockifier::state::state_api::StateResult<
-        blockifier::execution::contract_class::ContractClass,
-    > {
-        let contract_class =
-            crate::traits::DevnetStateReader::contract_class_at(self, &class_hash.0.into())
-                .map_err(|_| {
-                    blockifier::state::errors::StateError::UndeclaredClassHash(*class_hash)
-                })?;
+            fn get_class_hash_at(
+                &mut self,
+                contract_address: starknet_api::core::ContractAddress,
+            ) -> blockifier::state::state_api::StateResult<starknet_api::core::ClassHash> {
+                let class_hash =
+                    crate::traits::DevnetStateReader::class_hash_at(self, &contract_address.into());
+                Ok(starknet_api::core::ClassHash(class_hash.into()))
+            }
 
-        blockifier::execution::contract_class::ContractClass::try_from(contract_class)
-            .map_err(|err| blockifier::state::errors::StateError::StateReadError(err.to_string()))
-    }
+            fn get_compiled_contract_class(
+                &mut self,
+                class_hash: &starknet_api::core::ClassHash,
+            ) -> blockifier::state::state_api::StateResult<
+                blockifier::execution::contract_class::ContractClass,
+            > {
+                let contract_class =
+                    crate::traits::DevnetStateReader::contract_class_at(self, &class_hash.0.into())
+                        .map_err(|_| {
+                            blockifier::state::errors::StateError::UndeclaredClassHash(*class_hash)
+                        })?;
+
+                blockifier::execution::contract_class::ContractClass::try_from(contract_class)
+                    .map_err(|err| {
+                        blockifier::state::errors::StateError::StateReadError(err.to_string())
+                    })
+            }
 
-    fn get_compiled_class_hash(
-        &mut self,
-        class_hash: starknet_api::core::ClassHash,
-    ) -> blockifier::state::state_api::StateResult<starknet_api::core::CompiledClassHash> {
-        let compiled_class_hash =
-            crate::traits::DevnetStateReader::compiled_class_hash_at(self, &(class_hash.0.into()));
-        Ok(starknet_api::core::CompiledClassHash(compiled_class_hash.into()))
-    }
+            fn get_compiled_class_hash(
+                &mut self,
+                class_hash: starknet_api::core::ClassHash,
+            ) -> blockifier::state::state_api::StateResult<starknet_api::core::CompiledClassHash>
+            {
+                let compiled_class_hash = crate::traits::DevnetStateReader::compiled_class_hash_at(
+                    self,
+                    &(class_hash.0.into()),
+                );
+                Ok(starknet_api::core::CompiledClassHash(compiled_class_hash.into()))
+            }
+        }
+    };
 }
 
+impl_blockifier_state_reader!(DevnetState);
+impl_blockifier_state_reader!(StateOverlay<'_>);
+
 impl StarknetState {
+    /// Estimates the memory occupied by the state, including the contract classes, which every
+    /// state holds a copy of. The sizes of classes are cached in `class_sizes` by class hash, so
+    /// that each class is measured once when estimating many states.
+    pub(crate) fn estimated_size(&self, class_sizes: &mut HashMap<ClassHash, usize>) -> usize {
+        let persistent_state = &self.state.state;
+        let mut classes_size = |classes: &HashMap<ClassHash, ContractClass>| -> usize {
+            classes
+                .iter()
+                .map(|(class_hash, contract_class)| {
+                    *class_sizes
+                        .entry(*class_hash)
+                        .or_insert_with(|| estimated_class_size(contract_class))
+                })
+                .sum()
+        };
+
+        persistent_state.estimated_size()
+            + classes_size(&persistent_state.class_hash_to_compiled_class)
+            + classes_size(&self.contract_classes)
+    }
+
     /// this method clears the state from data that was accumulated in the StateCache
     /// and restores it to the data in the state_reader, which is the "persistent" data
     pub(crate) fn clear_dirty_state(&mut self) {
         self.state = CachedState::new(self.state.state.clone(), Default::default());
     }
+
+    /// A state for executing on the "persistent" data without committing and without copying it
+    pub(crate) fn overlay(&self) -> CachedState<StateOverlay<'_>> {
+        CachedState::new(StateOverlay::new(&self.state.state), Default::default())
+    }
 }
 
 impl Clone for StarknetState {
@@ -259,8 +322,7 @@ impl StateExtractor for StarknetState {
     }
 
     fn is_contract_declared(&mut self, class_hash: &ClassHash) -> bool {
-        self.state.state.class_hash_to_compiled_class_hash.contains_key(class_hash)
-            || self.state.state.class_hash_to_compiled_class.contains_key(class_hash)
+        self.state.state.is_class_declared(class_hash)
     }
 
     fn is_contract_deployed(&self, address: &ContractAddress) -> bool {
@@ -278,7 +340,7 @@ impl StateExtractor for StarknetState {
     }
 
     fn extract_state_diff_from_pending_state(&mut self) -> DevnetResult<StateDiff> {
-        StateDiff::difference_between_old_and_new_state(self.state.state.clone(), &mut self.state)
+        StateDiff::difference_between_old_and_new_state(&mut self.state)
     }
 
     fn get_nonce(&self, address: &ContractAddress) -> DevnetResult<Felt> {
@@ -292,6 +354,8 @@ impl StateExtractor for StarknetState {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use blockifier::state::state_api::{State, StateReader};
     use blockifier::test_utils::DictStateReader;
     use starknet_api::state::StorageKey;
@@ -309,6 +373,21 @@ mod tests {
         dummy_felt,
     };
 
+    #[test]
+    fn estimated_size_includes_contract_classes() {
+        let mut state = StarknetState::default();
+        let entries_size = state.state.state.estimated_size();
+
+        state.declare_contract_class(dummy_felt(), dummy_cairo_1_contract_class().into()).unwrap();
+        let contract_class = state.contract_classes.get(&dummy_felt()).unwrap();
+        let class_size = serde_json::to_vec(contract_class).unwrap().len();
+
+        let mut class_sizes = HashMap::new();
+        // the class is held in the persistent state and among the contract classes
+        assert_eq!(state.estimated_size(&mut class_sizes), entries_size + 2 * class_size);
+        assert_eq!(class_sizes, HashMap::from([(dummy_felt(), class_size)]));
+    }
+
     #[test]
     fn apply_state_update_for_contract_class_successfully() {
         let mut state = StarknetState::default();
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use blockifier::state::cached_state::CachedState;
-use blockifier::state::state_api::State;
+use blockifier::state::state_api::{State, StateReader};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::{ClassHash, Felt};
 use starknet_types::patricia_key::{PatriciaKey, StorageKey};
@@ -9,8 +9,8 @@ use starknet_types::rpc::state::{
     ClassHashes, ContractNonce, DeployedContract, StorageDiff, StorageEntry, ThinStateDiff,
 };
 
-use super::DevnetState;
 use crate::error::DevnetResult;
+use crate::traits::DevnetStateReader;
 
 /// This struct is used to store the difference between state modifications
 #[derive(PartialEq, Default, Debug, Clone)]
@@ -30,9 +30,10 @@ pub struct StateDiff {
 impl Eq for StateDiff {}
 
 impl StateDiff {
-    pub(crate) fn difference_between_old_and_new_state(
-        old_state: DevnetState,
-        new_state: &mut CachedState<DevnetState>,
+    /// The old state is the one read by the cached state, the new one includes the changes in
+    /// its cache
+    pub(crate) fn difference_between_old_and_new_state<S: StateReader + DevnetStateReader>(
+        new_state: &mut CachedState<S>,
     ) -> DevnetResult<Self> {
         let mut declared_contracts = Vec::<ClassHash>::new();
         let mut cairo_0_declared_contracts = Vec::<ClassHash>::new();
@@ -43,19 +44,23 @@ impl StateDiff {
         new_state.move_classes_to_global_cache();
         let diff = new_state.to_state_diff();
 
-        for (class_hash, class) in new_state.global_class_hash_to_class().get_order().iter() {
-            let class_hash_as_felt: Felt = class_hash.0.into();
-
-            if !(old_state.class_hash_to_compiled_class.contains_key(&class_hash_as_felt)
-                || old_state.class_hash_to_compiled_class_hash.contains_key(&class_hash_as_felt))
-            {
-                match class {
-                    blockifier::execution::contract_class::ContractClass::V0(_) => {
-                        cairo_0_declared_contracts.push(class_hash_as_felt)
-                    }
-                    blockifier::execution::contract_class::ContractClass::V1(_) => {
-                        declared_contracts.push(class_hash_as_felt)
-                    }
+        let cached_classes: Vec<(Felt, bool)> = new_state
+            .global_class_hash_to_class()
+            .get_order()
+            .iter()
+            .map(|(class_hash, class)| {
+                let is_cairo_0 =
+                    matches!(class, blockifier::execution::contract_class::ContractClass::V0(_));
+                (class_hash.0.into(), is_cairo_0)
+            })
+            .collect();
+
+        for (class_hash_as_felt, is_cairo_0) in cached_classes {
+            if !new_state.state.is_class_declared(&class_hash_as_felt) {
+                if is_cairo_0 {
+                    cairo_0_declared_contracts.push(class_hash_as_felt)
+                } else {
+                    declared_contracts.push(class_hash_as_felt)
                 }
             }
         }
@@ -193,11 +198,10 @@ mod tests {
 
     #[test]
     fn correct_no_difference_between_non_modified_states() {
-        let (old_state, mut new_state) = setup();
+        let mut new_state = setup();
 
         let generated_diff =
-            super::StateDiff::difference_between_old_and_new_state(old_state, &mut new_state)
-                .unwrap();
+            super::StateDiff::difference_between_old_and_new_state(&mut new_state).unwrap();
 
         let expected_diff = StateDiff::default();
 
@@ -206,7 +210,7 @@ mod tests {
 
     #[test]
     fn correct_difference_in_class_hash_to_compiled_class_hash() {
-        let (old_state, mut new_state) = setup();
+        let mut new_state = setup();
 
         let class_hash = StarkFelt::from(1u8);
         let compiled_class_hash = StarkFelt::from(2u8);
@@ -219,8 +223,7 @@ mod tests {
             .unwrap();
 
         let generated_diff =
-            super::StateDiff::difference_between_old_and_new_state(old_state, &mut new_state)
-                .unwrap();
+            super::StateDiff::difference_between_old_and_new_state(&mut new_state).unwrap();
         let mut expected_diff = StateDiff::default();
         expected_diff
             .class_hash_to_compiled_class_hash
@@ -231,11 +234,9 @@ mod tests {
 
     #[test]
     fn correct_difference_in_declared_classes() {
-        let old_state = DevnetState::default();
-
         let compiled_class_hash = Felt::from(1);
 
-        let mut new_state = CachedState::from(old_state.clone());
+        let mut new_state = setup();
         new_state
             .set_contract_class(
                 &ClassHash(compiled_class_hash.into()),
@@ -244,8 +245,7 @@ mod tests {
             .unwrap();
 
         let generated_diff =
-            super::StateDiff::difference_between_old_and_new_state(old_state, &mut new_state)
-                .unwrap();
+            super::StateDiff::difference_between_old_and_new_state(&mut new_state).unwrap();
 
         let mut expected_diff = StateDiff::default();
         expected_diff.declared_contracts.push(compiled_class_hash);
@@ -255,12 +255,10 @@ mod tests {
 
     #[test]
     fn correct_difference_in_cairo_0_declared_classes() {
-        let old_state = DevnetState::default();
-
         let class_hash = Felt::from(1);
         let cairo_0_contract_class: Cairo0ContractClass = dummy_cairo_0_contract_class().into();
 
-        let mut new_state = CachedState::from(old_state.clone());
+        let mut new_state = setup();
         new_state
             .set_contract_class(
                 &ClassHash(class_hash.into()),
@@ -269,8 +267,7 @@ mod tests {
             .unwrap();
 
         let generated_diff =
-            super::StateDiff::difference_between_old_and_new_state(old_state, &mut new_state)
-                .unwrap();
+            super::StateDiff::difference_between_old_and_new_state(&mut new_state).unwrap();
 
         let expected_diff = StateDiff {
             cairo_0_declared_contracts: vec![class_hash].into_iter().collect(),
@@ -282,7 +279,7 @@ mod tests {
 
     #[test]
     fn correct_difference_in_state_diff_object() {
-        let (old_state, mut new_state) = setup();
+        let mut new_state = setup();
         let class_hash = dummy_felt();
         let contract_address = dummy_contract_address();
 
@@ -291,8 +288,7 @@ mod tests {
             .unwrap();
 
         let generated_diff =
-            super::StateDiff::difference_between_old_and_new_state(old_state, &mut new_state)
-                .unwrap();
+            super::StateDiff::difference_between_old_and_new_state(&mut new_state).unwrap();
 
         let expected_diff = StateDiff {
             address_to_class_hash: vec![(contract_address, class_hash)].into_iter().collect(),
@@ -302,10 +298,7 @@ mod tests {
         assert_eq!(generated_diff, expected_diff);
     }
 
-    fn setup() -> (DevnetState, CachedState<DevnetState>) {
-        let state = DevnetState::default();
-        let cached_state = CachedState::from(state.clone());
-
-        (state, cached_state)
+    fn setup() -> CachedState<DevnetState> {
+        CachedState::from(DevnetState::default())
     }
 }
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use starknet_rs_ff::FieldElement;
+use starknet_types::contract_address::ContractAddress;
+use starknet_types::contract_class::ContractClass;
+use starknet_types::contract_storage_key::ContractStorageKey;
+use starknet_types::felt::{ClassHash, Felt};
+use starknet_types::patricia_key::PatriciaKey;
+use starknet_types::rpc::state_override::StateOverride;
+
+use super::DevnetState;
+use crate::constants::{ETH_ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS};
+use crate::error::{DevnetResult, Error, StateError};
+use crate::traits::DevnetStateReader;
+use crate::utils::get_storage_var_address;
+
+/// Reads through to the "persistent" data of a base state and keeps the changes to it locally.
+/// Executing on a `CachedState` of the overlay leaves the base untouched, and creating it costs
+/// nothing, no matter how big the base state is.
+pub(crate) struct StateOverlay<'a> {
+    base: &'a DevnetState,
+    changes: DevnetState,
+}
+
+impl<'a> StateOverlay<'a> {
+    pub(crate) fn new(base: &'a DevnetState) -> Self {
+        Self { base, changes: DevnetState::default() }
+    }
+
+    /// The value of the key in the changes, or in the base if it wasn't changed
+    fn lookup<'b, K: Eq + Hash, V>(
+        changes: &'b HashMap<K, V>,
+        base: &'b HashMap<K, V>,
+        key: &K,
+    ) -> Option<&'b V> {
+        changes.get(key).or_else(|| base.get(key))
+    }
+
+    /// Applies the overrides to the changes, so that they are seen by everything executed on the
+    /// overlay.
+    pub(crate) fn apply_state_override(
+        &mut self,
+        state_override: &StateOverride,
+    ) -> DevnetResult<()> {
+        let eth_fee_token_address =
+            ContractAddress::new(Felt::from_prefixed_hex_str(ETH_ERC20_CONTRACT_ADDRESS)?)?;
+        let strk_fee_token_address =
+            ContractAddress::new(Felt::from_prefixed_hex_str(STRK_ERC20_CONTRACT_ADDRESS)?)?;
+
+        for (address, contract_override) in state_override {
+            if let Some(class_hash) = contract_override.class_hash {
+                if !self.is_class_declared(&class_hash) {
+                    return Err(Error::StateError(StateError::NoneClassHash(class_hash)));
+                }
+                self.changes.address_to_class_hash.insert(*address, class_hash);
+            }
+
+            if let Some(nonce) = contract_override.nonce {
+                self.changes.address_to_nonce.insert(*address, nonce);
+            }
+
+            for (storage_key, value) in contract_override.storage.iter() {
+                self.changes.address_to_storage.insert(
+                    ContractStorageKey::new(*address, PatriciaKey::new(*storage_key)?),
+                    *value,
+                );
+            }
+
+            for (fee_token_address, balance) in [
+                (eth_fee_token_address, contract_override.eth_balance),
+                (strk_fee_token_address, contract_override.strk_balance),
+            ] {
+                if let Some(balance) = balance {
+                    self.set_erc20_balance(fee_token_address, *address, balance)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the balance as the u256 stored in `ERC20_balances` of the token contract
+    fn set_erc20_balance(
+        &mut self,
+        token_address: ContractAddress,
+        address: ContractAddress,
+        balance: Felt,
+    ) -> DevnetResult<()> {
+        let low_key = get_storage_var_address("ERC20_balances", &[Felt::from(address)])?;
+        let high_key =
+            PatriciaKey::new((FieldElement::from(low_key.to_felt()) + FieldElement::ONE).into())?;
+
+        let balance_bytes = balance.bytes();
+        let (high_bytes, low_bytes) = balance_bytes.split_at(16);
+        for (storage_key, value_bytes) in [(low_key, low_bytes), (high_key, high_bytes)] {
+            let mut value = [0; 32];
+            value[16..].copy_from_slice(value_bytes);
+            self.changes
+                .address_to_storage
+                .insert(ContractStorageKey::new(token_address, storage_key), Felt::new(value)?);
+        }
+
+        Ok(())
+    }
+}
+
+impl DevnetStateReader for StateOverlay<'_> {
+    fn compiled_class_hash_at(&self, class_hash: &ClassHash) -> ClassHash {
+        Self::lookup(
+            &self.changes.class_hash_to_compiled_class_hash,
+            &self.base.class_hash_to_compiled_class_hash,
+            class_hash,
+        )
+        .cloned()
+        .unwrap_or_default()
+    }
+
+    fn storage_at(&self, storage_key: &ContractStorageKey) -> Felt {
+        Self::lookup(&self.changes.address_to_storage, &self.base.address_to_storage, storage_key)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn nonce_at(&self, address: &ContractAddress) -> Felt {
+        Self::lookup(&self.changes.address_to_nonce, &self.base.address_to_nonce, address)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn class_hash_at(&self, address: &ContractAddress) -> ClassHash {
+        Self::lookup(&self.changes.address_to_class_hash, &self.base.address_to_class_hash, address)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn contract_class_at(&self, class_hash: &ClassHash) -> DevnetResult<ContractClass> {
+        let compiled_class_at = |class_hash| {
+            Self::lookup(
+                &self.changes.class_hash_to_compiled_class,
+                &self.base.class_hash_to_compiled_class,
+                class_hash,
+            )
+        };
+
+        if let Some(deprecated_contract_class) = compiled_class_at(class_hash) {
+            Ok(deprecated_contract_class.clone())
+        } else {
+            let compiled_class_hash = Self::lookup(
+                &self.changes.class_hash_to_compiled_class_hash,
+                &self.base.class_hash_to_compiled_class_hash,
+                class_hash,
+            )
+            .ok_or(Error::StateError(StateError::NoneCompiledHash(*class_hash)))?;
+
+            compiled_class_at(compiled_class_hash)
+                .ok_or(Error::StateError(StateError::NoneCasmClass(*compiled_class_hash)))
+                .cloned()
+        }
+    }
+
+    fn is_class_declared(&self, class_hash: &ClassHash) -> bool {
+        self.changes.is_class_declared(class_hash) || self.base.is_class_declared(class_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blockifier::state::cached_state::CachedState;
+    use blockifier::state::state_api::{State, StateReader};
+    use starknet_api::state::StorageKey;
+    use starknet_types::contract_storage_key::ContractStorageKey;
+    use starknet_types::felt::Felt;
+    use starknet_types::rpc::state_override::ContractStateOverride;
+
+    use super::StateOverlay;
+    use crate::error::{Error, StateError};
+    use crate::state::DevnetState;
+    use crate::traits::DevnetStateReader;
+    use crate::utils::test_utils::{dummy_contract_address, dummy_contract_storage_key};
+
+    #[test]
+    fn reads_through_to_the_base_state() {
+        let mut base = DevnetState::default();
+        base.address_to_storage.insert(dummy_contract_storage_key(), Felt::from(1));
+        base.address_to_nonce.insert(dummy_contract_address(), Felt::from(2));
+
+        let overlay = StateOverlay::new(&base);
+        assert_eq!(overlay.storage_at(&dummy_contract_storage_key()), Felt::from(1));
+        assert_eq!(overlay.nonce_at(&dummy_contract_address()), Felt::from(2));
+        assert_eq!(overlay.class_hash_at(&dummy_contract_address()), Felt::default());
+    }
+
+    #[test]
+    fn overrides_are_seen_through_the_overlay_but_not_by_the_base() {
+        let mut base = DevnetState::default();
+        base.address_to_nonce.insert(dummy_contract_address(), Felt::from(2));
+
+        let mut overlay = StateOverlay::new(&base);
+        let storage_key =
+            ContractStorageKey::new(dummy_contract_address(), Felt::from(3).try_into().unwrap());
+        overlay
+            .apply_state_override(
+                &[(
+                    dummy_contract_address(),
+                    ContractStateOverride {
+                        nonce: Some(Felt::from(5)),
+                        storage: [(Felt::from(3), Felt::from(4))].into_iter().collect(),
+                        ..Default::default()
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            )
+            .unwrap();
+
+        assert_eq!(overlay.nonce_at(&dummy_contract_address()), Felt::from(5));
+        assert_eq!(overlay.storage_at(&storage_key), Felt::from(4));
+        assert_eq!(base.nonce_at(&dummy_contract_address()), Felt::from(2));
+        assert_eq!(base.storage_at(&storage_key), Felt::default());
+    }
+
+    #[test]
+    fn override_with_undeclared_class_hash_fails() {
+        let base = DevnetState::default();
+        let mut overlay = StateOverlay::new(&base);
+
+        let result = overlay.apply_state_override(
+            &[(
+                dummy_contract_address(),
+                ContractStateOverride { class_hash: Some(Felt::from(1)), ..Default::default() },
+            )]
+            .into_iter()
+            .collect(),
+        );
+        assert!(matches!(result, Err(Error::StateError(StateError::NoneClassHash(_)))));
+    }
+
+    #[test]
+    fn writes_of_execution_do_not_reach_the_base_state() {
+        let storage_key = dummy_contract_storage_key();
+        let mut base = DevnetState::default();
+        base.address_to_storage.insert(storage_key, Felt::from(3));
+        let mut state = CachedState::from(StateOverlay::new(&base));
+
+        let contract_address = (*storage_key.get_contract_address()).try_into().unwrap();
+        let key = StorageKey((*storage_key.get_storage_key()).try_into().unwrap());
+        state.set_storage_at(contract_address, key, Felt::from(4).into());
+
+        // the write is kept on top of the overlay, as the diff of the execution
+        assert_eq!(state.get_storage_at(contract_address, key).unwrap(), Felt::from(4).into());
+        assert_eq!(
+            state.to_state_diff().storage_updates[&contract_address][&key],
+            Felt::from(4).into()
+        );
+
+        // the base still returns the old value
+        assert_eq!(base.storage_at(&storage_key), Felt::from(3));
+    }
+}
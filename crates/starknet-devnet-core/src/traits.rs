@@ -85,6 +85,7 @@ pub trait DevnetStateReader {
     fn nonce_at(&self, address: &ContractAddress) -> Felt;
     fn class_hash_at(&self, address: &ContractAddress) -> ClassHash;
     fn contract_class_at(&self, class_hash: &ClassHash) -> DevnetResult<ContractClass>;
+    fn is_class_declared(&self, class_hash: &ClassHash) -> bool;
 }
 
 /// This trait should be implemented by structures that generate accounts
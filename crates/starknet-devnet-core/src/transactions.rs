@@ -20,6 +20,7 @@ use starknet_types::rpc::transactions::{
 
 use crate::constants::UDC_CONTRACT_ADDRESS;
 use crate::error::{DevnetResult, Error};
+use crate::revert_reason::decode_revert_reason;
 use crate::traits::{HashIdentified, HashIdentifiedMut};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -81,8 +82,8 @@ impl StarknetTransaction {
                 true => ExecutionResult::Reverted {
                     reason: execution_info
                         .revert_error
-                        .clone()
-                        .unwrap_or("No revert error".to_string()),
+                        .as_deref()
+                        .map_or("No revert error".to_string(), decode_revert_reason),
                 },
                 false => ExecutionResult::Succeeded,
             },
@@ -6,12 +6,18 @@ use starknet_types::traits::ToDecimalString;
 use crate::api::http::error::HttpApiError;
 use crate::api::http::models::{Balance, SerializableAccount};
 use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::Api;
 
 pub async fn get_predeployed_accounts(
     Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<Json<Vec<SerializableAccount>>> {
-    let predeployed_accounts = state
-        .api
+    get_predeployed_accounts_impl(&state.api).await.map(Json)
+}
+
+pub(crate) async fn get_predeployed_accounts_impl(
+    api: &Api,
+) -> HttpApiResult<Vec<SerializableAccount>> {
+    let predeployed_accounts = api
         .starknet
         .read()
         .await
@@ -25,7 +31,7 @@ pub async fn get_predeployed_accounts(
         })
         .collect();
 
-    Ok(Json(predeployed_accounts))
+    Ok(predeployed_accounts)
 }
 
 pub async fn get_account_balance(
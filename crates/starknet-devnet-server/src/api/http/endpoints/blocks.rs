@@ -3,18 +3,23 @@ use axum::{Extension, Json};
 use crate::api::http::error::HttpApiError;
 use crate::api::http::models::{AbortedBlocks, AbortingBlocks, CreatedBlock};
 use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::Api;
 
 pub async fn create_block(
     Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<Json<CreatedBlock>> {
-    let mut starknet = state.api.starknet.write().await;
+    create_block_impl(&state.api).await.map(Json)
+}
+
+pub(crate) async fn create_block_impl(api: &Api) -> HttpApiResult<CreatedBlock> {
+    let mut starknet = api.starknet.write().await;
     starknet
         .create_block_dump_event(None, None)
         .map_err(|err| HttpApiError::CreateEmptyBlockError { msg: err.to_string() })?;
 
     let last_block = starknet.get_latest_block();
     match last_block {
-        Ok(block) => Ok(Json(CreatedBlock { block_hash: block.block_hash() })),
+        Ok(block) => Ok(CreatedBlock { block_hash: block.block_hash() }),
         Err(err) => Err(HttpApiError::CreateEmptyBlockError { msg: err.to_string() }),
     }
 }
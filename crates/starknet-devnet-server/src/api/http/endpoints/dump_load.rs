@@ -3,12 +3,17 @@ use axum::{Extension, Json};
 use crate::api::http::error::HttpApiError;
 use crate::api::http::models::{DumpPath, LoadPath};
 use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::api::Api;
 
 pub async fn dump(
     Json(path): Json<DumpPath>,
     Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<()> {
-    let starknet = state.api.starknet.write().await;
+    dump_impl(&state.api, path).await
+}
+
+pub(crate) async fn dump_impl(api: &Api, path: DumpPath) -> HttpApiResult<()> {
+    let starknet = api.starknet.write().await;
 
     if starknet.config.dump_on.is_none() {
         return Err(HttpApiError::DumpError {
@@ -46,12 +51,16 @@ pub async fn load(
     Json(path): Json<LoadPath>,
     Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<()> {
+    load_impl(&state.api, path).await
+}
+
+pub(crate) async fn load_impl(api: &Api, path: LoadPath) -> HttpApiResult<()> {
     let file_path = std::path::Path::new(&path.path);
     if path.path.is_empty() || !file_path.exists() {
         return Err(HttpApiError::FileNotFound);
     }
 
-    let mut starknet = state.api.starknet.write().await;
+    let mut starknet = api.starknet.write().await;
     let events =
         starknet.load_events_custom_path(Some(path.path)).map_err(|_| HttpApiError::LoadError)?;
     starknet.re_execute(events).map_err(|_| HttpApiError::ReExecutionError)?;
@@ -0,0 +1,20 @@
+use axum::http::header::{HeaderName, CONTENT_TYPE};
+use axum::Extension;
+
+use crate::api::http::error::HttpApiError;
+use crate::api::http::{HttpApiHandler, HttpApiResult};
+use crate::metrics::encode_metrics;
+
+/// The version of the Prometheus text format
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+pub async fn get_metrics(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<([(HeaderName, &'static str); 1], String)> {
+    state.api.starknet.read().await.update_state_metrics();
+
+    let metrics =
+        encode_metrics().map_err(|err| HttpApiError::MetricsError { msg: err.to_string() })?;
+
+    Ok(([(CONTENT_TYPE, METRICS_CONTENT_TYPE)], metrics))
+}
@@ -11,6 +11,7 @@ use crate::api::http::error::HttpApiError;
 use crate::api::http::models::{FeeToken, MintTokensRequest, MintTokensResponse};
 use crate::api::http::{HttpApiHandler, HttpApiResult};
 use crate::api::json_rpc::error::ApiError;
+use crate::api::Api;
 
 pub async fn get_fee_token() -> HttpApiResult<Json<FeeToken>> {
     Err(HttpApiError::GeneralError)
@@ -29,6 +30,7 @@ fn get_balance(
         erc20_address.into(),
         balance_selector,
         vec![Felt::from(address)], // calldata = the address being queried
+        None,
     )?;
 
     // format balance for output - initially it is a 2-member vector (low, high)
@@ -50,7 +52,14 @@ pub async fn mint(
     Json(request): Json<MintTokensRequest>,
     Extension(state): Extension<HttpApiHandler>,
 ) -> HttpApiResult<Json<MintTokensResponse>> {
-    let mut starknet = state.api.starknet.write().await;
+    mint_impl(&state.api, request).await.map(Json)
+}
+
+pub(crate) async fn mint_impl(
+    api: &Api,
+    request: MintTokensRequest,
+) -> HttpApiResult<MintTokensResponse> {
+    let mut starknet = api.starknet.write().await;
     let unit = request.unit.unwrap_or(FeeUnits::WEI);
     let erc20_address = match unit {
         FeeUnits::WEI => {
@@ -72,5 +81,5 @@ pub async fn mint(
     let new_balance = get_balance(&starknet, request.address, erc20_address)
         .map_err(|err| HttpApiError::MintingError { msg: err.to_string() })?;
 
-    Ok(Json(MintTokensResponse { new_balance: new_balance.to_str_radix(10), unit, tx_hash }))
+    Ok(MintTokensResponse { new_balance: new_balance.to_str_radix(10), unit, tx_hash })
 }
@@ -1,8 +1,9 @@
 use axum::{Extension, Json};
 
 use super::error::HttpApiError;
-use super::models::ForkStatus;
+use super::models::{DevnetConfig, ForkStatus};
 use super::{HttpApiHandler, HttpApiResult};
+use crate::api::Api;
 
 /// Dumping and loading
 pub mod dump_load;
@@ -22,6 +23,9 @@ pub mod accounts;
 /// Mint token - Local faucet
 pub mod mint_token;
 
+/// Prometheus metrics
+pub mod metrics;
+
 /// Is alive
 pub async fn is_alive() -> HttpApiResult<String> {
     Ok("Alive!!!".to_string())
@@ -29,9 +33,11 @@ pub async fn is_alive() -> HttpApiResult<String> {
 
 /// Restart
 pub async fn restart(Extension(state): Extension<HttpApiHandler>) -> HttpApiResult<()> {
-    state
-        .api
-        .starknet
+    restart_impl(&state.api).await
+}
+
+pub(crate) async fn restart_impl(api: &Api) -> HttpApiResult<()> {
+    api.starknet
         .write()
         .await
         .restart()
@@ -39,6 +45,14 @@ pub async fn restart(Extension(state): Extension<HttpApiHandler>) -> HttpApiResu
     Ok(())
 }
 
+/// Config
+pub async fn get_config(
+    Extension(state): Extension<HttpApiHandler>,
+) -> HttpApiResult<Json<DevnetConfig>> {
+    let starknet_config = state.api.starknet.read().await.config.clone();
+    Ok(Json(DevnetConfig { starknet_config, server_config: state.server_config }))
+}
+
 /// Fork
 pub async fn get_fork_status() -> HttpApiResult<Json<ForkStatus>> {
     Err(HttpApiError::GeneralError)
@@ -30,6 +30,10 @@ pub enum HttpApiError {
     MessagingError { msg: String },
     #[error("Invalid value: {msg}")]
     InvalidValueError { msg: String },
+    #[error("Could not collect metrics: {msg}")]
+    MetricsError { msg: String },
+    #[error("Missing or invalid API key; provide it as a bearer token in the Authorization header")]
+    Unauthorized,
 }
 
 impl IntoResponse for HttpApiError {
@@ -59,6 +63,10 @@ impl IntoResponse for HttpApiError {
             err @ HttpApiError::InvalidValueError { msg: _ } => {
                 (StatusCode::BAD_REQUEST, err.to_string())
             }
+            err @ HttpApiError::MetricsError { .. } => {
+                (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+            }
+            err @ HttpApiError::Unauthorized => (StatusCode::UNAUTHORIZED, err.to_string()),
         };
 
         let body = Json(json!({
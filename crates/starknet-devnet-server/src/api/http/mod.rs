@@ -1,10 +1,11 @@
 pub mod endpoints;
 pub mod error;
 #[allow(unused)]
-mod models;
+pub(crate) mod models;
 
 use self::error::HttpApiError;
 use super::Api;
+use crate::ServerConfig;
 
 /// Helper type for the result of the http api calls and reducing typing HttpApiError
 type HttpApiResult<T> = Result<T, HttpApiError>;
@@ -13,4 +14,5 @@ type HttpApiResult<T> = Result<T, HttpApiError>;
 #[derive(Clone)]
 pub struct HttpApiHandler {
     pub api: Api,
+    pub server_config: ServerConfig,
 }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use starknet_core::starknet::starknet_config::StarknetConfig;
 use starknet_rs_core::types::{Hash256, MsgToL1};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::{BlockHash, Calldata, EntryPointSelector, Felt, Nonce, TransactionHash};
@@ -8,34 +9,35 @@ use starknet_types::rpc::transaction_receipt::FeeUnits;
 use starknet_types::rpc::transactions::L1HandlerTransaction;
 
 use crate::api::http::error::HttpApiError;
+use crate::ServerConfig;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct DumpPath {
     pub path: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct LoadPath {
     pub path: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct PostmanLoadL1MessagingContract {
     pub network_url: String,
     pub address: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MessageHash {
     pub message_hash: Hash256,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TxHash {
     pub transaction_hash: TransactionHash,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CreatedBlock {
     pub block_hash: BlockHash,
 }
@@ -51,24 +53,24 @@ pub struct AbortedBlocks {
     aborted: Vec<BlockHash>,
 }
 
-#[derive(Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Time {
     pub time: u64,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SetTimeResponse {
     pub block_timestamp: u64,
     pub block_hash: BlockHash,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct IncreaseTimeResponse {
     pub timestamp_increased_by: u64,
     pub block_hash: BlockHash,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SerializableAccount {
     pub initial_balance: String,
     pub address: ContractAddress,
@@ -88,7 +90,7 @@ pub struct FeeToken {
     address: ContractAddress,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct MintTokensRequest {
     pub address: ContractAddress,
     pub amount: u128,
@@ -96,7 +98,7 @@ pub struct MintTokensRequest {
     pub unit: Option<FeeUnits>,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MintTokensResponse {
     /// decimal repr
     pub new_balance: String,
@@ -110,7 +112,15 @@ pub struct ForkStatus {
     block: u128,
 }
 
+/// The configuration Devnet is running with
 #[derive(Serialize)]
+pub struct DevnetConfig {
+    #[serde(flatten)]
+    pub starknet_config: StarknetConfig,
+    pub server_config: ServerConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct FlushedMessages {
     pub messages_to_l1: Vec<MessageToL1>,
     pub messages_to_l2: Vec<MessageToL2>,
@@ -118,12 +128,13 @@ pub struct FlushedMessages {
     pub l1_provider: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlushParameters {
+    #[serde(default)]
     pub dry_run: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MessagingLoadAddress {
     pub messaging_contract_address: String,
 }
@@ -0,0 +1,195 @@
+use starknet_core::error::{Error, StateError};
+use starknet_rs_core::types::{BlockId as ImportedBlockId, BlockTag};
+use starknet_types::felt::TransactionHash;
+use starknet_types::rpc::block::BlockId;
+use starknet_types::rpc::messaging::{MessageToL1, MessageToL2};
+use starknet_types::rpc::state_override::StateOverride;
+use starknet_types::rpc::transactions::{BroadcastedTransaction, FunctionCall, SimulationFlag};
+
+use super::error::{ApiError, StrictRpcResult};
+use super::models::{CallBundleOutput, MempoolTransactionOutput};
+use super::{JsonRpcHandler, StarknetResponse};
+use crate::api::http::endpoints::{
+    accounts, blocks, dump_load, mint_token, postman, restart_impl, time,
+};
+use crate::api::http::models::{
+    DumpPath, FlushParameters, LoadPath, MintTokensRequest, PostmanLoadL1MessagingContract, Time,
+};
+
+/// here are the definitions of the devnet_* JSON-RPC methods, which mirror the HTTP API of devnet
+impl JsonRpcHandler {
+    /// devnet_mint
+    pub async fn mint(&self, request: MintTokensRequest) -> StrictRpcResult {
+        let response = mint_token::mint_impl(&self.api, request).await?;
+
+        Ok(StarknetResponse::Mint(response))
+    }
+
+    /// devnet_createBlock
+    pub async fn create_block(&self) -> StrictRpcResult {
+        let created_block = blocks::create_block_impl(&self.api).await?;
+
+        Ok(StarknetResponse::CreateBlock(created_block))
+    }
+
+    /// devnet_setTime
+    pub async fn set_time(&self, time: Time) -> StrictRpcResult {
+        let response = time::set_time_impl(&self.api, time).await?;
+
+        Ok(StarknetResponse::SetTime(response))
+    }
+
+    /// devnet_increaseTime
+    pub async fn increase_time(&self, time: Time) -> StrictRpcResult {
+        let response = time::increase_time_impl(&self.api, time).await?;
+
+        Ok(StarknetResponse::IncreaseTime(response))
+    }
+
+    /// devnet_dump
+    pub async fn dump(&self, path: Option<DumpPath>) -> StrictRpcResult {
+        dump_load::dump_impl(&self.api, path.unwrap_or(DumpPath { path: None })).await?;
+
+        Ok(StarknetResponse::Dump)
+    }
+
+    /// devnet_load
+    pub async fn load(&self, path: LoadPath) -> StrictRpcResult {
+        dump_load::load_impl(&self.api, path).await?;
+
+        Ok(StarknetResponse::Load)
+    }
+
+    /// devnet_restart
+    pub async fn restart(&self) -> StrictRpcResult {
+        restart_impl(&self.api).await?;
+
+        Ok(StarknetResponse::Restart)
+    }
+
+    /// devnet_getPredeployedAccounts
+    pub async fn get_predeployed_accounts(&self) -> StrictRpcResult {
+        let accounts = accounts::get_predeployed_accounts_impl(&self.api).await?;
+
+        Ok(StarknetResponse::PredeployedAccounts(accounts))
+    }
+
+    /// devnet_getMempool
+    pub async fn get_mempool(&self) -> StrictRpcResult {
+        let starknet = self.api.starknet.read().await;
+        let transactions = starknet
+            .get_mempool()
+            .iter()
+            .map(|transaction| MempoolTransactionOutput {
+                transaction_hash: transaction.transaction_hash,
+                r#type: transaction.get_type(),
+                sender_address: transaction.sender_address,
+                nonce: transaction.nonce,
+            })
+            .collect();
+
+        Ok(StarknetResponse::Mempool(transactions))
+    }
+
+    /// devnet_postmanLoad
+    pub async fn postman_load(&self, data: PostmanLoadL1MessagingContract) -> StrictRpcResult {
+        let response = postman::postman_load_impl(&self.api, data).await?;
+
+        Ok(StarknetResponse::PostmanLoad(response))
+    }
+
+    /// devnet_postmanFlush
+    pub async fn postman_flush(&self, data: Option<FlushParameters>) -> StrictRpcResult {
+        let flushed_messages = postman::postman_flush_impl(&self.api, data).await?;
+
+        Ok(StarknetResponse::PostmanFlush(flushed_messages))
+    }
+
+    /// devnet_postmanSendMessageToL2
+    pub async fn postman_send_message_to_l2(&self, message: MessageToL2) -> StrictRpcResult {
+        let response = postman::postman_send_message_to_l2_impl(&self.api, message).await?;
+
+        Ok(StarknetResponse::PostmanSendMessageToL2(response))
+    }
+
+    /// devnet_postmanConsumeMessageFromL2
+    pub async fn postman_consume_message_from_l2(&self, message: MessageToL1) -> StrictRpcResult {
+        let response = postman::postman_consume_message_from_l2_impl(&self.api, message).await?;
+
+        Ok(StarknetResponse::PostmanConsumeMessageFromL2(response))
+    }
+
+    /// devnet_traceTransactionDecoded
+    pub async fn get_decoded_trace_transaction(
+        &self,
+        transaction_hash: TransactionHash,
+    ) -> StrictRpcResult {
+        let starknet = self.api.starknet.read().await;
+        match starknet.get_decoded_transaction_trace_by_hash(transaction_hash) {
+            Ok(result) => Ok(StarknetResponse::TraceTransactionDecoded(result)),
+            Err(Error::NoTransaction) => Err(ApiError::TransactionNotFound),
+            Err(Error::UnsupportedTransactionType) => Err(ApiError::NoTraceAvailable),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// devnet_profileTransaction
+    pub async fn profile_transaction(&self, transaction_hash: TransactionHash) -> StrictRpcResult {
+        let starknet = self.api.starknet.read().await;
+        match starknet.profile_transaction(transaction_hash) {
+            Ok(profile) => Ok(StarknetResponse::ProfileTransaction(profile)),
+            Err(Error::NoTransaction) => Err(ApiError::TransactionNotFound),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// devnet_callBundle
+    pub async fn call_bundle(
+        &self,
+        block_id: Option<BlockId>,
+        transactions: Vec<BroadcastedTransaction>,
+        simulation_flags: Vec<SimulationFlag>,
+        calls: Vec<FunctionCall>,
+    ) -> StrictRpcResult {
+        let block_id = block_id.unwrap_or(BlockId::from(ImportedBlockId::Tag(BlockTag::Latest)));
+        let starknet = self.api.starknet.read().await;
+
+        match starknet.call_bundle(block_id.as_ref(), &transactions, &simulation_flags, calls) {
+            Ok((transaction_traces, call_results)) => {
+                Ok(StarknetResponse::CallBundle(CallBundleOutput {
+                    transaction_traces,
+                    call_results,
+                }))
+            }
+            Err(Error::NoBlock) => Err(ApiError::BlockNotFound),
+            Err(Error::ContractNotFound) => Err(ApiError::ContractNotFound),
+            Err(err) => Err(ApiError::ContractError { error: err }),
+        }
+    }
+
+    /// devnet_traceCall
+    pub async fn trace_call(
+        &self,
+        block_id: BlockId,
+        request: FunctionCall,
+        state_override: Option<StateOverride>,
+    ) -> StrictRpcResult {
+        let starknet = self.api.starknet.read().await;
+
+        match starknet.trace_call(
+            block_id.as_ref(),
+            request.contract_address.into(),
+            request.entry_point_selector,
+            request.calldata,
+            state_override.as_ref(),
+        ) {
+            Ok(invocation) => Ok(StarknetResponse::TraceCall(invocation)),
+            Err(Error::NoBlock) => Err(ApiError::BlockNotFound),
+            Err(Error::ContractNotFound) => Err(ApiError::ContractNotFound),
+            Err(Error::StateError(StateError::NoneClassHash(_))) => {
+                Err(ApiError::ClassHashNotFound)
+            }
+            Err(err) => Err(ApiError::ContractError { error: err }),
+        }
+    }
+}
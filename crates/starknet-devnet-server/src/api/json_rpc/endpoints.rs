@@ -1,26 +1,34 @@
 use starknet_core::error::{Error, StateError};
-use starknet_rs_core::types::{BlockId as ImportedBlockId, MsgFromL1};
+use starknet_core::starknet::events::EventsContinuationToken;
+use starknet_rs_core::types::{BlockId as ImportedBlockId, MsgFromL1, TransactionStatus};
 use starknet_types::contract_address::ContractAddress;
 use starknet_types::felt::{ClassHash, TransactionHash};
 use starknet_types::patricia_key::PatriciaKey;
 use starknet_types::rpc::block::{Block, BlockHeader, BlockId};
+use starknet_types::rpc::block_override::BlockOverride;
 use starknet_types::rpc::state::StateUpdate;
+use starknet_types::rpc::state_override::StateOverride;
 use starknet_types::rpc::transactions::{
     BroadcastedTransaction, EventFilter, EventsChunk, FunctionCall, SimulationFlag,
 };
+use starknet_types::starknet_api::block::BlockStatus;
 use starknet_types::traits::ToHexString;
 
 use super::error::{ApiError, StrictRpcResult};
 use super::models::{BlockHashAndNumberOutput, SyncingOutput, TransactionStatusOutput};
-use super::{JsonRpcHandler, StarknetResponse, RPC_SPEC_VERSION};
-
-const DEFAULT_CONTINUATION_TOKEN: &str = "0";
+use super::spec_reader::discover_document;
+use super::{JsonRpcHandler, StarknetResponse};
 
 /// here are the definitions and stub implementations of all JSON-RPC read endpoints
 impl JsonRpcHandler {
     /// starknet_specVersion
     pub fn spec_version(&self) -> StrictRpcResult {
-        Ok(StarknetResponse::SpecVersion(RPC_SPEC_VERSION.to_string()))
+        Ok(StarknetResponse::SpecVersion(self.spec_version.as_str().to_string()))
+    }
+
+    /// rpc.discover
+    pub fn discover(&self) -> StrictRpcResult {
+        Ok(StarknetResponse::Discover(discover_document().clone()))
     }
 
     /// starknet_getBlockWithTxHashes
@@ -33,13 +41,20 @@ impl JsonRpcHandler {
                 },
             )?;
 
-        Ok(StarknetResponse::BlockWithTransactionHashes(Block {
+        let block = Block {
             status: *block.status(),
             header: BlockHeader::from(&block),
             transactions: starknet_types::rpc::transactions::Transactions::Hashes(
                 block.get_transactions().to_owned(),
             ),
-        }))
+        };
+
+        match block.status {
+            BlockStatus::Pending => {
+                Ok(StarknetResponse::PendingBlockWithTransactionHashes(block.into()))
+            }
+            _ => Ok(StarknetResponse::BlockWithTransactionHashes(block)),
+        }
     }
 
     /// starknet_getBlockWithTxs
@@ -53,7 +68,12 @@ impl JsonRpcHandler {
                 },
             )?;
 
-        Ok(StarknetResponse::BlockWithFullTransactions(block))
+        match block.status {
+            BlockStatus::Pending => {
+                Ok(StarknetResponse::PendingBlockWithFullTransactions(block.into()))
+            }
+            _ => Ok(StarknetResponse::BlockWithFullTransactions(block)),
+        }
     }
 
     /// starknet_getStateUpdate
@@ -116,19 +136,17 @@ impl JsonRpcHandler {
         &self,
         transaction_hash: TransactionHash,
     ) -> StrictRpcResult {
-        match self
-            .api
-            .starknet
-            .read()
-            .await
-            .get_transaction_execution_and_finality_status(transaction_hash)
-        {
-            Ok((execution_status, finality_status)) => {
+        let starknet = self.api.starknet.read().await;
+        match starknet.get_transaction_status(transaction_hash) {
+            Ok(TransactionStatus::Rejected) => {
                 Ok(StarknetResponse::TransactionStatusByHash(TransactionStatusOutput {
-                    execution_status,
-                    finality_status,
+                    failure_reason: starknet
+                        .get_rejection_reason(&transaction_hash)
+                        .map(str::to_string),
+                    ..TransactionStatus::Rejected.into()
                 }))
             }
+            Ok(status) => Ok(StarknetResponse::TransactionStatusByHash(status.into())),
             Err(Error::NoTransaction) => Err(ApiError::TransactionNotFound),
             Err(err) => Err(err.into()),
         }
@@ -231,7 +249,12 @@ impl JsonRpcHandler {
     }
 
     /// starknet_call
-    pub async fn call(&self, block_id: BlockId, request: FunctionCall) -> StrictRpcResult {
+    pub async fn call(
+        &self,
+        block_id: BlockId,
+        request: FunctionCall,
+        state_override: Option<StateOverride>,
+    ) -> StrictRpcResult {
         let starknet = self.api.starknet.read().await;
 
         match starknet.call(
@@ -239,10 +262,14 @@ impl JsonRpcHandler {
             request.contract_address.into(),
             request.entry_point_selector,
             request.calldata,
+            state_override.as_ref(),
         ) {
             Ok(result) => Ok(StarknetResponse::Call(result)),
             Err(Error::NoBlock) => Err(ApiError::BlockNotFound),
             Err(Error::ContractNotFound) => Err(ApiError::ContractNotFound),
+            Err(Error::StateError(StateError::NoneClassHash(_))) => {
+                Err(ApiError::ClassHashNotFound)
+            }
             Err(err) => Err(ApiError::ContractError { error: err }),
         }
     }
@@ -253,12 +280,23 @@ impl JsonRpcHandler {
         block_id: BlockId,
         request: Vec<BroadcastedTransaction>,
         simulation_flags: Vec<SimulationFlag>,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
     ) -> StrictRpcResult {
         let starknet = self.api.starknet.read().await;
-        match starknet.estimate_fee(block_id.as_ref(), &request, &simulation_flags) {
+        match starknet.estimate_fee(
+            block_id.as_ref(),
+            &request,
+            &simulation_flags,
+            state_override.as_ref(),
+            block_override.as_ref(),
+        ) {
             Ok(result) => Ok(StarknetResponse::EsimateFee(result)),
             Err(Error::ContractNotFound) => Err(ApiError::ContractNotFound),
             Err(Error::NoBlock) => Err(ApiError::BlockNotFound),
+            Err(Error::StateError(StateError::NoneClassHash(_))) => {
+                Err(ApiError::ClassHashNotFound)
+            }
             Err(err) => Err(ApiError::ContractError { error: err }),
         }
     }
@@ -315,24 +353,24 @@ impl JsonRpcHandler {
     pub async fn get_events(&self, filter: EventFilter) -> StrictRpcResult {
         let starknet = self.api.starknet.read().await;
 
-        let page = filter
+        let continuation_token = filter
             .continuation_token
-            .unwrap_or(DEFAULT_CONTINUATION_TOKEN.to_string())
-            .parse::<usize>()
+            .map(|token| token.parse::<EventsContinuationToken>())
+            .transpose()
             .map_err(|_| ApiError::InvalidContinuationToken)?;
 
-        let (events, has_more_events) = starknet.get_events(
+        let (events, continuation_token) = starknet.get_events(
             filter.from_block,
             filter.to_block,
             filter.address,
             filter.keys,
-            page * filter.chunk_size,
+            continuation_token,
             Some(filter.chunk_size),
         )?;
 
         Ok(StarknetResponse::Events(EventsChunk {
             events,
-            continuation_token: if has_more_events { Some((page + 1).to_string()) } else { None },
+            continuation_token: continuation_token.map(|token| token.to_string()),
         }))
     }
 
@@ -365,13 +403,23 @@ impl JsonRpcHandler {
         block_id: BlockId,
         transactions: Vec<BroadcastedTransaction>,
         simulation_flags: Vec<SimulationFlag>,
+        state_override: Option<StateOverride>,
+        block_override: Option<BlockOverride>,
     ) -> StrictRpcResult {
-        // borrowing as write/mutable because trace calculation requires so
-        let mut starknet = self.api.starknet.write().await;
-        match starknet.simulate_transactions(block_id.as_ref(), &transactions, simulation_flags) {
+        let starknet = self.api.starknet.read().await;
+        match starknet.simulate_transactions(
+            block_id.as_ref(),
+            &transactions,
+            simulation_flags,
+            state_override.as_ref(),
+            block_override.as_ref(),
+        ) {
             Ok(result) => Ok(StarknetResponse::SimulateTransactions(result)),
             Err(Error::ContractNotFound) => Err(ApiError::ContractNotFound),
             Err(Error::NoBlock) => Err(ApiError::BlockNotFound),
+            Err(Error::StateError(StateError::NoneClassHash(_))) => {
+                Err(ApiError::ClassHashNotFound)
+            }
             Err(err) => Err(ApiError::ContractError { error: err }),
         }
     }
@@ -400,3 +448,41 @@ impl JsonRpcHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+    use starknet_core::starknet::starknet_config::StarknetConfig;
+    use starknet_core::starknet::Starknet;
+    use starknet_rs_core::types::{BlockId as ImportedBlockId, BlockTag};
+
+    use crate::api::json_rpc::{JsonRpcHandler, RpcSpecVersion, StarknetResponse};
+    use crate::api::Api;
+
+    #[test]
+    fn simulation_is_not_blocked_by_concurrent_reads() {
+        let starknet = Starknet::new(&StarknetConfig::default()).unwrap();
+        let handler = JsonRpcHandler {
+            api: Api::new(starknet),
+            spec_version: RpcSpecVersion::V0_6,
+            validate_rpc_schema: false,
+            require_api_key_for_writes: false,
+            disable_devnet_api: false,
+        };
+
+        // a request reading the state, e.g. starknet_call, holds the lock during the simulation
+        let _read_guard = handler.api.starknet.try_read().unwrap();
+        let simulation = handler
+            .simulate_transactions(
+                ImportedBlockId::Tag(BlockTag::Latest).into(),
+                vec![],
+                vec![],
+                None,
+                None,
+            )
+            .now_or_never()
+            .expect("The simulation waited for the lock");
+
+        assert!(matches!(simulation, Ok(StarknetResponse::SimulateTransactions(_))));
+    }
+}
@@ -1,9 +1,11 @@
 use serde_json::json;
+use starknet_core::revert_reason::decode_revert_reason;
 use starknet_types;
 use thiserror::Error;
 use tracing::error;
 
 use super::{StarknetResponse, WILDCARD_RPC_ERROR_CODE};
+use crate::api::http::error::HttpApiError;
 use crate::rpc_core::error::RpcError;
 
 #[allow(unused)]
@@ -53,6 +55,8 @@ pub enum ApiError {
     ValidationFailure { reason: String },
     #[error("No trace available for transaction")]
     NoTraceAvailable,
+    #[error(transparent)]
+    HttpApiError(#[from] HttpApiError),
 }
 
 impl ApiError {
@@ -90,7 +94,9 @@ impl ApiError {
                 message: error_message.into(),
                 data: Some(json!(
                     {
-                        "revert_error": anyhow::format_err!(inner_error).root_cause().to_string()
+                        "revert_error": decode_revert_reason(
+                            &anyhow::format_err!(inner_error).root_cause().to_string()
+                        )
                     }
                 )),
             },
@@ -181,6 +187,17 @@ impl ApiError {
                 message: error_message.into(),
                 data: None,
             },
+            ApiError::HttpApiError(HttpApiError::InvalidValueError { .. }) => {
+                RpcError::invalid_params(error_message)
+            }
+            ApiError::HttpApiError(HttpApiError::RestartError { .. }) => {
+                RpcError::internal_error_with(error_message)
+            }
+            ApiError::HttpApiError(_) => RpcError {
+                code: crate::rpc_core::error::ErrorCode::ServerError(WILDCARD_RPC_ERROR_CODE),
+                message: error_message.into(),
+                data: None,
+            },
         }
     }
 }
@@ -1,30 +1,65 @@
+mod devnet_endpoints;
 mod endpoints;
 pub mod error;
 pub mod models;
-#[cfg(test)]
 mod spec_reader;
+mod v0_5;
 mod write_endpoints;
 
 pub const RPC_SPEC_VERSION: &str = "0.6.0";
+pub const RPC_SPEC_VERSION_0_5: &str = "0.5.1";
+
+/// Versions of the JSON-RPC specification that can be served. Each version is available on its
+/// own route, the latest one is also served on the unversioned routes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RpcSpecVersion {
+    V0_5,
+    #[default]
+    V0_6,
+}
+
+impl RpcSpecVersion {
+    pub const ALL: [RpcSpecVersion; 2] = [RpcSpecVersion::V0_5, RpcSpecVersion::V0_6];
+
+    /// The version reported by `starknet_specVersion`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RpcSpecVersion::V0_5 => RPC_SPEC_VERSION_0_5,
+            RpcSpecVersion::V0_6 => RPC_SPEC_VERSION,
+        }
+    }
+
+    /// The path on which the version is served, e.g. `/rpc/v0_5`
+    pub fn route(&self) -> &'static str {
+        match self {
+            RpcSpecVersion::V0_5 => "/rpc/v0_5",
+            RpcSpecVersion::V0_6 => "/rpc/v0_6",
+        }
+    }
+}
 
 use models::{
-    BlockAndClassHashInput, BlockAndContractAddressInput, BlockAndIndexInput, CallInput,
-    EstimateFeeInput, EventsInput, GetStorageInput, TransactionHashInput,
+    BlockAndClassHashInput, BlockAndContractAddressInput, BlockAndIndexInput, CallBundleInput,
+    CallBundleOutput, CallInput, EstimateFeeInput, EventsInput, GetStorageInput,
+    MempoolTransactionOutput, TransactionHashInput,
 };
 use serde::{Deserialize, Serialize};
 use starknet_rs_core::types::ContractClass as CodegenContractClass;
 use starknet_types::felt::{ClassHash, Felt};
-use starknet_types::rpc::block::Block;
+use starknet_types::rpc::block::{Block, PendingBlock};
 use starknet_types::rpc::estimate_message_fee::{
     EstimateMessageFeeRequestWrapper, FeeEstimateWrapper,
 };
+use starknet_types::rpc::execution_profile::ExecutionProfile;
+use starknet_types::rpc::messaging::{MessageToL1, MessageToL2};
 use starknet_types::rpc::state::StateUpdate;
 use starknet_types::rpc::transaction_receipt::TransactionReceipt;
 use starknet_types::rpc::transactions::{
-    BlockTransactionTraces, EventsChunk, SimulatedTransaction, Transaction, TransactionTrace,
+    BlockTransactionTraces, EventsChunk, FunctionInvocation, SimulatedTransaction, Transaction,
+    TransactionTrace,
 };
 use starknet_types::starknet_api::block::BlockNumber;
-use tracing::{error, info, trace};
+use tracing::{error, info, trace, warn};
 
 use self::error::StrictRpcResult;
 use self::models::{
@@ -34,6 +69,11 @@ use self::models::{
     SyncingOutput, TransactionStatusOutput,
 };
 use super::Api;
+use crate::api::http::models::{
+    CreatedBlock, DumpPath, FlushParameters, FlushedMessages, IncreaseTimeResponse, LoadPath,
+    MessageHash, MessagingLoadAddress, MintTokensRequest, MintTokensResponse,
+    PostmanLoadL1MessagingContract, SerializableAccount, SetTimeResponse, Time, TxHash,
+};
 use crate::api::json_rpc::models::{
     BroadcastedDeclareTransactionEnumWrapper, BroadcastedDeployAccountTransactionEnumWrapper,
     BroadcastedInvokeTransactionEnumWrapper, SimulateTransactionsInput,
@@ -48,6 +88,29 @@ pub trait ToRpcResponseResult {
     fn to_rpc_result(self) -> ResponseResult;
 }
 
+/// Devnet methods which control the state of Devnet. They require the API key, if one is set, and
+/// are not served if the devnet API is disabled.
+const DEVNET_CONTROL_METHODS: [&str; 11] = [
+    "devnet_mint",
+    "devnet_createBlock",
+    "devnet_setTime",
+    "devnet_increaseTime",
+    "devnet_dump",
+    "devnet_load",
+    "devnet_restart",
+    "devnet_postmanLoad",
+    "devnet_postmanFlush",
+    "devnet_postmanSendMessageToL2",
+    "devnet_postmanConsumeMessageFromL2",
+];
+
+/// Methods which add transactions. They require the API key, if configured so.
+const WRITE_METHODS: [&str; 3] = [
+    "starknet_addDeclareTransaction",
+    "starknet_addDeployAccountTransaction",
+    "starknet_addInvokeTransaction",
+];
+
 /// Used when there is no defined code to use
 pub const WILDCARD_RPC_ERROR_CODE: i64 = -1;
 
@@ -77,6 +140,14 @@ impl ToRpcResponseResult for StrictRpcResult {
 #[derive(Clone)]
 pub struct JsonRpcHandler {
     pub api: Api,
+    pub spec_version: RpcSpecVersion,
+    /// If set, requests and responses are checked against the spec. Only the latest version of
+    /// the spec is bundled, so the older versions are not checked.
+    pub validate_rpc_schema: bool,
+    /// If set, adding transactions requires the API key as well as the devnet control methods
+    pub require_api_key_for_writes: bool,
+    /// If set, the devnet control methods are not served
+    pub disable_devnet_api: bool,
 }
 
 #[async_trait::async_trait]
@@ -85,82 +156,142 @@ impl RpcHandler for JsonRpcHandler {
 
     async fn on_request(&self, request: Self::Request) -> ResponseResult {
         info!(target: "rpc", "received method in on_request {}", request);
-        self.execute(request).await
+        match self.spec_version {
+            RpcSpecVersion::V0_5 => match v0_5::validate_request(&request) {
+                Ok(()) => v0_5::to_rpc_result(self.execute(request).await),
+                Err(err) => err.api_error_to_rpc_error().into(),
+            },
+            RpcSpecVersion::V0_6 => self.execute(request).await.to_rpc_result(),
+        }
+    }
+
+    fn requires_api_key(&self, method: &str) -> bool {
+        DEVNET_CONTROL_METHODS.contains(&method)
+            || (self.require_api_key_for_writes && WRITE_METHODS.contains(&method))
+    }
+
+    fn validate_call(&self, method: &str, params: &serde_json::Value) -> Result<(), RpcError> {
+        if self.disable_devnet_api && DEVNET_CONTROL_METHODS.contains(&method) {
+            return Err(RpcError::method_not_found());
+        }
+
+        if !self.validates_rpc_schema() {
+            return Ok(());
+        }
+
+        spec_reader::validate_params(method, params).map_err(|err| {
+            warn!(target: "rpc", method, err, "request does not match the spec");
+            RpcError::invalid_params(format!("Request does not match the spec: {err}"))
+        })
+    }
+
+    fn deserialize_request(&self, call: serde_json::Value) -> serde_json::Result<Self::Request> {
+        match self.spec_version {
+            RpcSpecVersion::V0_5 => v0_5::deserialize_request(call),
+            RpcSpecVersion::V0_6 => serde_json::from_value(call),
+        }
+    }
+
+    fn validate_result(&self, method: &str, result: ResponseResult) -> ResponseResult {
+        let violation = match &result {
+            ResponseResult::Success(value) if self.validates_rpc_schema() => {
+                spec_reader::validate_result(method, value).err()
+            }
+            _ => None,
+        };
+
+        match violation {
+            Some(err) => {
+                error!(target: "rpc", method, err, "response does not match the spec");
+                RpcError::internal_error_with(format!("Response does not match the spec: {err}"))
+                    .into()
+            }
+            None => result,
+        }
     }
 }
 
 impl JsonRpcHandler {
+    fn validates_rpc_schema(&self) -> bool {
+        self.validate_rpc_schema && self.spec_version == RpcSpecVersion::V0_6
+    }
+
     /// The method matches the request to the corresponding enum variant and executes the request
-    async fn execute(&self, request: StarknetRequest) -> ResponseResult {
+    async fn execute(&self, request: StarknetRequest) -> StrictRpcResult {
         trace!(target: "JsonRpcHandler::execute", "executing starknet request");
 
         match request {
-            StarknetRequest::SpecVersion => self.spec_version().to_rpc_result(),
+            StarknetRequest::SpecVersion => self.spec_version(),
             StarknetRequest::BlockWithTransactionHashes(block) => {
-                self.get_block_with_tx_hashes(block.block_id).await.to_rpc_result()
+                self.get_block_with_tx_hashes(block.block_id).await
             }
             StarknetRequest::BlockWithFullTransactions(block) => {
-                self.get_block_with_txs(block.block_id).await.to_rpc_result()
-            }
-            StarknetRequest::StateUpdate(block) => {
-                self.get_state_update(block.block_id).await.to_rpc_result()
+                self.get_block_with_txs(block.block_id).await
             }
+            StarknetRequest::StateUpdate(block) => self.get_state_update(block.block_id).await,
             StarknetRequest::StorageAt(GetStorageInput { contract_address, key, block_id }) => {
-                self.get_storage_at(contract_address, key, block_id).await.to_rpc_result()
+                self.get_storage_at(contract_address, key, block_id).await
             }
             StarknetRequest::TransactionStatusByHash(TransactionHashInput { transaction_hash }) => {
-                self.get_transaction_status_by_hash(transaction_hash).await.to_rpc_result()
+                self.get_transaction_status_by_hash(transaction_hash).await
             }
             StarknetRequest::TransactionByHash(TransactionHashInput { transaction_hash }) => {
-                self.get_transaction_by_hash(transaction_hash).await.to_rpc_result()
+                self.get_transaction_by_hash(transaction_hash).await
             }
             StarknetRequest::TransactionByBlockAndIndex(BlockAndIndexInput { block_id, index }) => {
-                self.get_transaction_by_block_id_and_index(block_id, index).await.to_rpc_result()
+                self.get_transaction_by_block_id_and_index(block_id, index).await
             }
             StarknetRequest::TransactionReceiptByTransactionHash(TransactionHashInput {
                 transaction_hash,
-            }) => self.get_transaction_receipt_by_hash(transaction_hash).await.to_rpc_result(),
+            }) => self.get_transaction_receipt_by_hash(transaction_hash).await,
             StarknetRequest::ClassByHash(BlockAndClassHashInput { block_id, class_hash }) => {
-                self.get_class(block_id, class_hash).await.to_rpc_result()
+                self.get_class(block_id, class_hash).await
             }
             StarknetRequest::ClassHashAtContractAddress(BlockAndContractAddressInput {
                 block_id,
                 contract_address,
-            }) => self.get_class_hash_at(block_id, contract_address).await.to_rpc_result(),
+            }) => self.get_class_hash_at(block_id, contract_address).await,
             StarknetRequest::ClassAtContractAddress(BlockAndContractAddressInput {
                 block_id,
                 contract_address,
-            }) => self.get_class_at(block_id, contract_address).await.to_rpc_result(),
+            }) => self.get_class_at(block_id, contract_address).await,
             StarknetRequest::BlockTransactionCount(block) => {
-                self.get_block_txs_count(block.block_id).await.to_rpc_result()
+                self.get_block_txs_count(block.block_id).await
             }
-            StarknetRequest::Call(CallInput { request, block_id }) => {
-                self.call(block_id, request).await.to_rpc_result()
+            StarknetRequest::Call(CallInput { request, block_id, state_override }) => {
+                self.call(block_id, request, state_override).await
             }
             StarknetRequest::EsimateFee(EstimateFeeInput {
                 request,
                 block_id,
                 simulation_flags,
-            }) => self.estimate_fee(block_id, request, simulation_flags).await.to_rpc_result(),
-            StarknetRequest::BlockNumber => self.block_number().await.to_rpc_result(),
-            StarknetRequest::BlockHashAndNumber => {
-                self.block_hash_and_number().await.to_rpc_result()
-            }
-            StarknetRequest::ChainId => self.chain_id().await.to_rpc_result(),
-            StarknetRequest::Syncing => self.syncing().await.to_rpc_result(),
-            StarknetRequest::Events(EventsInput { filter }) => {
-                self.get_events(filter).await.to_rpc_result()
+                state_override,
+                block_override,
+            }) => {
+                self.estimate_fee(
+                    block_id,
+                    request,
+                    simulation_flags,
+                    state_override,
+                    block_override,
+                )
+                .await
             }
+            StarknetRequest::BlockNumber => self.block_number().await,
+            StarknetRequest::BlockHashAndNumber => self.block_hash_and_number().await,
+            StarknetRequest::ChainId => self.chain_id().await,
+            StarknetRequest::Syncing => self.syncing().await,
+            StarknetRequest::Events(EventsInput { filter }) => self.get_events(filter).await,
             StarknetRequest::ContractNonce(BlockAndContractAddressInput {
                 block_id,
                 contract_address,
-            }) => self.get_nonce(block_id, contract_address).await.to_rpc_result(),
+            }) => self.get_nonce(block_id, contract_address).await,
             StarknetRequest::AddDeclareTransaction(BroadcastedDeclareTransactionInput {
                 declare_transaction,
             }) => {
                 let BroadcastedDeclareTransactionEnumWrapper::Declare(broadcasted_transaction) =
                     declare_transaction;
-                self.add_declare_transaction(broadcasted_transaction).await.to_rpc_result()
+                self.add_declare_transaction(broadcasted_transaction).await
             }
             StarknetRequest::AddDeployAccountTransaction(
                 BroadcastedDeployAccountTransactionInput { deploy_account_transaction },
@@ -168,33 +299,74 @@ impl JsonRpcHandler {
                 let BroadcastedDeployAccountTransactionEnumWrapper::DeployAccount(
                     broadcasted_transaction,
                 ) = deploy_account_transaction;
-                self.add_deploy_account_transaction(broadcasted_transaction).await.to_rpc_result()
+                self.add_deploy_account_transaction(broadcasted_transaction).await
             }
             StarknetRequest::AddInvokeTransaction(BroadcastedInvokeTransactionInput {
                 invoke_transaction,
             }) => {
                 let BroadcastedInvokeTransactionEnumWrapper::Invoke(broadcasted_transaction) =
                     invoke_transaction;
-                self.add_invoke_transaction(broadcasted_transaction).await.to_rpc_result()
+                self.add_invoke_transaction(broadcasted_transaction).await
+            }
+            StarknetRequest::EstimateMessageFee(request) => {
+                self.estimate_message_fee(request.get_block_id(), request.get_raw_message().clone())
+                    .await
             }
-            StarknetRequest::EstimateMessageFee(request) => self
-                .estimate_message_fee(request.get_block_id(), request.get_raw_message().clone())
-                .await
-                .to_rpc_result(),
             StarknetRequest::SimulateTransactions(SimulateTransactionsInput {
                 block_id,
                 transactions,
                 simulation_flags,
-            }) => self
-                .simulate_transactions(block_id, transactions, simulation_flags)
+                state_override,
+                block_override,
+            }) => {
+                self.simulate_transactions(
+                    block_id,
+                    transactions,
+                    simulation_flags,
+                    state_override,
+                    block_override,
+                )
                 .await
-                .to_rpc_result(),
+            }
             StarknetRequest::TraceTransaction(TransactionHashInput { transaction_hash }) => {
-                self.get_trace_transaction(transaction_hash).await.to_rpc_result()
+                self.get_trace_transaction(transaction_hash).await
             }
             StarknetRequest::BlockTransactionTraces(BlockIdInput { block_id }) => {
-                self.get_trace_block_transactions(block_id).await.to_rpc_result()
+                self.get_trace_block_transactions(block_id).await
+            }
+            StarknetRequest::Mint(request) => self.mint(request).await,
+            StarknetRequest::CreateBlock => self.create_block().await,
+            StarknetRequest::SetTime(time) => self.set_time(time).await,
+            StarknetRequest::IncreaseTime(time) => self.increase_time(time).await,
+            StarknetRequest::Dump(path) => self.dump(path).await,
+            StarknetRequest::Load(path) => self.load(path).await,
+            StarknetRequest::Restart => self.restart().await,
+            StarknetRequest::PredeployedAccounts => self.get_predeployed_accounts().await,
+            StarknetRequest::PostmanLoad(data) => self.postman_load(data).await,
+            StarknetRequest::PostmanFlush(data) => self.postman_flush(data).await,
+            StarknetRequest::PostmanSendMessageToL2(message) => {
+                self.postman_send_message_to_l2(message).await
+            }
+            StarknetRequest::PostmanConsumeMessageFromL2(message) => {
+                self.postman_consume_message_from_l2(message).await
+            }
+            StarknetRequest::TraceTransactionDecoded(TransactionHashInput { transaction_hash }) => {
+                self.get_decoded_trace_transaction(transaction_hash).await
+            }
+            StarknetRequest::TraceCall(CallInput { request, block_id, state_override }) => {
+                self.trace_call(block_id, request, state_override).await
             }
+            StarknetRequest::ProfileTransaction(TransactionHashInput { transaction_hash }) => {
+                self.profile_transaction(transaction_hash).await
+            }
+            StarknetRequest::CallBundle(CallBundleInput {
+                block_id,
+                transactions,
+                simulation_flags,
+                calls,
+            }) => self.call_bundle(block_id, transactions, simulation_flags, calls).await,
+            StarknetRequest::Mempool => self.get_mempool().await,
+            StarknetRequest::Discover => self.discover(),
         }
     }
 }
@@ -258,6 +430,42 @@ pub enum StarknetRequest {
     TraceTransaction(TransactionHashInput),
     #[serde(rename = "starknet_traceBlockTransactions")]
     BlockTransactionTraces(BlockIdInput),
+    #[serde(rename = "devnet_mint")]
+    Mint(MintTokensRequest),
+    #[serde(rename = "devnet_createBlock", with = "empty_params")]
+    CreateBlock,
+    #[serde(rename = "devnet_setTime")]
+    SetTime(Time),
+    #[serde(rename = "devnet_increaseTime")]
+    IncreaseTime(Time),
+    #[serde(rename = "devnet_dump")]
+    Dump(Option<DumpPath>),
+    #[serde(rename = "devnet_load")]
+    Load(LoadPath),
+    #[serde(rename = "devnet_restart", with = "empty_params")]
+    Restart,
+    #[serde(rename = "devnet_getPredeployedAccounts", with = "empty_params")]
+    PredeployedAccounts,
+    #[serde(rename = "devnet_postmanLoad")]
+    PostmanLoad(PostmanLoadL1MessagingContract),
+    #[serde(rename = "devnet_postmanFlush")]
+    PostmanFlush(Option<FlushParameters>),
+    #[serde(rename = "devnet_postmanSendMessageToL2")]
+    PostmanSendMessageToL2(MessageToL2),
+    #[serde(rename = "devnet_postmanConsumeMessageFromL2")]
+    PostmanConsumeMessageFromL2(MessageToL1),
+    #[serde(rename = "devnet_traceTransactionDecoded")]
+    TraceTransactionDecoded(TransactionHashInput),
+    #[serde(rename = "devnet_traceCall")]
+    TraceCall(CallInput),
+    #[serde(rename = "devnet_profileTransaction")]
+    ProfileTransaction(TransactionHashInput),
+    #[serde(rename = "devnet_callBundle")]
+    CallBundle(CallBundleInput),
+    #[serde(rename = "devnet_getMempool", with = "empty_params")]
+    Mempool,
+    #[serde(rename = "rpc.discover", with = "empty_params")]
+    Discover,
 }
 
 impl std::fmt::Display for StarknetRequest {
@@ -307,6 +515,30 @@ impl std::fmt::Display for StarknetRequest {
             StarknetRequest::BlockTransactionTraces(_) => {
                 write!(f, "starknet_traceBlockTransactions")
             }
+            StarknetRequest::Mint(_) => write!(f, "devnet_mint"),
+            StarknetRequest::CreateBlock => write!(f, "devnet_createBlock"),
+            StarknetRequest::SetTime(_) => write!(f, "devnet_setTime"),
+            StarknetRequest::IncreaseTime(_) => write!(f, "devnet_increaseTime"),
+            StarknetRequest::Dump(_) => write!(f, "devnet_dump"),
+            StarknetRequest::Load(_) => write!(f, "devnet_load"),
+            StarknetRequest::Restart => write!(f, "devnet_restart"),
+            StarknetRequest::PredeployedAccounts => write!(f, "devnet_getPredeployedAccounts"),
+            StarknetRequest::PostmanLoad(_) => write!(f, "devnet_postmanLoad"),
+            StarknetRequest::PostmanFlush(_) => write!(f, "devnet_postmanFlush"),
+            StarknetRequest::PostmanSendMessageToL2(_) => {
+                write!(f, "devnet_postmanSendMessageToL2")
+            }
+            StarknetRequest::PostmanConsumeMessageFromL2(_) => {
+                write!(f, "devnet_postmanConsumeMessageFromL2")
+            }
+            StarknetRequest::TraceTransactionDecoded(_) => {
+                write!(f, "devnet_traceTransactionDecoded")
+            }
+            StarknetRequest::TraceCall(_) => write!(f, "devnet_traceCall"),
+            StarknetRequest::ProfileTransaction(_) => write!(f, "devnet_profileTransaction"),
+            StarknetRequest::CallBundle(_) => write!(f, "devnet_callBundle"),
+            StarknetRequest::Mempool => write!(f, "devnet_getMempool"),
+            StarknetRequest::Discover => write!(f, "rpc.discover"),
         }
     }
 }
@@ -317,6 +549,8 @@ impl std::fmt::Display for StarknetRequest {
 pub enum StarknetResponse {
     BlockWithTransactionHashes(Block),
     BlockWithFullTransactions(Block),
+    PendingBlockWithTransactionHashes(PendingBlock),
+    PendingBlockWithFullTransactions(PendingBlock),
     StateUpdate(StateUpdate),
     StorageAt(Felt),
     TransactionByHash(Transaction),
@@ -343,6 +577,25 @@ pub enum StarknetResponse {
     SpecVersion(String),
     TraceTransaction(TransactionTrace),
     BlockTransactionTraces(BlockTransactionTraces),
+    Mint(MintTokensResponse),
+    CreateBlock(CreatedBlock),
+    SetTime(SetTimeResponse),
+    IncreaseTime(IncreaseTimeResponse),
+    Dump,
+    Load,
+    Restart,
+    PredeployedAccounts(Vec<SerializableAccount>),
+    PostmanLoad(MessagingLoadAddress),
+    PostmanFlush(FlushedMessages),
+    PostmanSendMessageToL2(TxHash),
+    PostmanConsumeMessageFromL2(MessageHash),
+    TraceCall(FunctionInvocation),
+    ProfileTransaction(ExecutionProfile),
+    CallBundle(CallBundleOutput),
+    Mempool(Vec<MempoolTransactionOutput>),
+    /// Must stay the last variants, so that the other responses are deserialized first
+    TraceTransactionDecoded(serde_json::Value),
+    Discover(serde_json::Value),
 }
 
 #[cfg(test)]
@@ -899,6 +1152,61 @@ mod requests_tests {
         }
     }
 
+    #[test]
+    fn deserialize_devnet_requests() {
+        for body in [
+            json!({"method": "devnet_mint", "params": {"address": "0x1", "amount": 10}}),
+            json!({"method": "devnet_createBlock", "params": []}),
+            json!({"method": "devnet_setTime", "params": {"time": 1}}),
+            json!({"method": "devnet_increaseTime", "params": {"time": 1}}),
+            json!({"method": "devnet_dump", "params": {"path": "dump_file"}}),
+            json!({"method": "devnet_dump", "params": null}),
+            json!({"method": "devnet_load", "params": {"path": "dump_file"}}),
+            json!({"method": "devnet_restart"}),
+            json!({"method": "devnet_getPredeployedAccounts", "params": {}}),
+            json!({"method": "devnet_postmanFlush", "params": {"dry_run": true}}),
+            json!({"method": "devnet_postmanFlush", "params": {}}),
+            json!({"method": "devnet_postmanFlush", "params": null}),
+            json!({
+                "method": "devnet_traceTransactionDecoded",
+                "params": {"transaction_hash": "0x1"}
+            }),
+            json!({
+                "method": "devnet_traceCall",
+                "params": {
+                    "request": {
+                        "contract_address": "0x1",
+                        "entry_point_selector": "0x2",
+                        "calldata": []
+                    },
+                    "block_id": "latest"
+                }
+            }),
+            json!({
+                "method": "devnet_profileTransaction",
+                "params": {"transaction_hash": "0x1"}
+            }),
+            json!({
+                "method": "devnet_callBundle",
+                "params": {
+                    "transactions": [],
+                    "calls": [
+                        {"contract_address": "0x1", "entry_point_selector": "0x2", "calldata": []}
+                    ]
+                }
+            }),
+            json!({"method": "devnet_getMempool"}),
+            json!({"method": "devnet_getMempool", "params": {}}),
+        ] {
+            assert_deserialization_succeeds(body.to_string().as_str())
+        }
+
+        assert_deserialization_fails(
+            &json!({"method": "devnet_mint", "params": {"address": "0x1"}}).to_string(),
+            "missing field `amount`",
+        );
+    }
+
     fn assert_deserialization_succeeds(json_str: &str) {
         serde_json::from_str::<StarknetRequest>(json_str).unwrap();
     }
@@ -1,13 +1,17 @@
 use serde::{Deserialize, Serialize};
-use starknet_rs_core::types::{TransactionExecutionStatus, TransactionFinalityStatus};
+use starknet_rs_core::types::{
+    SequencerTransactionStatus, TransactionExecutionStatus, TransactionStatus,
+};
 use starknet_types::contract_address::ContractAddress;
-use starknet_types::felt::{BlockHash, ClassHash, TransactionHash};
+use starknet_types::felt::{BlockHash, ClassHash, Felt, Nonce, TransactionHash};
 use starknet_types::patricia_key::PatriciaKey;
 use starknet_types::rpc::block::BlockId;
+use starknet_types::rpc::block_override::BlockOverride;
+use starknet_types::rpc::state_override::StateOverride;
 use starknet_types::rpc::transactions::{
     BroadcastedDeclareTransaction, BroadcastedDeployAccountTransaction,
     BroadcastedInvokeTransaction, BroadcastedTransaction, EventFilter, FunctionCall,
-    SimulationFlag,
+    SimulationFlag, TransactionTrace, TransactionType,
 };
 use starknet_types::starknet_api::block::BlockNumber;
 
@@ -59,6 +63,9 @@ pub struct BlockAndContractAddressInput {
 pub struct CallInput {
     pub request: FunctionCall,
     pub block_id: BlockId,
+    /// Devnet extension: changes applied to the state before the execution, never committed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_override: Option<StateOverride>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -67,6 +74,12 @@ pub struct EstimateFeeInput {
     pub request: Vec<BroadcastedTransaction>,
     pub simulation_flags: Vec<SimulationFlag>,
     pub block_id: BlockId,
+    /// Devnet extension: changes applied to the state before the execution, never committed
+    #[serde(default)]
+    pub state_override: Option<StateOverride>,
+    /// Devnet extension: fields of the block context replaced for the execution
+    #[serde(default)]
+    pub block_override: Option<BlockOverride>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -157,14 +170,68 @@ pub struct SimulateTransactionsInput {
     pub block_id: BlockId,
     pub transactions: Vec<BroadcastedTransaction>,
     pub simulation_flags: Vec<SimulationFlag>,
+    /// Devnet extension: changes applied to the state before the execution, never committed
+    #[serde(default)]
+    pub state_override: Option<StateOverride>,
+    /// Devnet extension: fields of the block context replaced for the execution
+    #[serde(default)]
+    pub block_override: Option<BlockOverride>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CallBundleInput {
+    /// The latest block if not given
+    #[serde(default)]
+    pub block_id: Option<BlockId>,
+    pub transactions: Vec<BroadcastedTransaction>,
+    #[serde(default)]
+    pub simulation_flags: Vec<SimulationFlag>,
+    pub calls: Vec<FunctionCall>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize))]
+#[serde(deny_unknown_fields)]
+pub struct CallBundleOutput {
+    pub transaction_traces: Vec<TransactionTrace>,
+    /// The results of the calls, executed after the transactions
+    pub call_results: Vec<Vec<Felt>>,
 }
 
 #[derive(Debug, Serialize)]
 #[cfg_attr(test, derive(Deserialize))]
 #[serde(deny_unknown_fields)]
 pub struct TransactionStatusOutput {
-    pub finality_status: TransactionFinalityStatus,
-    pub execution_status: TransactionExecutionStatus,
+    pub finality_status: SequencerTransactionStatus,
+    /// Not known until the transaction is executed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_status: Option<TransactionExecutionStatus>,
+    /// Why the transaction was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+}
+
+impl From<TransactionStatus> for TransactionStatusOutput {
+    fn from(status: TransactionStatus) -> Self {
+        let execution_status = match status {
+            TransactionStatus::AcceptedOnL2(execution_status)
+            | TransactionStatus::AcceptedOnL1(execution_status) => Some(execution_status),
+            TransactionStatus::Received | TransactionStatus::Rejected => None,
+        };
+
+        Self { finality_status: status.finality_status(), execution_status, failure_reason: None }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize))]
+#[serde(deny_unknown_fields)]
+pub struct MempoolTransactionOutput {
+    pub transaction_hash: TransactionHash,
+    pub r#type: TransactionType,
+    pub sender_address: ContractAddress,
+    pub nonce: Nonce,
 }
 
 #[cfg(test)]
@@ -392,10 +459,40 @@ mod tests {
                     calldata: vec![Felt::from_prefixed_hex_str("0x03").unwrap()],
                 },
                 block_id: BlockId::from(ImportedBlockId::Number(1)),
+                state_override: None,
             }
         );
     }
 
+    #[test]
+    fn deserialize_call_input_with_state_override() {
+        let json_str = r#"{
+            "request": {"contract_address": "0x01", "entry_point_selector": "0x02", "calldata": []},
+            "block_id": "latest",
+            "state_override": {
+                "0x04": {"nonce": "0x5", "storage": {"0x6": "0x7"}, "eth_balance": "0x8"}
+            }
+        }"#;
+        let call_input = serde_json::from_str::<super::CallInput>(json_str).unwrap();
+
+        let state_override = call_input.state_override.unwrap();
+        let contract_override = state_override
+            .get(&ContractAddress::new(Felt::from_prefixed_hex_str("0x4").unwrap()).unwrap())
+            .unwrap();
+        assert_eq!(contract_override.nonce, Some(Felt::from(5)));
+        assert_eq!(contract_override.storage.get(&Felt::from(6)), Some(&Felt::from(7)));
+        assert_eq!(contract_override.eth_balance, Some(Felt::from(8)));
+        assert_eq!(contract_override.class_hash, None);
+        assert_eq!(contract_override.strk_balance, None);
+
+        let unknown_override = r#"{
+            "request": {"contract_address": "0x01", "entry_point_selector": "0x02", "calldata": []},
+            "block_id": "latest",
+            "state_override": {"0x04": {"code": "0x5"}}
+        }"#;
+        assert!(serde_json::from_str::<super::CallInput>(unknown_override).is_err());
+    }
+
     #[test]
     fn deserialize_get_storage_input() {
         fn assert_get_storage_input_correctness(
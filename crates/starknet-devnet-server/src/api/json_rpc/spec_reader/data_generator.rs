@@ -229,5 +229,6 @@ pub fn generate_schema_value(
         Schema::Primitive(Primitive::Array(array)) => array.accept(&generator),
         Schema::Primitive(Primitive::Boolean(boolean)) => boolean.accept(&generator),
         Schema::Primitive(Primitive::Object(obj)) => obj.accept(&generator),
+        Schema::Primitive(Primitive::Null(_)) => Ok(Value::Null),
     }
 }
@@ -1,20 +1,32 @@
 use std::collections::HashMap;
+#[cfg(test)]
 use std::ffi::OsStr;
+#[cfg(test)]
 use std::fs;
+use std::sync::OnceLock;
 
 use serde::{Deserialize, Serialize};
-use serde_json::{Map, Value};
+#[cfg(test)]
+use serde_json::Map;
+use serde_json::{json, Value};
 
+#[cfg(test)]
 use self::data_generator::generate_schema_value;
 use self::spec_schemas::Schema;
+use super::RPC_SPEC_VERSION;
 
+#[cfg(test)]
 mod data_generator;
+#[cfg(test)]
 mod spec_modifier;
 mod spec_schemas;
+mod validator;
 
 #[derive(Serialize, Deserialize)]
 pub struct Spec {
     openrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    info: Option<Value>,
     methods: Vec<Method>,
     components: Components,
 }
@@ -22,13 +34,21 @@ pub struct Spec {
 #[derive(Serialize, Deserialize)]
 struct Method {
     name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
     params: Vec<Param>,
     result: Result,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errors: Option<Value>,
 }
 
 #[derive(Serialize, Deserialize)]
 struct Param {
     name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
     required: bool,
     schema: Schema,
 }
@@ -36,15 +56,123 @@ struct Param {
 #[derive(Serialize, Deserialize)]
 struct Result {
     name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
     schema: Schema,
 }
 
 #[derive(Serialize, Deserialize)]
 struct Components {
     schemas: HashMap<String, Schema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errors: Option<HashMap<String, Value>>,
+}
+
+/// The spec of the JSON-RPC API served by devnet. It is combined from the bundled Starknet specs
+/// and the spec of the devnet_* methods, which also overrides the Starknet methods that devnet
+/// serves differently.
+fn served_spec() -> &'static Spec {
+    static SPEC: OnceLock<Spec> = OnceLock::new();
+
+    SPEC.get_or_init(|| {
+        let mut spec = Spec::from_bundled(include_str!(
+            "../../../../test_data/spec/0.6.0/starknet_api_openrpc.json"
+        ));
+
+        for spec_str in [
+            include_str!("../../../../test_data/spec/0.6.0/starknet_write_api.json"),
+            include_str!("../../../../test_data/spec/0.6.0/starknet_trace_api_openrpc.json"),
+            include_str!("../../../../test_data/spec/devnet/devnet_api_openrpc.json"),
+        ] {
+            spec.merge(Spec::from_bundled(spec_str));
+        }
+
+        spec.info = Some(json!({ "title": "Starknet Devnet API", "version": RPC_SPEC_VERSION }));
+
+        spec
+    })
+}
+
+/// The OpenRPC document of the JSON-RPC API served by devnet, returned by `rpc.discover`
+pub(crate) fn discover_document() -> &'static Value {
+    static DOCUMENT: OnceLock<Value> = OnceLock::new();
+
+    DOCUMENT.get_or_init(|| {
+        serde_json::to_value(served_spec()).expect("Could not serialize the JSON-RPC spec")
+    })
+}
+
+/// Checks the params of a call against the spec of the method. Methods which are not in the spec
+/// are not checked.
+pub(crate) fn validate_params(
+    method_name: &str,
+    params: &Value,
+) -> core::result::Result<(), String> {
+    let spec = served_spec();
+    match spec.method(method_name) {
+        Some(method) => {
+            validator::validate_params(&method.params, params, &spec.components.schemas)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Checks the successful result of a call against the spec of the method. Methods which are not
+/// in the spec are not checked.
+pub(crate) fn validate_result(
+    method_name: &str,
+    result: &Value,
+) -> core::result::Result<(), String> {
+    let spec = served_spec();
+    match spec.method(method_name) {
+        Some(method) => validator::validate_value(
+            result,
+            &method.result.schema,
+            &spec.components.schemas,
+            "result",
+        ),
+        None => Ok(()),
+    }
 }
 
 impl Spec {
+    /// Parses a spec which is bundled with the binary. References to the other spec files are
+    /// turned into local references, so that the specs can be merged into a single document.
+    fn from_bundled(spec_str: &str) -> Self {
+        let spec_str = spec_str
+            .replace("\"./api/starknet_api_openrpc.json#", "\"#")
+            .replace("\"./starknet_api_openrpc.json#", "\"#");
+
+        serde_json::from_str(&spec_str).expect("Could not parse the JSON-RPC spec")
+    }
+
+    /// Adds the methods of `other` and the components which are not already defined. Methods of
+    /// `other` replace the methods with the same name.
+    fn merge(&mut self, other: Spec) {
+        for method in other.methods {
+            match self.methods.iter_mut().find(|existing| existing.name == method.name) {
+                Some(existing) => *existing = method,
+                None => self.methods.push(method),
+            }
+        }
+
+        for (name, schema) in other.components.schemas {
+            self.components.schemas.entry(name).or_insert(schema);
+        }
+
+        if let Some(other_errors) = other.components.errors {
+            let errors = self.components.errors.get_or_insert_with(HashMap::new);
+            for (name, error) in other_errors {
+                errors.entry(name).or_insert(error);
+            }
+        }
+    }
+
+    fn method(&self, name: &str) -> Option<&Method> {
+        self.methods.iter().find(|method| method.name == name)
+    }
+
+    #[cfg(test)]
     fn load_from_dir(dir_path: &str) -> Vec<Self> {
         let mut specs: Vec<Spec> = Vec::new();
 
@@ -76,6 +204,7 @@ impl Spec {
         specs
     }
 
+    #[cfg(test)]
     fn load_from_path(
         path: &str,
         modify_spec_instructions: &Option<spec_modifier::SpecModifier>,
@@ -97,6 +226,7 @@ impl Spec {
     }
 }
 
+#[cfg(test)]
 fn generate_combined_schema(specs: &Vec<Spec>) -> HashMap<String, Schema> {
     let mut combined_schema = HashMap::<String, Schema>::new();
 
@@ -128,6 +258,7 @@ fn generate_combined_schema(specs: &Vec<Spec>) -> HashMap<String, Schema> {
     combined_schema
 }
 
+#[cfg(test)]
 fn generate_json_rpc_request(
     method: &Method,
     schemas: &HashMap<String, Schema>,
@@ -154,6 +285,7 @@ fn generate_json_rpc_request(
         .map_err(|err| format!("Could not serialize the JSON-RPC request: {}", err))
 }
 
+#[cfg(test)]
 fn generate_json_rpc_response(
     method: &Method,
     schemas: &HashMap<String, Schema>,
@@ -161,10 +293,11 @@ fn generate_json_rpc_response(
     generate_schema_value(&method.result.schema, schemas, 0)
 }
 
+#[cfg(test)]
 mod tests {
     use std::fs::File;
 
-    use super::{generate_combined_schema, generate_json_rpc_response, Spec};
+    use super::{discover_document, generate_combined_schema, generate_json_rpc_response, Spec};
     use crate::api::json_rpc::spec_reader::generate_json_rpc_request;
     use crate::api::json_rpc::{StarknetRequest, StarknetResponse, RPC_SPEC_VERSION};
 
@@ -194,6 +327,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn discover_document_contains_only_served_methods() {
+        let document = discover_document();
+        let methods = document["methods"].as_array().unwrap();
+
+        for method in methods {
+            let name = method["name"].as_str().unwrap();
+            // parameters are not generated, so only check that the method itself is known
+            let err = serde_json::from_value::<StarknetRequest>(
+                serde_json::json!({ "method": name, "params": { "invalid_param": 0 } }),
+            )
+            .err();
+
+            if let Some(err) = err {
+                assert!(!err.to_string().contains("unknown variant"), "Unknown method {name}");
+            }
+        }
+
+        for name in ["starknet_traceBlockTransactions", "devnet_mint", "devnet_postmanFlush"] {
+            assert!(methods.iter().any(|method| method["name"] == name), "Missing method {name}");
+        }
+        assert_eq!(document["info"]["version"], RPC_SPEC_VERSION);
+        assert!(document["components"]["schemas"].get("TRANSACTION_TRACE").is_some());
+        assert!(document["components"]["errors"].get("BLOCK_NOT_FOUND").is_some());
+        assert!(!document.to_string().contains("starknet_api_openrpc.json"));
+    }
+
     #[test]
     fn test_spec_methods() {
         let specs =
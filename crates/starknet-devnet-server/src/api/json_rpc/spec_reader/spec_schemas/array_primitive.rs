@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::{Common, Schema};
+#[cfg(test)]
 use crate::api::json_rpc::spec_reader::data_generator::{Acceptor, Visitor};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -10,6 +11,7 @@ pub struct ArrayPrimitive {
     pub common: Common,
     pub items: Box<Schema>,
 }
+#[cfg(test)]
 impl Acceptor for ArrayPrimitive {
     fn accept(&self, visitor: &impl Visitor) -> Result<serde_json::Value, String> {
         visitor.do_for_array_primitive(self)
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::Common;
+#[cfg(test)]
 use crate::api::json_rpc::spec_reader::data_generator::{Acceptor, Visitor};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -12,6 +13,7 @@ pub struct BooleanPrimitive {
     pub generated_value: Option<bool>,
 }
 
+#[cfg(test)]
 impl Acceptor for BooleanPrimitive {
     fn accept(&self, visitor: &impl Visitor) -> Result<serde_json::Value, String> {
         visitor.do_for_boolean_primitive()
@@ -4,6 +4,7 @@ use self::all_of_schema::AllOf;
 use self::array_primitive::ArrayPrimitive;
 use self::boolean_primitive::BooleanPrimitive;
 use self::integer_primitive::IntegerPrimitive;
+use self::null_primitive::NullPrimitive;
 use self::object_primitive::ObjectPrimitive;
 use self::one_of_schema::OneOf;
 use self::ref_schema::Reference;
@@ -13,6 +14,7 @@ pub mod all_of_schema;
 pub mod array_primitive;
 pub mod boolean_primitive;
 pub mod integer_primitive;
+pub mod null_primitive;
 pub mod object_primitive;
 pub mod one_of_schema;
 pub mod ref_schema;
@@ -28,9 +30,11 @@ pub struct Common {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub additional_properties: Option<bool>,
     #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub t: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub not: Option<serde_json::Value>,
 }
 
@@ -41,6 +45,7 @@ pub enum Primitive {
     Boolean(BooleanPrimitive),
     Integer(IntegerPrimitive),
     Number(IntegerPrimitive),
+    Null(NullPrimitive),
     Object(ObjectPrimitive),
     String(StringPrimitive),
 }
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use super::Common;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct NullPrimitive {
+    #[serde(flatten)]
+    pub common: Common,
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 use super::{Common, Schema};
+#[cfg(test)]
 use crate::api::json_rpc::spec_reader::data_generator::{Acceptor, Visitor};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -17,6 +18,7 @@ pub struct ObjectPrimitive {
     pub required: Option<Vec<String>>,
 }
 
+#[cfg(test)]
 impl Acceptor for ObjectPrimitive {
     fn accept(&self, visitor: &impl Visitor) -> Result<serde_json::Value, String> {
         visitor.do_for_object_primitive(self)
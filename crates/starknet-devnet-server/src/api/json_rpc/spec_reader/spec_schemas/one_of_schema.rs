@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::{Common, Schema};
+#[cfg(test)]
 use crate::api::json_rpc::spec_reader::data_generator::{Acceptor, Visitor};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -11,6 +12,7 @@ pub struct OneOf {
     pub one_of: Vec<Schema>,
 }
 
+#[cfg(test)]
 impl Acceptor for OneOf {
     fn accept(&self, visitor: &impl Visitor) -> Result<serde_json::Value, String> {
         visitor.do_for_one_of(self)
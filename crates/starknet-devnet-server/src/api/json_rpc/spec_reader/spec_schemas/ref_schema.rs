@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::Common;
+#[cfg(test)]
 use crate::api::json_rpc::spec_reader::data_generator::{Acceptor, Visitor};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -15,6 +16,7 @@ pub struct Reference {
     pub common: Common,
 }
 
+#[cfg(test)]
 impl Acceptor for Reference {
     fn accept(&self, visitor: &impl Visitor) -> Result<serde_json::Value, String> {
         visitor.do_for_ref(self)
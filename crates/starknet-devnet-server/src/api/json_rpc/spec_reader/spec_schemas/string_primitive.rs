@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::Common;
+#[cfg(test)]
 use crate::api::json_rpc::spec_reader::data_generator::{Acceptor, Visitor};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -18,6 +19,7 @@ pub struct StringPrimitive {
     pub pattern: Option<String>,
 }
 
+#[cfg(test)]
 impl Acceptor for StringPrimitive {
     fn accept(&self, visitor: &impl Visitor) -> Result<serde_json::Value, String> {
         visitor.do_for_string_primitive(self)
@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use regex::Regex;
+use serde_json::Value;
+use starknet_types::felt::Felt;
+
+use super::spec_schemas::all_of_schema::AllOf;
+use super::spec_schemas::array_primitive::ArrayPrimitive;
+use super::spec_schemas::integer_primitive::IntegerPrimitive;
+use super::spec_schemas::object_primitive::ObjectPrimitive;
+use super::spec_schemas::one_of_schema::OneOf;
+use super::spec_schemas::ref_schema::Reference;
+use super::spec_schemas::string_primitive::StringPrimitive;
+use super::spec_schemas::{Primitive, Schema};
+use super::Param;
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Checks the params of a call, which can be passed either by name or by position
+pub(super) fn validate_params(
+    expected_params: &[Param],
+    params: &Value,
+    schemas: &HashMap<String, Schema>,
+) -> Result<(), String> {
+    match params {
+        Value::Null => validate_missing_params(expected_params, |_| false),
+        Value::Object(named_params) => {
+            for (name, value) in named_params {
+                let param = expected_params
+                    .iter()
+                    .find(|param| &param.name == name)
+                    .ok_or_else(|| format!("params: unknown param {name}"))?;
+                validate_value(value, &param.schema, schemas, &format!("params.{name}"))?;
+            }
+
+            validate_missing_params(expected_params, |param| named_params.contains_key(&param.name))
+        }
+        Value::Array(positional_params) => {
+            if positional_params.len() > expected_params.len() {
+                return Err(format!(
+                    "params: expected at most {} params, got {}",
+                    expected_params.len(),
+                    positional_params.len()
+                ));
+            }
+
+            for (param, value) in expected_params.iter().zip(positional_params) {
+                validate_value(value, &param.schema, schemas, &format!("params.{}", param.name))?;
+            }
+
+            validate_missing_params(&expected_params[positional_params.len()..], |_| false)
+        }
+        other => Err(format!("params: expected an object or an array, got {other}")),
+    }
+}
+
+fn validate_missing_params(
+    expected_params: &[Param],
+    is_present: impl Fn(&Param) -> bool,
+) -> Result<(), String> {
+    match expected_params.iter().find(|param| param.required && !is_present(param)) {
+        Some(param) => Err(format!("params: missing required param {}", param.name)),
+        None => Ok(()),
+    }
+}
+
+/// Checks the value against the schema. `path` is the location of the value, used in the error
+/// message.
+///
+/// The variants of oneOf are not always mutually exclusive in the Starknet spec, so a value is
+/// accepted if it matches any of them.
+pub(super) fn validate_value(
+    value: &Value,
+    schema: &Schema,
+    schemas: &HashMap<String, Schema>,
+    path: &str,
+) -> Result<(), String> {
+    match schema {
+        Schema::Ref(reference) => validate_ref(value, reference, schemas, path),
+        Schema::OneOf(one_of) => validate_one_of(value, one_of, schemas, path),
+        Schema::AllOf(all_of) => validate_all_of(value, all_of, schemas, path),
+        Schema::Primitive(Primitive::Integer(integer)) => validate_integer(value, integer, path),
+        Schema::Primitive(Primitive::Number(_)) => match value {
+            Value::Number(_) => Ok(()),
+            other => Err(type_mismatch(path, "number", other)),
+        },
+        Schema::Primitive(Primitive::String(string)) => validate_string(value, string, path),
+        Schema::Primitive(Primitive::Boolean(_)) => match value {
+            Value::Bool(_) => Ok(()),
+            other => Err(type_mismatch(path, "boolean", other)),
+        },
+        Schema::Primitive(Primitive::Null(_)) => match value {
+            Value::Null => Ok(()),
+            other => Err(type_mismatch(path, "null", other)),
+        },
+        Schema::Primitive(Primitive::Array(array)) => validate_array(value, array, schemas, path),
+        Schema::Primitive(Primitive::Object(object)) => {
+            validate_object(value, object, schemas, path)
+        }
+    }
+}
+
+fn type_mismatch(path: &str, expected: &str, value: &Value) -> String {
+    format!("{path}: expected {expected}, got {value}")
+}
+
+fn validate_ref(
+    value: &Value,
+    reference: &Reference,
+    schemas: &HashMap<String, Schema>,
+    path: &str,
+) -> Result<(), String> {
+    let schema_name = reference.ref_field.strip_prefix(SCHEMA_REF_PREFIX);
+    let schema = schema_name
+        .and_then(|schema_name| schemas.get(schema_name))
+        .ok_or_else(|| format!("{path}: unresolvable reference {}", reference.ref_field))?;
+
+    validate_value(value, schema, schemas, path).or_else(|err| {
+        if schema_name == Some("FELT") && is_felt_of_63_digits(value) { Ok(()) } else { Err(err) }
+    })
+}
+
+/// The pattern of FELT in the bundled spec admits at most 62 hex digits, although the schema is
+/// described as "represented by at most 63 hex digits", so values of 63 digits are checked to be
+/// below the field prime instead
+fn is_felt_of_63_digits(value: &Value) -> bool {
+    let Value::String(string) = value else { return false };
+
+    string.len() == 65
+        && string.starts_with("0x")
+        && !string.starts_with("0x0")
+        && Felt::from_prefixed_hex_str(string).and_then(|felt| felt.to_field_element()).is_ok()
+}
+
+fn validate_one_of(
+    value: &Value,
+    one_of: &OneOf,
+    schemas: &HashMap<String, Schema>,
+    path: &str,
+) -> Result<(), String> {
+    let mut variant_errors = vec![];
+    for variant in one_of.one_of.iter() {
+        match validate_value(value, variant, schemas, path) {
+            Ok(()) => return Ok(()),
+            Err(err) => variant_errors.push(err),
+        }
+    }
+
+    Err(format!("{path}: no variant matched [{}]", variant_errors.join("; ")))
+}
+
+fn validate_all_of(
+    value: &Value,
+    all_of: &AllOf,
+    schemas: &HashMap<String, Schema>,
+    path: &str,
+) -> Result<(), String> {
+    all_of.all_of.iter().try_for_each(|schema| validate_value(value, schema, schemas, path))
+}
+
+fn validate_integer(value: &Value, integer: &IntegerPrimitive, path: &str) -> Result<(), String> {
+    if !value.is_i64() && !value.is_u64() {
+        return Err(type_mismatch(path, "integer", value));
+    }
+
+    if let Some(minimum) = integer.minimum {
+        // values which do not fit in i64 are above any minimum
+        if value.as_i64().map_or(false, |integer_value| integer_value < i64::from(minimum)) {
+            return Err(format!("{path}: {value} is less than {minimum}"));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_string(value: &Value, string: &StringPrimitive, path: &str) -> Result<(), String> {
+    let value = value.as_str().ok_or_else(|| type_mismatch(path, "string", value))?;
+
+    if let Some(possible_enums) = &string.possible_enums {
+        if !possible_enums.iter().any(|possible_enum| possible_enum == value) {
+            return Err(format!("{path}: {value} is not one of {possible_enums:?}"));
+        }
+    }
+
+    if let Some(pattern) = &string.pattern {
+        if !matches_pattern(pattern, value)? {
+            return Err(format!("{path}: {value} does not match the pattern {pattern}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Compiled patterns are cached, because the same few patterns are matched for every response
+fn matches_pattern(pattern: &str, value: &str) -> Result<bool, String> {
+    static PATTERNS: OnceLock<Mutex<HashMap<String, Regex>>> = OnceLock::new();
+
+    let mut patterns = PATTERNS
+        .get_or_init(Default::default)
+        .lock()
+        .map_err(|_| "The cache of patterns is poisoned".to_string())?;
+
+    if !patterns.contains_key(pattern) {
+        let regex =
+            Regex::new(pattern).map_err(|err| format!("Invalid pattern {pattern}: {err}"))?;
+        patterns.insert(pattern.to_string(), regex);
+    }
+
+    Ok(patterns[pattern].is_match(value))
+}
+
+fn validate_array(
+    value: &Value,
+    array: &ArrayPrimitive,
+    schemas: &HashMap<String, Schema>,
+    path: &str,
+) -> Result<(), String> {
+    let elements = value.as_array().ok_or_else(|| type_mismatch(path, "array", value))?;
+
+    elements.iter().enumerate().try_for_each(|(index, element)| {
+        validate_value(element, &array.items, schemas, &format!("{path}[{index}]"))
+    })
+}
+
+fn validate_object(
+    value: &Value,
+    object: &ObjectPrimitive,
+    schemas: &HashMap<String, Schema>,
+    path: &str,
+) -> Result<(), String> {
+    let fields = value.as_object().ok_or_else(|| type_mismatch(path, "object", value))?;
+
+    for field_name in object.required.iter().flatten() {
+        if !fields.contains_key(field_name) {
+            return Err(format!("{path}: missing required field {field_name}"));
+        }
+    }
+
+    for (field_name, field_value) in fields {
+        match object.properties.get(field_name) {
+            Some(field_schema) => {
+                validate_value(field_value, field_schema, schemas, &format!("{path}.{field_name}"))?
+            }
+            None if object.common.additional_properties == Some(false) => {
+                return Err(format!("{path}: unknown field {field_name}"));
+            }
+            None => (),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{validate_params, validate_value};
+    use crate::api::json_rpc::spec_reader::served_spec;
+
+    fn validate_result(method_name: &str, result: serde_json::Value) -> Result<(), String> {
+        let spec = served_spec();
+        let method = spec.method(method_name).unwrap();
+        validate_value(&result, &method.result.schema, &spec.components.schemas, "result")
+    }
+
+    #[test]
+    fn accepts_params_by_name_and_by_position() {
+        let spec = served_spec();
+        let method = spec.method("starknet_getStorageAt").unwrap();
+        let schemas = &spec.components.schemas;
+
+        let named = json!({ "contract_address": "0x1", "key": "0x02", "block_id": "latest" });
+        validate_params(&method.params, &named, schemas).unwrap();
+
+        let positional = json!(["0x1", "0x02", { "block_number": 1 }]);
+        validate_params(&method.params, &positional, schemas).unwrap();
+    }
+
+    #[test]
+    fn rejects_missing_unknown_and_invalid_params() {
+        let spec = served_spec();
+        let method = spec.method("starknet_getStorageAt").unwrap();
+        let schemas = &spec.components.schemas;
+
+        let err = validate_params(&method.params, &json!({ "contract_address": "0x1" }), schemas)
+            .unwrap_err();
+        assert_eq!(err, "params: missing required param key");
+
+        let params =
+            json!({ "contract_address": "0x1", "key": "0x02", "block_id": "latest", "x": 1 });
+        let err = validate_params(&method.params, &params, schemas).unwrap_err();
+        assert_eq!(err, "params: unknown param x");
+
+        let params = json!({ "contract_address": "1", "key": "0x02", "block_id": "latest" });
+        let err = validate_params(&method.params, &params, schemas).unwrap_err();
+        assert!(err.starts_with("params.contract_address: 1 does not match the pattern"), "{err}");
+    }
+
+    #[test]
+    fn validates_result() {
+        validate_result("starknet_blockNumber", json!(5)).unwrap();
+        validate_result("starknet_chainId", json!("0x534e5f474f45524c49")).unwrap();
+
+        let err = validate_result("starknet_blockNumber", json!("0x5")).unwrap_err();
+        assert_eq!(err, "result: expected integer, got \"0x5\"");
+
+        let err = validate_result("starknet_blockNumber", json!(-1)).unwrap_err();
+        assert_eq!(err, "result: -1 is less than 0");
+    }
+
+    #[test]
+    fn validates_fields_of_objects() {
+        let block_hash = format!("0x{}", "f".repeat(62));
+        validate_result(
+            "starknet_blockHashAndNumber",
+            json!({ "block_hash": block_hash, "block_number": 1 }),
+        )
+        .unwrap();
+
+        let err = validate_result("starknet_blockHashAndNumber", json!({ "block_hash": "0x1" }))
+            .unwrap_err();
+        assert_eq!(err, "result: missing required field block_number");
+
+        let err = validate_result(
+            "starknet_blockHashAndNumber",
+            json!({ "block_hash": "0x01", "block_number": 1 }),
+        )
+        .unwrap_err();
+        assert!(err.starts_with("result.block_hash: 0x01 does not match the pattern"), "{err}");
+    }
+
+    #[test]
+    fn accepts_felts_of_63_digits_below_the_field_prime() {
+        let block_hash = |hash: String| json!({ "block_hash": hash, "block_number": 1 });
+
+        validate_result(
+            "starknet_blockHashAndNumber",
+            block_hash(format!("0x8{}", "0".repeat(62))),
+        )
+        .unwrap();
+
+        for too_big in [format!("0x9{}", "0".repeat(62)), format!("0x1{}", "0".repeat(63))] {
+            let err = validate_result("starknet_blockHashAndNumber", block_hash(too_big.clone()))
+                .unwrap_err();
+            assert!(
+                err.starts_with(&format!(
+                    "result.block_hash: {too_big} does not match the pattern"
+                )),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_any_matching_variant_of_one_of() {
+        let block_id_schema = &served_spec().components.schemas["BLOCK_ID"];
+        let schemas = &served_spec().components.schemas;
+
+        for block_id in
+            [json!("latest"), json!({ "block_number": 1 }), json!({ "block_hash": "0x1" })]
+        {
+            validate_value(&block_id, block_id_schema, schemas, "block_id").unwrap();
+        }
+
+        let err =
+            validate_value(&json!("earliest"), block_id_schema, schemas, "block_id").unwrap_err();
+        assert!(err.starts_with("block_id: no variant matched"), "{err}");
+    }
+}
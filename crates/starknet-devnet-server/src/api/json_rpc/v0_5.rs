@@ -0,0 +1,230 @@
+use serde::Deserialize;
+use starknet_types::rpc::block::BlockId;
+use starknet_types::rpc::block_override::BlockOverride;
+use starknet_types::rpc::state_override::StateOverride;
+use starknet_types::rpc::transactions::{
+    BroadcastedDeclareTransaction, BroadcastedDeployAccountTransaction,
+    BroadcastedInvokeTransaction, BroadcastedTransaction, SimulationFlag,
+};
+use starknet_types::rpc::v0_5;
+
+use super::error::{ApiError, StrictRpcResult};
+use super::models::{
+    BroadcastedDeclareTransactionEnumWrapper, BroadcastedDeclareTransactionInput,
+    BroadcastedDeployAccountTransactionEnumWrapper, BroadcastedDeployAccountTransactionInput,
+    BroadcastedInvokeTransactionEnumWrapper, BroadcastedInvokeTransactionInput,
+    SimulateTransactionsInput,
+};
+use super::{to_rpc_result as serialize_to_rpc_result, StarknetRequest, StarknetResponse};
+use crate::rpc_core::error::RpcError;
+use crate::rpc_core::response::ResponseResult;
+
+/// The params of `starknet_estimateFee`, which has no simulation flags in the 0.5 specification
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EstimateFeeInput {
+    request: Vec<BroadcastedTransaction>,
+    block_id: BlockId,
+    #[serde(default)]
+    simulation_flags: Vec<SimulationFlag>,
+    #[serde(default)]
+    state_override: Option<StateOverride>,
+    #[serde(default)]
+    block_override: Option<BlockOverride>,
+}
+
+impl From<EstimateFeeInput> for super::models::EstimateFeeInput {
+    fn from(input: EstimateFeeInput) -> Self {
+        Self {
+            request: input.request,
+            simulation_flags: input.simulation_flags,
+            block_id: input.block_id,
+            state_override: input.state_override,
+            block_override: input.block_override,
+        }
+    }
+}
+
+/// Deserializes the request like on the latest version, except for the methods whose params
+/// changed between the versions
+pub(crate) fn deserialize_request(
+    mut call: serde_json::Value,
+) -> serde_json::Result<StarknetRequest> {
+    if call["method"] == "starknet_estimateFee" {
+        let input: EstimateFeeInput = serde_json::from_value(call["params"].take())?;
+        return Ok(StarknetRequest::EsimateFee(input.into()));
+    }
+
+    serde_json::from_value(call)
+}
+
+fn is_v3_transaction(transaction: &BroadcastedTransaction) -> bool {
+    matches!(
+        transaction,
+        BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V3(_))
+            | BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V3(_))
+            | BroadcastedTransaction::DeployAccount(BroadcastedDeployAccountTransaction::V3(_))
+    )
+}
+
+/// Rejects requests that can be deserialized, but are not part of the 0.5 specification: version 3
+/// transactions were introduced in 0.6. `rpc.discover` is rejected too, because only the document
+/// of 0.6 is bundled.
+pub(crate) fn validate_request(request: &StarknetRequest) -> Result<(), ApiError> {
+    if matches!(request, StarknetRequest::Discover) {
+        return Err(ApiError::RpcError(RpcError {
+            message: "rpc.discover is only served on JSON-RPC v0.6".into(),
+            ..RpcError::method_not_found()
+        }));
+    }
+
+    let contains_v3_transaction = match request {
+        StarknetRequest::EsimateFee(super::models::EstimateFeeInput {
+            request: transactions,
+            ..
+        })
+        | StarknetRequest::SimulateTransactions(SimulateTransactionsInput {
+            transactions, ..
+        }) => transactions.iter().any(is_v3_transaction),
+        _ => matches!(
+            request,
+            StarknetRequest::AddDeclareTransaction(BroadcastedDeclareTransactionInput {
+                declare_transaction: BroadcastedDeclareTransactionEnumWrapper::Declare(
+                    BroadcastedDeclareTransaction::V3(_)
+                ),
+            }) | StarknetRequest::AddDeployAccountTransaction(
+                BroadcastedDeployAccountTransactionInput {
+                    deploy_account_transaction:
+                        BroadcastedDeployAccountTransactionEnumWrapper::DeployAccount(
+                            BroadcastedDeployAccountTransaction::V3(_)
+                        ),
+                }
+            ) | StarknetRequest::AddInvokeTransaction(BroadcastedInvokeTransactionInput {
+                invoke_transaction: BroadcastedInvokeTransactionEnumWrapper::Invoke(
+                    BroadcastedInvokeTransaction::V3(_)
+                ),
+            })
+        ),
+    };
+
+    if contains_v3_transaction {
+        return Err(ApiError::RpcError(RpcError::invalid_params(
+            "Transactions of version 3 are not supported in JSON-RPC v0.5",
+        )));
+    }
+
+    Ok(())
+}
+
+/// Converts the result to the format of the 0.5 specification. Responses that did not change
+/// between the versions are serialized as they are.
+pub(crate) fn to_rpc_result(result: StrictRpcResult) -> ResponseResult {
+    let response = match result {
+        Ok(response) => response,
+        Err(err) => return err.api_error_to_rpc_error().into(),
+    };
+
+    match response {
+        StarknetResponse::BlockWithTransactionHashes(block)
+        | StarknetResponse::BlockWithFullTransactions(block) => {
+            serialize_to_rpc_result(v0_5::Block::from(&block))
+        }
+        StarknetResponse::PendingBlockWithTransactionHashes(block)
+        | StarknetResponse::PendingBlockWithFullTransactions(block) => {
+            serialize_to_rpc_result(v0_5::PendingBlock::from(&block))
+        }
+        StarknetResponse::TransactionByHash(transaction)
+        | StarknetResponse::TransactionByBlockAndIndex(transaction) => {
+            serialize_to_rpc_result(v0_5::transaction(&transaction))
+        }
+        StarknetResponse::TransactionReceiptByTransactionHash(receipt) => {
+            serialize_to_rpc_result(v0_5::TransactionReceipt::from(receipt.as_ref()))
+        }
+        StarknetResponse::EsimateFee(fee_estimates) => serialize_to_rpc_result(
+            fee_estimates.iter().map(v0_5::FeeEstimate::from).collect::<Vec<_>>(),
+        ),
+        StarknetResponse::EstimateMessageFee(fee_estimate) => {
+            serialize_to_rpc_result(v0_5::FeeEstimate::from(&fee_estimate))
+        }
+        StarknetResponse::SimulateTransactions(simulated_transactions) => serialize_to_rpc_result(
+            simulated_transactions.iter().map(v0_5::SimulatedTransaction::from).collect::<Vec<_>>(),
+        ),
+        StarknetResponse::TraceTransaction(trace) => {
+            serialize_to_rpc_result(v0_5::TransactionTrace::from(&trace))
+        }
+        StarknetResponse::BlockTransactionTraces(traces) => {
+            serialize_to_rpc_result(v0_5::BlockTransactionTraces::from(&traces))
+        }
+        StarknetResponse::TraceCall(invocation) => {
+            serialize_to_rpc_result(v0_5::FunctionInvocation::from(&invocation))
+        }
+        other => serialize_to_rpc_result(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_types::rpc::estimate_message_fee::FeeEstimateWrapper;
+
+    use super::{deserialize_request, to_rpc_result, validate_request};
+    use crate::api::json_rpc::{StarknetRequest, StarknetResponse};
+    use crate::rpc_core::response::ResponseResult;
+
+    #[test]
+    fn fee_estimation_has_no_unit() {
+        let result =
+            Ok(StarknetResponse::EsimateFee(vec![FeeEstimateWrapper::new_in_wei_units(1, 2)]));
+
+        match to_rpc_result(result) {
+            ResponseResult::Success(value) => assert_eq!(
+                value,
+                serde_json::json!([{"gas_consumed": "0x1", "gas_price": "0x2", "overall_fee": "0x2"}])
+            ),
+            ResponseResult::Error(err) => panic!("Unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn fee_estimation_request_without_simulation_flags_is_accepted() {
+        let call = serde_json::json!({
+            "method": "starknet_estimateFee",
+            "params": {"request": [], "block_id": "latest"}
+        });
+
+        match deserialize_request(call.clone()) {
+            Ok(StarknetRequest::EsimateFee(input)) => assert!(input.simulation_flags.is_empty()),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+        // the flags are required in the latest version
+        assert!(serde_json::from_value::<StarknetRequest>(call).is_err());
+    }
+
+    #[test]
+    fn v3_transactions_are_rejected() {
+        let request: StarknetRequest = serde_json::from_value(serde_json::json!({
+            "method": "starknet_addInvokeTransaction",
+            "params": {
+                "invoke_transaction": {
+                    "type": "INVOKE",
+                    "version": "0x3",
+                    "signature": [],
+                    "nonce": "0x0",
+                    "sender_address": "0x1",
+                    "calldata": [],
+                    "resource_bounds": {
+                        "l1_gas": {"max_amount": "0x1", "max_price_per_unit": "0x1"},
+                        "l2_gas": {"max_amount": "0x0", "max_price_per_unit": "0x0"}
+                    },
+                    "tip": "0x0",
+                    "paymaster_data": [],
+                    "account_deployment_data": [],
+                    "nonce_data_availability_mode": "L1",
+                    "fee_data_availability_mode": "L1"
+                }
+            }
+        }))
+        .unwrap();
+
+        assert!(validate_request(&request).is_err());
+    }
+}
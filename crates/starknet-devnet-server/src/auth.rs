@@ -0,0 +1,65 @@
+use axum::http::{header, HeaderMap, Request};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use hyper::Body;
+
+use crate::api::http::error::HttpApiError;
+
+/// Token which clients provide as `Authorization: Bearer <token>` to use the protected endpoints
+#[derive(Clone)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+
+    /// Checks whether the headers contain the bearer token
+    pub fn is_authorized(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map_or(false, |token| constant_time_eq(token.as_bytes(), self.0.as_bytes()))
+    }
+}
+
+/// Compares without returning early, so that the time taken does not reveal the matching prefix
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left.iter().zip(right).fold(0, |difference, (l, r)| difference | (l ^ r)) == 0
+}
+
+/// Middleware that rejects the requests without the API key, if one is set
+pub async fn require_api_key(request: Request<Body>, next: Next<Body>) -> Response {
+    match request.extensions().get::<ApiKey>() {
+        Some(api_key) if !api_key.is_authorized(request.headers()) => {
+            HttpApiError::Unauthorized.into_response()
+        }
+        _ => next.run(request).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{header, HeaderMap, HeaderValue};
+
+    use super::ApiKey;
+
+    fn headers_with_authorization(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn only_the_bearer_token_is_authorized() {
+        let api_key = ApiKey::new("secret");
+
+        assert!(api_key.is_authorized(&headers_with_authorization("Bearer secret")));
+        assert!(!api_key.is_authorized(&HeaderMap::new()));
+        for invalid_value in ["secret", "Bearer secre", "Bearer secrets", "Basic secret"] {
+            assert!(!api_key.is_authorized(&headers_with_authorization(invalid_value)));
+        }
+    }
+}
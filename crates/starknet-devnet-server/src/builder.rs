@@ -1,23 +1,80 @@
 use std::convert::Infallible;
+use std::future::Future;
+use std::io;
 use std::net::SocketAddr;
 use std::time::Duration;
 
+use axum::middleware::from_fn;
 use axum::response::Response;
 use axum::routing::{post, IntoMakeService};
 use axum::{Extension, Router};
-use hyper::server::conn::AddrIncoming;
+use hyper::server::accept;
 use hyper::{header, Method, Request, Server};
 use starknet_core::starknet::starknet_config::StarknetConfig;
-use tower::Service;
+use tower::{Layer, Service};
 use tower_http::cors::CorsLayer;
 use tower_http::timeout::TimeoutLayer;
 use tower_http::trace::TraceLayer;
 
+use crate::auth::{self, ApiKey};
+use crate::listener::{self, Incoming, ListenAddress};
+use crate::recorder::{self, Recorder};
 use crate::rpc_handler::{self, RpcHandler};
 use crate::ServerConfig;
 
-/// Helper type for naming the [`Server`]
-pub type StarknetDevnetServer = Server<AddrIncoming, IntoMakeService<Router>>;
+/// The configured routes, bound to the address at which they are served. Serving starts with
+/// [`StarknetDevnetServer::serve`].
+pub struct StarknetDevnetServer {
+    incoming: Incoming,
+    local_address: ListenAddress,
+    service: IntoMakeService<Router>,
+}
+
+impl StarknetDevnetServer {
+    /// The address the server is bound to, with the actual port if it was chosen by the OS
+    pub fn local_addr(&self) -> &ListenAddress {
+        &self.local_address
+    }
+
+    pub async fn serve(self) -> hyper::Result<()> {
+        self.serve_with_graceful_shutdown(futures::future::pending()).await
+    }
+
+    /// Serves until `signal` completes, then waits for the pending requests to be answered
+    pub async fn serve_with_graceful_shutdown(
+        self,
+        signal: impl Future<Output = ()>,
+    ) -> hyper::Result<()> {
+        let Self { incoming, local_address, service } = self;
+        match incoming {
+            Incoming::Tcp(incoming) => {
+                Server::builder(incoming).serve(service).with_graceful_shutdown(signal).await
+            }
+            Incoming::Tls(mut connections) => {
+                let incoming = accept::poll_fn(move |cx| {
+                    connections.poll_recv(cx).map(|connection| connection.map(Ok::<_, io::Error>))
+                });
+                Server::builder(incoming).serve(service).with_graceful_shutdown(signal).await
+            }
+            #[cfg(unix)]
+            Incoming::Unix(unix_listener) => {
+                let incoming = accept::poll_fn(move |cx| {
+                    unix_listener
+                        .poll_accept(cx)
+                        .map(|accepted| Some(accepted.map(|(stream, _)| stream)))
+                });
+                let result =
+                    Server::builder(incoming).serve(service).with_graceful_shutdown(signal).await;
+
+                // the socket file is not removed when the listener is dropped
+                if let ListenAddress::Unix(path) = local_address {
+                    let _ = std::fs::remove_file(path);
+                }
+                result
+            }
+        }
+    }
+}
 
 /// Helper for constructing a [`Server`].
 /// [`Builder`] is a convenience wrapper around [`Router`] with added support for JSON-RPC and HTTP
@@ -33,6 +90,8 @@ pub struct Builder<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send +
     json_rpc_handler: TJsonRpcHandler,
     http_api_handler: THttpApiHandler,
     config: Option<ServerConfig>,
+    recorder: Option<Recorder>,
+    api_key: Option<ApiKey>,
 }
 
 impl<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send + Sync + 'static>
@@ -49,10 +108,13 @@ impl<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send + Sync + 'static
             json_rpc_handler,
             http_api_handler,
             config: None,
+            recorder: None,
+            api_key: None,
         }
     }
 
-    /// Adds an HTTP endpoint to a specific route
+    /// Adds an HTTP endpoint to a specific route. Its requests are recorded, if a [`Recorder`] is
+    /// set.
     pub fn http_api_route<THttpMethodService>(
         self,
         path: &str,
@@ -65,9 +127,28 @@ impl<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send + Sync + 'static
             + 'static,
         THttpMethodService::Future: Send + 'static,
     {
+        let http_service = from_fn(recorder::record_http_exchange).layer(http_service);
         Self { routes: self.routes.route(path, http_service), ..self }
     }
 
+    /// Adds an HTTP endpoint that requires the [`ApiKey`], if one is set, to a specific route.
+    /// Its requests are recorded, if a [`Recorder`] is set, including the rejected ones.
+    pub fn protected_http_api_route<THttpMethodService>(
+        self,
+        path: &str,
+        http_service: THttpMethodService,
+    ) -> Self
+    where
+        THttpMethodService: Service<Request<hyper::Body>, Response = Response, Error = Infallible>
+            + Clone
+            + Send
+            + 'static,
+        THttpMethodService::Future: Send + 'static,
+    {
+        let http_service = from_fn(auth::require_api_key).layer(http_service);
+        self.http_api_route(path, http_service)
+    }
+
     /// Adds the object that will be available on every HTTP request
     pub fn set_http_api_handler(self, handler: THttpApiHandler) -> Self {
         Self { http_api_handler: handler, ..self }
@@ -82,6 +163,29 @@ impl<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send + Sync + 'static
         }
     }
 
+    /// Sets the path to a JSON-RPC endpoint that is served by its own handler instead of the one
+    /// shared by the routes added with [`Builder::json_rpc_route`]
+    pub fn json_rpc_route_with_handler(self, path: &str, handler: TJsonRpcHandler) -> Self {
+        Self {
+            routes: self.routes.route(
+                path,
+                post(rpc_handler::handle::<TJsonRpcHandler>).layer(Extension(handler)),
+            ),
+            ..self
+        }
+    }
+
+    /// Sets the [`Recorder`] of the requests and responses of all routes
+    pub fn set_recorder(self, recorder: Recorder) -> Self {
+        Self { recorder: Some(recorder), ..self }
+    }
+
+    /// Sets the [`ApiKey`] required by the protected HTTP routes and the JSON-RPC methods
+    /// for which [`RpcHandler::requires_api_key`] holds
+    pub fn set_api_key(self, api_key: ApiKey) -> Self {
+        Self { api_key: Some(api_key), ..self }
+    }
+
     /// Sets additional configuration for the [`StarknetDevnetServer`]
     pub fn set_config(self, config: ServerConfig) -> Self {
         Self { config: Some(config), ..self }
@@ -91,26 +195,57 @@ impl<TJsonRpcHandler: RpcHandler, THttpApiHandler: Clone + Send + Sync + 'static
     /// [`ServerConfig`] and all handlers that have Some value. If TJsonRpcHandler and/or
     /// THttpApiHandler are set each methods that serves the route will be able to use it.
     /// https://docs.rs/axum/latest/axum/#using-request-extensions
-    pub fn build(self, starknet_config: &StarknetConfig) -> StarknetDevnetServer {
+    ///
+    /// Binds to the Unix socket or to the address with TLS, if configured in [`ServerConfig`],
+    /// otherwise to the plain address. Must be called within a tokio runtime.
+    pub fn build(self, starknet_config: &StarknetConfig) -> io::Result<StarknetDevnetServer> {
+        let (incoming, local_address) = match &self.config {
+            #[cfg(unix)]
+            Some(ServerConfig { unix_socket_path: Some(path), .. }) => listener::bind_unix(path)?,
+            #[cfg(not(unix))]
+            Some(ServerConfig { unix_socket_path: Some(_), .. }) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "Unix sockets are not supported on this platform",
+                ));
+            }
+            Some(ServerConfig {
+                tls_cert_path: Some(cert_path),
+                tls_key_path: Some(key_path),
+                ..
+            }) => listener::bind_tls(self.address, cert_path, key_path)?,
+            _ => listener::bind_tcp(self.address)?,
+        };
+
         let mut svc = self.routes;
 
         svc = svc
             .layer(Extension(self.json_rpc_handler))
-            .layer(Extension(self.http_api_handler))
+            .layer(Extension(self.http_api_handler));
+
+        if let Some(recorder) = self.recorder {
+            svc = svc.layer(Extension(recorder));
+        }
+
+        if let Some(api_key) = self.api_key {
+            svc = svc.layer(Extension(api_key));
+        }
+
+        svc = svc
             .layer(TraceLayer::new_for_http())
             .layer(TimeoutLayer::new(Duration::from_secs(starknet_config.timeout.into())));
 
-        if let Some(ServerConfig { allow_origin }) = self.config {
+        if let Some(ServerConfig { allow_origin, .. }) = self.config {
             svc = svc.layer(
                 // see https://docs.rs/tower-http/latest/tower_http/cors/index.html
                 // for more details
                 CorsLayer::new()
                     .allow_origin(allow_origin.0)
-                    .allow_headers(vec![header::CONTENT_TYPE])
+                    .allow_headers(vec![header::CONTENT_TYPE, header::AUTHORIZATION])
                     .allow_methods(vec![Method::GET, Method::POST]),
             )
         }
 
-        Server::bind(&self.address).serve(svc.into_make_service())
+        Ok(StarknetDevnetServer { incoming, local_address, service: svc.into_make_service() })
     }
 }
@@ -8,11 +8,46 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 pub struct ServerConfig {
     /// The cors `allow_origin` header
     pub allow_origin: HeaderValueWrapper,
+    /// Check JSON-RPC requests and responses against the spec
+    #[serde(default)]
+    pub validate_rpc_schema: bool,
+    /// The file to which the requests and responses are recorded
+    #[serde(default)]
+    pub record_path: Option<String>,
+    /// The PEM certificate chain to serve HTTPS with, together with `tls_key_path`
+    #[serde(default)]
+    pub tls_cert_path: Option<String>,
+    /// The PEM private key of the certificate
+    #[serde(default)]
+    pub tls_key_path: Option<String>,
+    /// The Unix socket to listen at instead of the TCP address
+    #[serde(default)]
+    pub unix_socket_path: Option<String>,
+    /// The bearer token required by the devnet control endpoints. Not serialized, so that it is
+    /// not revealed.
+    #[serde(default, skip_serializing)]
+    pub api_key: Option<String>,
+    /// Require the API key also for adding transactions
+    #[serde(default)]
+    pub require_api_key_for_writes: bool,
+    /// Do not serve the devnet control endpoints
+    #[serde(default)]
+    pub disable_devnet_api: bool,
 }
 
 impl Default for ServerConfig {
     fn default() -> Self {
-        Self { allow_origin: "*".parse::<HeaderValue>().unwrap().into() }
+        Self {
+            allow_origin: "*".parse::<HeaderValue>().unwrap().into(),
+            validate_rpc_schema: false,
+            record_path: None,
+            tls_cert_path: None,
+            tls_key_path: None,
+            unix_socket_path: None,
+            api_key: None,
+            require_api_key_for_writes: false,
+            disable_devnet_api: false,
+        }
     }
 }
 
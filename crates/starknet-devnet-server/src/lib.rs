@@ -1,6 +1,10 @@
 pub mod api;
+pub mod auth;
 pub mod builder;
 mod config;
+pub mod listener;
+mod metrics;
+pub mod recorder;
 pub mod rpc_core;
 /// handlers for axum server
 pub mod rpc_handler;
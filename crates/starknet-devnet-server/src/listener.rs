@@ -0,0 +1,142 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use hyper::server::conn::AddrIncoming;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::rustls::{Certificate, PrivateKey, ServerConfig as TlsConfig};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tracing::{debug, warn};
+
+/// Number of connections which completed the TLS handshake, but were not yet taken by the server
+const TLS_CONNECTIONS_BUFFER: usize = 64;
+
+/// Where the server accepts connections: a TCP address, a TCP address with TLS or a Unix socket
+#[derive(Debug, Clone)]
+pub enum ListenAddress {
+    Tcp(SocketAddr),
+    Tls(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl fmt::Display for ListenAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListenAddress::Tcp(address) => write!(f, "{address}"),
+            ListenAddress::Tls(address) => write!(f, "{address} (TLS)"),
+            #[cfg(unix)]
+            ListenAddress::Unix(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Bound listener, from which the server takes the incoming connections
+pub(crate) enum Incoming {
+    Tcp(AddrIncoming),
+    /// Connections which completed the TLS handshake
+    Tls(mpsc::Receiver<TlsStream<TcpStream>>),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener),
+}
+
+pub(crate) fn bind_tcp(address: SocketAddr) -> io::Result<(Incoming, ListenAddress)> {
+    let incoming = AddrIncoming::bind(&address)
+        .map_err(|err| io::Error::new(io::ErrorKind::AddrNotAvailable, err))?;
+    let address = incoming.local_addr();
+
+    Ok((Incoming::Tcp(incoming), ListenAddress::Tcp(address)))
+}
+
+/// Binds to `address` and performs the TLS handshake of every accepted connection in a separate
+/// task, so that a slow client does not block the others. Must be called within a tokio runtime.
+pub(crate) fn bind_tls(
+    address: SocketAddr,
+    cert_path: &str,
+    key_path: &str,
+) -> io::Result<(Incoming, ListenAddress)> {
+    let acceptor = TlsAcceptor::from(Arc::new(load_tls_config(cert_path, key_path)?));
+
+    let std_listener = std::net::TcpListener::bind(address)?;
+    std_listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(std_listener)?;
+    let address = listener.local_addr()?;
+
+    let (sender, receiver) = mpsc::channel(TLS_CONNECTIONS_BUFFER);
+    tokio::spawn(async move {
+        loop {
+            let (stream, peer) = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(err) => {
+                        warn!(target: "server", ?err, "failed to accept connection");
+                        continue;
+                    }
+                },
+                // the server stopped taking connections
+                _ = sender.closed() => break,
+            };
+
+            let acceptor = acceptor.clone();
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                match acceptor.accept(stream).await {
+                    Ok(tls_stream) => {
+                        let _ = sender.send(tls_stream).await;
+                    }
+                    Err(err) => debug!(target: "server", ?peer, ?err, "TLS handshake failed"),
+                }
+            });
+        }
+    });
+
+    Ok((Incoming::Tls(receiver), ListenAddress::Tls(address)))
+}
+
+/// Binds a Unix socket at `path`. Fails if the file already exists. Must be called within a tokio
+/// runtime.
+#[cfg(unix)]
+pub(crate) fn bind_unix(path: &str) -> io::Result<(Incoming, ListenAddress)> {
+    let listener = tokio::net::UnixListener::bind(path)?;
+    Ok((Incoming::Unix(listener), ListenAddress::Unix(PathBuf::from(path))))
+}
+
+fn load_tls_config(cert_path: &str, key_path: &str) -> io::Result<TlsConfig> {
+    let invalid_data = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+    let open = |path: &str| {
+        File::open(path)
+            .map(BufReader::new)
+            .map_err(|err| io::Error::new(err.kind(), format!("Could not open {path}: {err}")))
+    };
+
+    let certs: Vec<Certificate> =
+        rustls_pemfile::certs(&mut open(cert_path)?)?.into_iter().map(Certificate).collect();
+    if certs.is_empty() {
+        return Err(invalid_data(format!("No certificate found in {cert_path}")));
+    }
+
+    let key = rustls_pemfile::read_all(&mut open(key_path)?)?
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| invalid_data(format!("No private key found in {key_path}")))?;
+
+    let mut config = TlsConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|err| invalid_data(format!("Invalid certificate or key: {err}")))?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    Ok(config)
+}
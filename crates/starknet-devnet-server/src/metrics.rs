@@ -0,0 +1,48 @@
+use std::sync::OnceLock;
+
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, Encoder, HistogramVec, IntCounterVec,
+    TextEncoder,
+};
+
+/// Metrics of the JSON-RPC calls, registered in the default prometheus registry
+pub(crate) struct RpcMetrics {
+    /// Handled calls, labeled by method and outcome: success or error
+    pub(crate) requests: IntCounterVec,
+    pub(crate) request_duration: HistogramVec,
+}
+
+impl RpcMetrics {
+    fn register() -> prometheus::Result<Self> {
+        Ok(Self {
+            requests: register_int_counter_vec!(
+                "devnet_rpc_requests_total",
+                "Number of handled JSON-RPC calls by method and outcome",
+                &["method", "outcome"]
+            )?,
+            request_duration: register_histogram_vec!(
+                "devnet_rpc_request_duration_seconds",
+                "Time spent handling JSON-RPC calls by method",
+                &["method"]
+            )?,
+        })
+    }
+}
+
+pub(crate) fn rpc_metrics() -> &'static RpcMetrics {
+    static METRICS: OnceLock<RpcMetrics> = OnceLock::new();
+    METRICS
+        .get_or_init(|| RpcMetrics::register().expect("RPC metrics should be registered only once"))
+}
+
+/// Encodes all metrics of the default registry in the Prometheus text format
+pub(crate) fn encode_metrics() -> prometheus::Result<String> {
+    // make sure the metrics are registered even if nothing was observed yet
+    rpc_metrics();
+    starknet_core::metrics::metrics();
+
+    let mut buffer = vec![];
+    TextEncoder::new().encode(&prometheus::gather(), &mut buffer)?;
+
+    String::from_utf8(buffer).map_err(|err| prometheus::Error::Msg(err.to_string()))
+}
@@ -0,0 +1,120 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::body::{boxed, Full};
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+/// A request received by the server and the response it was answered with. Bodies which are not
+/// JSON are stored as strings, missing bodies as null.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    /// Milliseconds since the Unix epoch, at the time the response was sent
+    pub timestamp: u64,
+    /// The HTTP method of the request
+    pub method: String,
+    /// The path of the request, including the query
+    pub path: String,
+    pub request: serde_json::Value,
+    pub status: u16,
+    pub response: serde_json::Value,
+}
+
+impl RecordedExchange {
+    pub fn new(
+        method: &str,
+        path: &str,
+        request: serde_json::Value,
+        status: u16,
+        response: serde_json::Value,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+
+        Self {
+            timestamp,
+            method: method.to_string(),
+            path: path.to_string(),
+            request,
+            status,
+            response,
+        }
+    }
+}
+
+/// Appends every exchange as a line of JSON to a file. The file is written to immediately, so that
+/// the recording is complete even if devnet does not exit gracefully.
+#[derive(Clone)]
+pub struct Recorder {
+    file: Arc<Mutex<File>>,
+}
+
+impl Recorder {
+    pub fn new(path: &str) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+
+        Ok(Self { file: Arc::new(Mutex::new(file)) })
+    }
+
+    pub fn record(&self, exchange: &RecordedExchange) {
+        let result =
+            serde_json::to_string(exchange).map_err(std::io::Error::from).and_then(|line| {
+                let mut file = self.file.lock().map_err(|_| {
+                    std::io::Error::new(std::io::ErrorKind::Other, "Recording file is poisoned")
+                })?;
+                writeln!(file, "{line}")
+            });
+
+        if let Err(err) = result {
+            error!(target: "recorder", ?err, "failed to record exchange");
+        }
+    }
+}
+
+/// Parses the body as JSON, falls back to a string if it is not JSON
+pub fn body_to_value(body: &[u8]) -> serde_json::Value {
+    if body.is_empty() {
+        return serde_json::Value::Null;
+    }
+
+    serde_json::from_slice(body)
+        .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(body).into_owned()))
+}
+
+/// Middleware that records the requests of the HTTP API, if recording is enabled
+pub async fn record_http_exchange(request: Request<Body>, next: Next<Body>) -> Response {
+    let Some(recorder) = request.extensions().get::<Recorder>().cloned() else {
+        return next.run(request).await;
+    };
+
+    let method = request.method().to_string();
+    let path = request.uri().path_and_query().map(ToString::to_string).unwrap_or_default();
+
+    let (parts, body) = request.into_parts();
+    let request_body = hyper::body::to_bytes(body).await.unwrap_or_default();
+    let request = Request::from_parts(parts, Body::from(request_body.clone()));
+
+    let response = next.run(request).await;
+    let status = response.status().as_u16();
+
+    let (parts, body) = response.into_parts();
+    let response_body = hyper::body::to_bytes(body).await.unwrap_or_default();
+
+    recorder.record(&RecordedExchange::new(
+        &method,
+        &path,
+        body_to_value(&request_body),
+        status,
+        body_to_value(&response_body),
+    ));
+
+    Response::from_parts(parts, boxed(Full::from(response_body)))
+}
@@ -57,6 +57,11 @@ impl RpcError {
         RpcError { code: ErrorCode::InternalError, message: message.into().into(), data: None }
     }
 
+    /// Creates a new `Unauthorized`
+    pub const fn unauthorized() -> Self {
+        Self::new(ErrorCode::Unauthorized)
+    }
+
     /// Creates a new rpc error for when a transaction was rejected
     pub fn transaction_rejected<M>(message: M) -> Self
     where
@@ -94,6 +99,8 @@ pub enum ErrorCode {
     TransactionRejected,
     /// Custom geth error code, <https://github.com/vapory-legacy/wiki/blob/master/JSON-RPC-Error-Codes-Improvement-Proposal.md>
     ExecutionError,
+    /// The method requires the API key, which was missing or invalid
+    Unauthorized,
     /// Used for server specific errors.
     ServerError(i64),
 }
@@ -109,6 +116,7 @@ impl ErrorCode {
             ErrorCode::InternalError => -32603,
             ErrorCode::TransactionRejected => -32003,
             ErrorCode::ExecutionError => 3,
+            ErrorCode::Unauthorized => -32001,
             ErrorCode::ServerError(c) => c,
         }
     }
@@ -124,6 +132,7 @@ impl ErrorCode {
             ErrorCode::TransactionRejected => "Transaction rejected",
             ErrorCode::ServerError(_) => "Server error",
             ErrorCode::ExecutionError => "Execution error",
+            ErrorCode::Unauthorized => "Missing or invalid API key",
         }
     }
 }
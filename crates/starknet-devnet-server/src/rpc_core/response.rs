@@ -29,6 +29,10 @@ impl RpcResponse {
     pub fn invalid_request(id: Id) -> Self {
         Self::new(id, RpcError::invalid_request())
     }
+
+    pub fn result(&self) -> &ResponseResult {
+        &self.result
+    }
 }
 
 /// Represents the result of a call either success or error
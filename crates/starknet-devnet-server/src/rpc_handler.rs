@@ -1,13 +1,18 @@
 use std::fmt::{self};
+use std::time::Instant;
 
 use axum::extract::rejection::JsonRejection;
-use axum::extract::Extension;
+use axum::extract::{Extension, OriginalUri};
+use axum::http::{HeaderMap, StatusCode};
 use axum::Json;
 use futures::{future, FutureExt};
 use serde::de::DeserializeOwned;
 use tracing::{error, trace, warn};
 
-use crate::rpc_core::error::RpcError;
+use crate::auth::ApiKey;
+use crate::metrics::rpc_metrics;
+use crate::recorder::{RecordedExchange, Recorder};
+use crate::rpc_core::error::{ErrorCode, RpcError};
 use crate::rpc_core::request::{Request, RpcCall, RpcMethodCall};
 use crate::rpc_core::response::{Response, ResponseResult, RpcResponse};
 
@@ -20,11 +25,33 @@ pub trait RpcHandler: Clone + Send + Sync + 'static {
     /// Invoked when the request was received
     async fn on_request(&self, request: Self::Request) -> ResponseResult;
 
+    /// Whether calling `method` requires the API key, if one is set
+    fn requires_api_key(&self, _method: &str) -> bool {
+        false
+    }
+
+    /// Invoked for every incoming `RpcMethodCall` before it is deserialized into the `Request`
+    /// type. Returning an error rejects the call.
+    fn validate_call(&self, _method: &str, _params: &serde_json::Value) -> Result<(), RpcError> {
+        Ok(())
+    }
+
+    /// Deserializes a `{ "method" : "<name>", "params": "<params>" }` message into the `Request`
+    /// type
+    fn deserialize_request(&self, call: serde_json::Value) -> serde_json::Result<Self::Request> {
+        serde_json::from_value(call)
+    }
+
+    /// Invoked with the result of every `RpcMethodCall`, before it is sent back
+    fn validate_result(&self, _method: &str, result: ResponseResult) -> ResponseResult {
+        result
+    }
+
     /// Invoked for every incoming `RpcMethodCall`
     ///
     /// This will attempt to deserialize a `{ "method" : "<name>", "params": "<params>" }` message
-    /// into the `Request` type of this handler. If a `Request` instance was deserialized
-    /// successfully, [`Self::on_request`] will be invoked.
+    /// into the `Request` type of this handler with [`Self::deserialize_request`]. If a `Request`
+    /// instance was deserialized successfully, [`Self::on_request`] will be invoked.
     ///
     /// **Note**: override this function if the expected `Request` deviates from `{ "method" :
     /// "<name>", "params": "<params>" }`
@@ -33,15 +60,19 @@ pub trait RpcHandler: Clone + Send + Sync + 'static {
         let RpcMethodCall { method, params, id, .. } = call;
 
         let params: serde_json::Value = params.into();
+        if let Err(err) = self.validate_call(&method, &params) {
+            return RpcResponse::new(id, err);
+        }
+
         let call = serde_json::json!({
             "method": &method,
             "params": params
         });
 
-        match serde_json::from_value::<Self::Request>(call) {
+        match self.deserialize_request(call) {
             Ok(req) => {
                 let result = self.on_request(req).await;
-                RpcResponse::new(id, result)
+                RpcResponse::new(id, self.validate_result(&method, result))
             }
             Err(err) => {
                 let err = err.to_string();
@@ -64,12 +95,34 @@ pub trait RpcHandler: Clone + Send + Sync + 'static {
 pub async fn handle<THandler: RpcHandler>(
     request: Result<Json<Request>, JsonRejection>,
     Extension(handler): Extension<THandler>,
+    OriginalUri(uri): OriginalUri,
+    recorder: Option<Extension<Recorder>>,
+    api_key: Option<Extension<ApiKey>>,
+    headers: HeaderMap,
 ) -> Json<Response> {
     match request {
-        Ok(req) => handle_request(req.0, handler)
-            .await
-            .unwrap_or_else(|| Response::error(RpcError::invalid_request()))
-            .into(),
+        Ok(req) => {
+            let recorded_request = recorder.as_ref().map(|_| serde_json::to_value(&req.0));
+            let authorized =
+                api_key.map_or(true, |Extension(api_key)| api_key.is_authorized(&headers));
+            let response = handle_request(req.0, handler, authorized)
+                .await
+                .unwrap_or_else(|| Response::error(RpcError::invalid_request()));
+
+            if let (Some(Extension(recorder)), Some(Ok(recorded_request))) =
+                (recorder, recorded_request)
+            {
+                recorder.record(&RecordedExchange::new(
+                    "POST",
+                    uri.path(),
+                    recorded_request,
+                    StatusCode::OK.as_u16(),
+                    serde_json::to_value(&response).unwrap_or_default(),
+                ));
+            }
+
+            response.into()
+        }
         Err(err) => {
             warn!(target: "rpc", ?err, "invalid request");
             Response::error(RpcError::invalid_request()).into()
@@ -80,10 +133,12 @@ pub async fn handle<THandler: RpcHandler>(
 /// Handle the JSON-RPC [Request]
 ///
 /// This will try to deserialize the payload into the request type of the handler and if successful
-/// invoke the handler.
+/// invoke the handler. Unless `authorized`, the calls of methods which require the API key are
+/// rejected.
 pub async fn handle_request<THandler: RpcHandler>(
     req: Request,
     handler: THandler,
+    authorized: bool,
 ) -> Option<Response> {
     /// processes batch calls
     fn responses_as_batch(outs: Vec<Option<RpcResponse>>) -> Option<Response> {
@@ -92,11 +147,13 @@ pub async fn handle_request<THandler: RpcHandler>(
     }
 
     match req {
-        Request::Single(call) => handle_call(call, handler).await.map(Response::Single),
+        Request::Single(call) => handle_call(call, handler, authorized).await.map(Response::Single),
         Request::Batch(calls) => {
-            future::join_all(calls.into_iter().map(move |call| handle_call(call, handler.clone())))
-                .map(responses_as_batch)
-                .await
+            future::join_all(
+                calls.into_iter().map(move |call| handle_call(call, handler.clone(), authorized)),
+            )
+            .map(responses_as_batch)
+            .await
         }
     }
 }
@@ -105,11 +162,22 @@ pub async fn handle_request<THandler: RpcHandler>(
 async fn handle_call<THandler: RpcHandler>(
     call: RpcCall,
     handler: THandler,
+    authorized: bool,
 ) -> Option<RpcResponse> {
     match call {
         RpcCall::MethodCall(call) => {
             trace!(target: "rpc", id = ?call.id , method = ?call.method,  "handling call");
-            Some(handler.on_call(call).await)
+            let method = call.method.clone();
+            let start = Instant::now();
+            let response = if authorized || !handler.requires_api_key(&method) {
+                handler.on_call(call).await
+            } else {
+                warn!(target: "rpc", method, "missing or invalid API key");
+                RpcResponse::new(call.id, RpcError::unauthorized())
+            };
+            observe_call(&method, &response, start);
+
+            Some(response)
         }
         RpcCall::Notification(notification) => {
             trace!(target: "rpc", method = ?notification.method, "received rpc notification");
@@ -121,3 +189,17 @@ async fn handle_call<THandler: RpcHandler>(
         }
     }
 }
+
+/// Updates the metrics of JSON-RPC calls with a handled call
+fn observe_call(method: &str, response: &RpcResponse, start: Instant) {
+    let (method, outcome) = match response.result() {
+        ResponseResult::Success(_) => (method, "success"),
+        // unknown methods are not labeled by name, so that they can not flood the metrics
+        ResponseResult::Error(err) if err.code == ErrorCode::MethodNotFound => ("unknown", "error"),
+        ResponseResult::Error(_) => (method, "error"),
+    };
+
+    let metrics = rpc_metrics();
+    metrics.requests.with_label_values(&[method, outcome]).inc();
+    metrics.request_duration.with_label_values(&[method]).observe(start.elapsed().as_secs_f64());
+}
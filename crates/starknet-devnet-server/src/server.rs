@@ -4,47 +4,93 @@ use axum::routing::{get, post};
 use starknet_core::starknet::starknet_config::StarknetConfig;
 
 use crate::api::http::{endpoints as http, HttpApiHandler};
-use crate::api::json_rpc::JsonRpcHandler;
+use crate::api::json_rpc::{JsonRpcHandler, RpcSpecVersion};
 use crate::api::Api;
+use crate::auth::ApiKey;
 use crate::builder::StarknetDevnetServer;
+use crate::recorder::Recorder;
 use crate::ServerConfig;
 
-/// Configures an [axum::Server] that handles related JSON-RPC calls and WEB API calls via HTTP
+/// Configures an [axum::Server] that handles related JSON-RPC calls and WEB API calls via HTTP.
+/// Fails if the file to record to can not be created or if binding fails.
 pub fn serve_http_api_json_rpc(
     addr: SocketAddr,
     config: ServerConfig,
     api: Api,
     starknet_config: &StarknetConfig,
-) -> StarknetDevnetServer {
-    let http = HttpApiHandler { api: api.clone() };
-    let json_rpc = JsonRpcHandler { api };
-
-    crate::builder::Builder::<JsonRpcHandler, HttpApiHandler>::new(addr, json_rpc, http)
-        .set_config(config)
-        .json_rpc_route("/")
-        .json_rpc_route("/rpc")
+) -> std::io::Result<StarknetDevnetServer> {
+    let recorder = config.record_path.as_deref().map(Recorder::new).transpose()?;
+    let http = HttpApiHandler { api: api.clone(), server_config: config.clone() };
+    let json_rpc = JsonRpcHandler {
+        api,
+        spec_version: RpcSpecVersion::default(),
+        validate_rpc_schema: config.validate_rpc_schema,
+        require_api_key_for_writes: config.require_api_key_for_writes,
+        disable_devnet_api: config.disable_devnet_api,
+    };
+    let api_key = config.api_key.clone().map(ApiKey::new);
+    let disable_devnet_api = config.disable_devnet_api;
+
+    let mut builder = crate::builder::Builder::<JsonRpcHandler, HttpApiHandler>::new(
+        addr,
+        json_rpc.clone(),
+        http,
+    )
+    .set_config(config)
+    .json_rpc_route("/")
+    .json_rpc_route("/rpc");
+
+    if let Some(recorder) = recorder {
+        builder = builder.set_recorder(recorder);
+    }
+
+    if let Some(api_key) = api_key {
+        builder = builder.set_api_key(api_key);
+    }
+
+    for spec_version in RpcSpecVersion::ALL {
+        builder = builder.json_rpc_route_with_handler(
+            spec_version.route(),
+            JsonRpcHandler { spec_version, ..json_rpc.clone() },
+        );
+    }
+
+    builder = builder
         .http_api_route("/is_alive", get(http::is_alive))
-        .http_api_route("/dump", post(http::dump_load::dump))
-        .http_api_route("/load", post(http::dump_load::load))
-        .http_api_route("/postman/load_l1_messaging_contract", post(http::postman::postman_load))
-        .http_api_route("/postman/flush", post(http::postman::postman_flush))
-        .http_api_route(
-            "/postman/send_message_to_l2",
-            post(http::postman::postman_send_message_to_l2),
-        )
-        .http_api_route(
-            "/postman/consume_message_from_l2",
-            post(http::postman::postman_consume_message_from_l2),
-        )
-        .http_api_route("/create_block", post(http::blocks::create_block))
-        .http_api_route("/abort_blocks", post(http::blocks::abort_blocks))
-        .http_api_route("/restart", post(http::restart))
-        .http_api_route("/set_time", post(http::time::set_time))
-        .http_api_route("/increase_time", post(http::time::increase_time))
         .http_api_route("/predeployed_accounts", get(http::accounts::get_predeployed_accounts))
         .http_api_route("/account_balance", get(http::accounts::get_account_balance))
         .http_api_route("/fee_token", get(http::mint_token::get_fee_token))
-        .http_api_route("/mint", post(http::mint_token::mint))
         .http_api_route("/fork_status", get(http::get_fork_status))
-        .build(starknet_config)
+        .http_api_route("/config", get(http::get_config))
+        .http_api_route("/metrics", get(http::metrics::get_metrics));
+
+    // the routes which control the state of devnet
+    if !disable_devnet_api {
+        builder = builder
+            .protected_http_api_route("/dump", post(http::dump_load::dump))
+            .protected_http_api_route("/load", post(http::dump_load::load))
+            .protected_http_api_route(
+                "/postman/load_l1_messaging_contract",
+                post(http::postman::postman_load),
+            )
+            .protected_http_api_route("/postman/flush", post(http::postman::postman_flush))
+            .protected_http_api_route(
+                "/postman/send_message_to_l2",
+                post(http::postman::postman_send_message_to_l2),
+            )
+            .protected_http_api_route(
+                "/postman/consume_message_from_l2",
+                post(http::postman::postman_consume_message_from_l2),
+            )
+            .protected_http_api_route("/create_block", post(http::blocks::create_block))
+            .protected_http_api_route("/abort_blocks", post(http::blocks::abort_blocks))
+            .protected_http_api_route("/restart", post(http::restart))
+            .protected_http_api_route("/set_time", post(http::time::set_time))
+            .protected_http_api_route("/increase_time", post(http::time::increase_time))
+            .protected_http_api_route("/mint", post(http::mint_token::mint));
+    }
+
+    let server = builder.build(starknet_config)?;
+
+    Ok(server)
 }
@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use serde::{Serialize, Serializer};
 use starknet_rs_core::chain_id::{MAINNET, TESTNET};
 use starknet_rs_ff::FieldElement;
 
@@ -28,6 +29,15 @@ impl Display for ChainId {
     }
 }
 
+impl Serialize for ChainId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 impl From<ChainId> for FieldElement {
     fn from(value: ChainId) -> Self {
         match value {
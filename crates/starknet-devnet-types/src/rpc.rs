@@ -1,12 +1,16 @@
 pub mod block;
+pub mod block_override;
 pub mod contract_address;
 pub mod contract_class;
 pub mod emitted_event;
 pub mod estimate_message_fee;
+pub mod execution_profile;
 pub mod eth_address;
 pub mod felt;
 mod macro_utils;
 pub mod messaging;
 pub mod state;
+pub mod state_override;
 pub mod transaction_receipt;
 pub mod transactions;
+pub mod v0_5;
@@ -81,6 +81,40 @@ pub struct BlockHeader {
     pub l1_gas_price: ResourcePrice,
 }
 
+/// A block that is not sealed yet. Unlike [`Block`], it has no status, hash, number or new root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PendingBlock {
+    #[serde(flatten)]
+    pub header: PendingBlockHeader,
+    pub transactions: Transactions,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PendingBlockHeader {
+    pub parent_hash: BlockHash,
+    pub sequencer_address: ContractAddress,
+    pub timestamp: BlockTimestamp,
+    pub starknet_version: String,
+    pub l1_gas_price: ResourcePrice,
+}
+
+impl From<Block> for PendingBlock {
+    fn from(block: Block) -> Self {
+        Self {
+            header: PendingBlockHeader {
+                parent_hash: block.header.parent_hash,
+                sequencer_address: block.header.sequencer_address,
+                timestamp: block.header.timestamp,
+                starknet_version: block.header.starknet_version,
+                l1_gas_price: block.header.l1_gas_price,
+            },
+            transactions: block.transactions,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ResourcePrice {
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::contract_address::ContractAddress;
+use crate::felt::Felt;
+
+/// Fields of the block context which are replaced when estimating fees or simulating
+/// transactions. The block context of Devnet itself is not changed.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequencer_address: Option<ContractAddress>,
+    /// In wei
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eth_l1_gas_price: Option<Felt>,
+    /// In fri
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strk_l1_gas_price: Option<Felt>,
+}
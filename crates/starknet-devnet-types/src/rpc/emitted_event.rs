@@ -8,8 +8,11 @@ use crate::felt::{BlockHash, Felt, TransactionHash};
 #[serde(deny_unknown_fields)]
 pub struct EmittedEvent {
     pub transaction_hash: TransactionHash,
-    pub block_hash: BlockHash,
-    pub block_number: BlockNumber,
+    /// not present for events of the pending block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<BlockHash>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<BlockNumber>,
     pub from_address: ContractAddress,
     pub keys: Vec<Felt>,
     pub data: Vec<Felt>,
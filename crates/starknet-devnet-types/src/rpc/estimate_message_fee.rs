@@ -16,7 +16,7 @@ use crate::{impl_wrapper_deserialize, impl_wrapper_serialize};
 
 #[derive(Clone, Debug)]
 pub struct FeeEstimateWrapper {
-    inner: FeeEstimate,
+    pub(crate) inner: FeeEstimate,
 }
 
 impl_wrapper_deserialize!(FeeEstimateWrapper, FeeEstimate);
@@ -36,7 +36,8 @@ impl FeeEstimateWrapper {
             inner: FeeEstimate {
                 gas_consumed: FieldElement::from(gas_consumed),
                 gas_price: FieldElement::from(gas_price),
-                overall_fee: FieldElement::from(gas_consumed * gas_price),
+                // the product of two u64 values always fits in u128
+                overall_fee: FieldElement::from(gas_consumed as u128 * gas_price as u128),
                 unit,
             },
         }
@@ -0,0 +1,36 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::contract_address::ContractAddress;
+use crate::felt::Felt;
+
+/// The resources used by the execution of a transaction, broken down per contract and entry point
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutionProfile {
+    /// Sorted by the number of steps, in descending order
+    pub entry_points: Vec<EntryPointProfile>,
+    /// One line per call stack, in the folded stacks format read by flamegraph tools. The frames
+    /// are separated with `;` and followed by the steps spent in the last frame.
+    pub folded_stacks: String,
+}
+
+/// The resources used by the invocations of an entry point of a contract. Only the resources
+/// used by the entry point itself are counted, the ones of the calls it makes are left out.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EntryPointProfile {
+    pub contract_address: ContractAddress,
+    pub entry_point_selector: Felt,
+    /// The name from the ABI of the class; the selector if the ABI doesn't have it
+    pub entry_point: String,
+    pub calls: usize,
+    pub steps: usize,
+    pub memory_holes: usize,
+    pub builtins: BTreeMap<String, usize>,
+    /// The syscalls seen in the call tree of the execution: `call_contract`, `library_call`,
+    /// `deploy`, `emit_event`, `send_message_to_l1` and `storage_read`. Other syscalls, such as
+    /// `storage_write` or `get_block_hash`, leave no trace there and aren't counted.
+    pub traced_syscalls: BTreeMap<String, usize>,
+}
@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::contract_address::ContractAddress;
+use crate::felt::{ClassHash, Felt};
+
+/// Changes applied to the state of a contract before executing a call, a fee estimation or a
+/// simulation. They are applied to a copy of the state and never committed.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContractStateOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Felt>,
+    /// The class must be declared. If the address is not deployed, it is deployed with this class.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_hash: Option<ClassHash>,
+    /// Storage key to value
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub storage: HashMap<Felt, Felt>,
+    /// The balance in the ETH fee token, set in the storage of the token contract
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eth_balance: Option<Felt>,
+    /// The balance in the STRK fee token, set in the storage of the token contract
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strk_balance: Option<Felt>,
+}
+
+/// The overrides per contract address
+pub type StateOverride = HashMap<ContractAddress, ContractStateOverride>;
@@ -40,6 +40,7 @@ use crate::felt::{
     BlockHash, Calldata, EntryPointSelector, Felt, Nonce, TransactionHash, TransactionSignature,
     TransactionVersion,
 };
+use crate::rpc::execution_profile::ExecutionProfile;
 use crate::rpc::transaction_receipt::{CommonTransactionReceipt, MaybePendingProperties};
 use crate::{impl_wrapper_deserialize, impl_wrapper_serialize};
 
@@ -325,6 +326,12 @@ impl ResourceBoundsWrapper {
             },
         }
     }
+
+    /// The most that can be paid for L1 gas
+    pub fn l1_gas_max_fee(&self) -> Fee {
+        Fee((self.inner.l1_gas.max_amount as u128)
+            .saturating_mul(self.inner.l1_gas.max_price_per_unit))
+    }
 }
 
 impl From<&ResourceBoundsWrapper> for starknet_api::transaction::ResourceBoundsMapping {
@@ -656,18 +663,18 @@ pub enum CallType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct FunctionInvocation {
-    contract_address: ContractAddress,
-    entry_point_selector: EntryPointSelector,
-    calldata: Calldata,
-    caller_address: ContractAddress,
-    class_hash: Felt,
-    entry_point_type: EntryPointType,
-    call_type: CallType,
-    result: Vec<Felt>,
-    calls: Vec<FunctionInvocation>,
-    events: Vec<OrderedEvent>,
-    messages: Vec<OrderedMessageToL1>,
-    execution_resources: ExecutionResources,
+    pub(crate) contract_address: ContractAddress,
+    pub(crate) entry_point_selector: EntryPointSelector,
+    pub(crate) calldata: Calldata,
+    pub(crate) caller_address: ContractAddress,
+    pub(crate) class_hash: Felt,
+    pub(crate) entry_point_type: EntryPointType,
+    pub(crate) call_type: CallType,
+    pub(crate) result: Vec<Felt>,
+    pub(crate) calls: Vec<FunctionInvocation>,
+    pub(crate) events: Vec<OrderedEvent>,
+    pub(crate) messages: Vec<OrderedMessageToL1>,
+    pub(crate) execution_resources: ExecutionResources,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -744,6 +751,9 @@ pub struct L1HandlerTransactionTrace {
 pub struct SimulatedTransaction {
     pub transaction_trace: TransactionTrace,
     pub fee_estimation: FeeEstimateWrapper,
+    /// Only present if Devnet is started with `--profile`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<ExecutionProfile>,
 }
 
 impl FunctionInvocation {
@@ -12,18 +12,18 @@ use crate::felt::{
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct InvokeTransactionV3 {
-    version: TransactionVersion,
-    signature: TransactionSignature,
-    nonce: Nonce,
-    resource_bounds: ResourceBoundsWrapper,
-    tip: Tip,
-    paymaster_data: Vec<Felt>,
-    nonce_data_availability_mode: DataAvailabilityMode,
-    fee_data_availability_mode: DataAvailabilityMode,
-    account_deployment_data: Vec<Felt>,
-    sender_address: ContractAddress,
-    calldata: Calldata,
-    transaction_hash: TransactionHash,
+    pub(crate) version: TransactionVersion,
+    pub(crate) signature: TransactionSignature,
+    pub(crate) nonce: Nonce,
+    pub(crate) resource_bounds: ResourceBoundsWrapper,
+    pub(crate) tip: Tip,
+    pub(crate) paymaster_data: Vec<Felt>,
+    pub(crate) nonce_data_availability_mode: DataAvailabilityMode,
+    pub(crate) fee_data_availability_mode: DataAvailabilityMode,
+    pub(crate) account_deployment_data: Vec<Felt>,
+    pub(crate) sender_address: ContractAddress,
+    pub(crate) calldata: Calldata,
+    pub(crate) transaction_hash: TransactionHash,
 }
 
 impl InvokeTransactionV3 {
@@ -0,0 +1,551 @@
+//! Serialization of RPC responses according to the 0.5 version of the specification.
+//!
+//! The types in the rest of the `rpc` module follow the latest supported version of the
+//! specification. The types in this module are constructed from them and only differ in the fields
+//! that changed between the versions: fee units, l1 gas price, execution resources and the fields
+//! of transactions of version 3.
+
+use serde::Serialize;
+use starknet_api::block::{BlockNumber, BlockStatus, BlockTimestamp};
+use starknet_api::deprecated_contract_class::EntryPointType;
+use starknet_api::transaction::Fee;
+use starknet_rs_core::types::{ExecutionResult, Hash256, TransactionFinalityStatus};
+
+use super::block::GlobalRootHex;
+use super::estimate_message_fee::FeeEstimateWrapper;
+use super::messaging::{MessageToL1, OrderedMessageToL1};
+use super::transaction_receipt::{FeeAmount, FeeInUnits, MaybePendingProperties};
+use super::transactions::declare_transaction_v2::DeclareTransactionV2;
+use super::transactions::deploy_account_transaction_v1::DeployAccountTransactionV1;
+use super::transactions::invoke_transaction_v1::InvokeTransactionV1;
+use super::transactions::{
+    CallType, DeclareTransaction, DeployAccountTransaction, InvokeTransaction, Reversion,
+    Transaction, TransactionType, Transactions,
+};
+use crate::contract_address::ContractAddress;
+use crate::emitted_event::{Event, OrderedEvent};
+use crate::felt::{BlockHash, Calldata, EntryPointSelector, Felt, TransactionHash};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeEstimate {
+    pub gas_consumed: Felt,
+    pub gas_price: Felt,
+    pub overall_fee: Felt,
+}
+
+impl From<&FeeEstimateWrapper> for FeeEstimate {
+    fn from(fee_estimate: &FeeEstimateWrapper) -> Self {
+        Self {
+            gas_consumed: fee_estimate.inner.gas_consumed.into(),
+            gas_price: fee_estimate.inner.gas_price.into(),
+            overall_fee: fee_estimate.inner.overall_fee.into(),
+        }
+    }
+}
+
+/// In 0.5 all resources are required and are serialized as hex strings
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionResources {
+    pub steps: Felt,
+    pub memory_holes: Felt,
+    pub range_check_builtin_applications: Felt,
+    pub pedersen_builtin_applications: Felt,
+    pub poseidon_builtin_applications: Felt,
+    pub ec_op_builtin_applications: Felt,
+    pub ecdsa_builtin_applications: Felt,
+    pub bitwise_builtin_applications: Felt,
+    pub keccak_builtin_applications: Felt,
+}
+
+impl From<&super::transaction_receipt::ExecutionResources> for ExecutionResources {
+    fn from(resources: &super::transaction_receipt::ExecutionResources) -> Self {
+        fn to_hex(value: Option<usize>) -> Felt {
+            Felt::from(value.unwrap_or_default() as u128)
+        }
+
+        Self {
+            steps: to_hex(Some(resources.steps)),
+            memory_holes: to_hex(resources.memory_holes),
+            range_check_builtin_applications: to_hex(resources.range_check_builtin_applications),
+            pedersen_builtin_applications: to_hex(resources.pedersen_builtin_applications),
+            poseidon_builtin_applications: to_hex(resources.poseidon_builtin_applications),
+            ec_op_builtin_applications: to_hex(resources.ec_op_builtin_applications),
+            ecdsa_builtin_applications: to_hex(resources.ecdsa_builtin_applications),
+            bitwise_builtin_applications: to_hex(resources.bitwise_builtin_applications),
+            keccak_builtin_applications: to_hex(resources.keccak_builtin_applications),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum TransactionReceipt {
+    Deploy(DeployTransactionReceipt),
+    L1Handler(L1HandlerTransactionReceipt),
+    Common(CommonTransactionReceipt),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeployTransactionReceipt {
+    #[serde(flatten)]
+    pub common: CommonTransactionReceipt,
+    pub contract_address: ContractAddress,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct L1HandlerTransactionReceipt {
+    #[serde(flatten)]
+    pub common: CommonTransactionReceipt,
+    pub message_hash: Hash256,
+}
+
+/// Same as the receipt of the latest version, but the actual fee has no unit
+#[derive(Debug, Clone, Serialize)]
+pub struct CommonTransactionReceipt {
+    pub r#type: TransactionType,
+    pub transaction_hash: TransactionHash,
+    pub actual_fee: Fee,
+    pub messages_sent: Vec<MessageToL1>,
+    pub events: Vec<Event>,
+    #[serde(flatten)]
+    pub execution_status: ExecutionResult,
+    pub finality_status: TransactionFinalityStatus,
+    #[serde(flatten)]
+    pub maybe_pending_properties: MaybePendingProperties,
+    pub execution_resources: ExecutionResources,
+}
+
+impl From<&super::transaction_receipt::CommonTransactionReceipt> for CommonTransactionReceipt {
+    fn from(receipt: &super::transaction_receipt::CommonTransactionReceipt) -> Self {
+        let actual_fee = match &receipt.actual_fee {
+            FeeInUnits::WEI(FeeAmount { amount }) | FeeInUnits::FRI(FeeAmount { amount }) => {
+                *amount
+            }
+        };
+
+        Self {
+            r#type: receipt.r#type,
+            transaction_hash: receipt.transaction_hash,
+            actual_fee,
+            messages_sent: receipt.messages_sent.clone(),
+            events: receipt.events.clone(),
+            execution_status: receipt.execution_status.clone(),
+            finality_status: receipt.finality_status,
+            maybe_pending_properties: receipt.maybe_pending_properties.clone(),
+            execution_resources: ExecutionResources::from(&receipt.execution_resources),
+        }
+    }
+}
+
+impl From<&super::transaction_receipt::TransactionReceipt> for TransactionReceipt {
+    fn from(receipt: &super::transaction_receipt::TransactionReceipt) -> Self {
+        match receipt {
+            super::transaction_receipt::TransactionReceipt::Deploy(deploy) => {
+                TransactionReceipt::Deploy(DeployTransactionReceipt {
+                    common: (&deploy.common).into(),
+                    contract_address: deploy.contract_address,
+                })
+            }
+            super::transaction_receipt::TransactionReceipt::L1Handler(l1_handler) => {
+                TransactionReceipt::L1Handler(L1HandlerTransactionReceipt {
+                    common: (&l1_handler.common).into(),
+                    message_hash: l1_handler.message_hash,
+                })
+            }
+            super::transaction_receipt::TransactionReceipt::Common(common) => {
+                TransactionReceipt::Common(common.into())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourcePrice {
+    pub price_in_wei: Felt,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockHeader {
+    pub block_hash: BlockHash,
+    pub parent_hash: BlockHash,
+    pub block_number: BlockNumber,
+    pub sequencer_address: ContractAddress,
+    pub new_root: GlobalRootHex,
+    pub timestamp: BlockTimestamp,
+    pub starknet_version: String,
+    pub l1_gas_price: ResourcePrice,
+}
+
+impl From<&super::block::BlockHeader> for BlockHeader {
+    fn from(header: &super::block::BlockHeader) -> Self {
+        Self {
+            block_hash: header.block_hash,
+            parent_hash: header.parent_hash,
+            block_number: header.block_number,
+            sequencer_address: header.sequencer_address,
+            new_root: header.new_root,
+            timestamp: header.timestamp,
+            starknet_version: header.starknet_version.clone(),
+            l1_gas_price: ResourcePrice { price_in_wei: header.l1_gas_price.price_in_wei },
+        }
+    }
+}
+
+/// Transactions of version 3 are not part of 0.5, so they are shown with the fields of the
+/// previous version of their type. The most they can pay for L1 gas is shown as the maximal fee.
+pub fn transaction(transaction: &Transaction) -> Transaction {
+    match transaction {
+        Transaction::Invoke(InvokeTransaction::Version3(invoke)) => {
+            Transaction::Invoke(InvokeTransaction::Version1(InvokeTransactionV1 {
+                transaction_hash: invoke.transaction_hash,
+                max_fee: invoke.resource_bounds.l1_gas_max_fee(),
+                version: invoke.version,
+                signature: invoke.signature.clone(),
+                nonce: invoke.nonce,
+                sender_address: invoke.sender_address,
+                calldata: invoke.calldata.clone(),
+            }))
+        }
+        Transaction::Declare(DeclareTransaction::Version3(declare)) => {
+            Transaction::Declare(DeclareTransaction::Version2(DeclareTransactionV2 {
+                class_hash: declare.class_hash,
+                compiled_class_hash: declare.compiled_class_hash,
+                sender_address: declare.sender_address,
+                nonce: declare.nonce,
+                max_fee: declare.resource_bounds.l1_gas_max_fee(),
+                version: declare.version,
+                transaction_hash: declare.transaction_hash,
+                signature: declare.signature.clone(),
+            }))
+        }
+        Transaction::DeployAccount(DeployAccountTransaction::Version3(deploy_account)) => {
+            Transaction::DeployAccount(DeployAccountTransaction::Version1(Box::new(
+                DeployAccountTransactionV1 {
+                    transaction_hash: deploy_account.transaction_hash,
+                    max_fee: deploy_account.resource_bounds.l1_gas_max_fee(),
+                    version: deploy_account.version,
+                    signature: deploy_account.signature.clone(),
+                    nonce: deploy_account.nonce,
+                    class_hash: deploy_account.class_hash,
+                    contract_address_salt: deploy_account.contract_address_salt,
+                    constructor_calldata: deploy_account.constructor_calldata.clone(),
+                    contract_address: deploy_account.contract_address,
+                },
+            )))
+        }
+        other => other.clone(),
+    }
+}
+
+fn transactions(transactions: &Transactions) -> Transactions {
+    match transactions {
+        Transactions::Hashes(hashes) => Transactions::Hashes(hashes.clone()),
+        Transactions::Full(transactions) => {
+            Transactions::Full(transactions.iter().map(transaction).collect())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Block {
+    pub status: BlockStatus,
+    #[serde(flatten)]
+    pub header: BlockHeader,
+    pub transactions: Transactions,
+}
+
+impl From<&super::block::Block> for Block {
+    fn from(block: &super::block::Block) -> Self {
+        Self {
+            status: block.status,
+            header: (&block.header).into(),
+            transactions: transactions(&block.transactions),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingBlockHeader {
+    pub parent_hash: BlockHash,
+    pub sequencer_address: ContractAddress,
+    pub timestamp: BlockTimestamp,
+    pub starknet_version: String,
+    pub l1_gas_price: ResourcePrice,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingBlock {
+    #[serde(flatten)]
+    pub header: PendingBlockHeader,
+    pub transactions: Transactions,
+}
+
+impl From<&super::block::PendingBlock> for PendingBlock {
+    fn from(block: &super::block::PendingBlock) -> Self {
+        Self {
+            header: PendingBlockHeader {
+                parent_hash: block.header.parent_hash,
+                sequencer_address: block.header.sequencer_address,
+                timestamp: block.header.timestamp,
+                starknet_version: block.header.starknet_version.clone(),
+                l1_gas_price: ResourcePrice {
+                    price_in_wei: block.header.l1_gas_price.price_in_wei,
+                },
+            },
+            transactions: transactions(&block.transactions),
+        }
+    }
+}
+
+/// Same as the function invocation of the latest version, but without execution resources
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionInvocation {
+    contract_address: ContractAddress,
+    entry_point_selector: EntryPointSelector,
+    calldata: Calldata,
+    caller_address: ContractAddress,
+    class_hash: Felt,
+    entry_point_type: EntryPointType,
+    call_type: CallType,
+    result: Vec<Felt>,
+    calls: Vec<FunctionInvocation>,
+    events: Vec<OrderedEvent>,
+    messages: Vec<OrderedMessageToL1>,
+}
+
+impl From<&super::transactions::FunctionInvocation> for FunctionInvocation {
+    fn from(invocation: &super::transactions::FunctionInvocation) -> Self {
+        Self {
+            contract_address: invocation.contract_address,
+            entry_point_selector: invocation.entry_point_selector,
+            calldata: invocation.calldata.clone(),
+            caller_address: invocation.caller_address,
+            class_hash: invocation.class_hash,
+            entry_point_type: invocation.entry_point_type,
+            call_type: invocation.call_type.clone(),
+            result: invocation.result.clone(),
+            calls: invocation.calls.iter().map(FunctionInvocation::from).collect(),
+            events: invocation.events.clone(),
+            messages: invocation.messages.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ExecutionInvocation {
+    Succeeded(FunctionInvocation),
+    Reverted(Reversion),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum TransactionTrace {
+    #[serde(rename = "INVOKE")]
+    Invoke {
+        validate_invocation: Option<FunctionInvocation>,
+        execute_invocation: ExecutionInvocation,
+        fee_transfer_invocation: Option<FunctionInvocation>,
+    },
+    #[serde(rename = "DECLARE")]
+    Declare {
+        validate_invocation: Option<FunctionInvocation>,
+        fee_transfer_invocation: Option<FunctionInvocation>,
+    },
+    #[serde(rename = "DEPLOY_ACCOUNT")]
+    DeployAccount {
+        validate_invocation: Option<FunctionInvocation>,
+        constructor_invocation: Option<FunctionInvocation>,
+        fee_transfer_invocation: Option<FunctionInvocation>,
+    },
+    #[serde(rename = "L1_HANDLER")]
+    L1Handler { function_invocation: FunctionInvocation },
+}
+
+impl From<&super::transactions::TransactionTrace> for TransactionTrace {
+    fn from(trace: &super::transactions::TransactionTrace) -> Self {
+        let convert = |invocation: &Option<super::transactions::FunctionInvocation>| {
+            invocation.as_ref().map(FunctionInvocation::from)
+        };
+
+        match trace {
+            super::transactions::TransactionTrace::Invoke(invoke) => TransactionTrace::Invoke {
+                validate_invocation: convert(&invoke.validate_invocation),
+                execute_invocation: match &invoke.execute_invocation {
+                    super::transactions::ExecutionInvocation::Succeeded(invocation) => {
+                        ExecutionInvocation::Succeeded(invocation.into())
+                    }
+                    super::transactions::ExecutionInvocation::Reverted(reversion) => {
+                        ExecutionInvocation::Reverted(reversion.clone())
+                    }
+                },
+                fee_transfer_invocation: convert(&invoke.fee_transfer_invocation),
+            },
+            super::transactions::TransactionTrace::Declare(declare) => TransactionTrace::Declare {
+                validate_invocation: convert(&declare.validate_invocation),
+                fee_transfer_invocation: convert(&declare.fee_transfer_invocation),
+            },
+            super::transactions::TransactionTrace::DeployAccount(deploy_account) => {
+                TransactionTrace::DeployAccount {
+                    validate_invocation: convert(&deploy_account.validate_invocation),
+                    constructor_invocation: convert(&deploy_account.constructor_invocation),
+                    fee_transfer_invocation: convert(&deploy_account.fee_transfer_invocation),
+                }
+            }
+            super::transactions::TransactionTrace::L1Handler(l1_handler) => {
+                TransactionTrace::L1Handler {
+                    function_invocation: (&l1_handler.function_invocation).into(),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockTransactionTrace {
+    pub transaction_hash: Felt,
+    pub trace_root: TransactionTrace,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockTransactionTraces {
+    pub traces: Vec<BlockTransactionTrace>,
+}
+
+impl From<&super::transactions::BlockTransactionTraces> for BlockTransactionTraces {
+    fn from(block_traces: &super::transactions::BlockTransactionTraces) -> Self {
+        Self {
+            traces: block_traces
+                .traces
+                .iter()
+                .map(|trace| BlockTransactionTrace {
+                    transaction_hash: trace.transaction_hash,
+                    trace_root: (&trace.trace_root).into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedTransaction {
+    pub transaction_trace: TransactionTrace,
+    pub fee_estimation: FeeEstimate,
+}
+
+impl From<&super::transactions::SimulatedTransaction> for SimulatedTransaction {
+    fn from(simulated: &super::transactions::SimulatedTransaction) -> Self {
+        Self {
+            transaction_trace: (&simulated.transaction_trace).into(),
+            fee_estimation: (&simulated.fee_estimation).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet_api::transaction::Fee;
+    use starknet_rs_core::types::{ExecutionResult, TransactionFinalityStatus};
+
+    use super::ExecutionResources;
+    use crate::felt::Felt;
+    use crate::rpc::estimate_message_fee::FeeEstimateWrapper;
+    use crate::rpc::transaction_receipt::{
+        CommonTransactionReceipt, FeeAmount, FeeInUnits, MaybePendingProperties,
+    };
+    use crate::rpc::transactions::{InvokeTransaction, Transaction, TransactionType};
+
+    #[test]
+    fn fee_estimate_is_serialized_without_unit() {
+        let fee_estimate = super::FeeEstimate::from(&FeeEstimateWrapper::new_in_wei_units(2, 3));
+        let serialized = serde_json::to_value(fee_estimate).unwrap();
+
+        assert_eq!(
+            serialized,
+            serde_json::json!({"gas_consumed": "0x2", "gas_price": "0x3", "overall_fee": "0x6"})
+        );
+    }
+
+    #[test]
+    fn receipt_is_serialized_with_fee_without_unit_and_hex_resources() {
+        let receipt = CommonTransactionReceipt {
+            r#type: TransactionType::Invoke,
+            transaction_hash: Felt::from(1),
+            actual_fee: FeeInUnits::WEI(FeeAmount { amount: Fee(10) }),
+            messages_sent: vec![],
+            events: vec![],
+            execution_status: ExecutionResult::Succeeded,
+            finality_status: TransactionFinalityStatus::AcceptedOnL2,
+            maybe_pending_properties: MaybePendingProperties {
+                block_hash: None,
+                block_number: None,
+            },
+            execution_resources: crate::rpc::transaction_receipt::ExecutionResources {
+                steps: 16,
+                memory_holes: None,
+                range_check_builtin_applications: Some(2),
+                pedersen_builtin_applications: None,
+                poseidon_builtin_applications: None,
+                ec_op_builtin_applications: None,
+                ecdsa_builtin_applications: None,
+                bitwise_builtin_applications: None,
+                keccak_builtin_applications: None,
+            },
+        };
+
+        let serialized =
+            serde_json::to_value(super::CommonTransactionReceipt::from(&receipt)).unwrap();
+
+        assert_eq!(serialized["actual_fee"], serde_json::json!("0xa"));
+        assert_eq!(
+            serde_json::to_value(ExecutionResources::from(&receipt.execution_resources)).unwrap(),
+            serialized["execution_resources"]
+        );
+        assert_eq!(serialized["execution_resources"]["steps"], serde_json::json!("0x10"));
+        assert_eq!(serialized["execution_resources"]["memory_holes"], serde_json::json!("0x0"));
+        assert_eq!(
+            serialized["execution_resources"]["range_check_builtin_applications"],
+            serde_json::json!("0x2")
+        );
+    }
+
+    #[test]
+    fn invoke_transaction_v3_is_serialized_with_max_fee() {
+        let transaction: Transaction = serde_json::from_value(serde_json::json!({
+            "type": "INVOKE",
+            "version": "0x3",
+            "signature": ["0x1"],
+            "nonce": "0x2",
+            "resource_bounds": {
+                "l1_gas": {"max_amount": "0x10", "max_price_per_unit": "0x3"},
+                "l2_gas": {"max_amount": "0x0", "max_price_per_unit": "0x0"}
+            },
+            "tip": "0x0",
+            "paymaster_data": [],
+            "nonce_data_availability_mode": "L1",
+            "fee_data_availability_mode": "L1",
+            "account_deployment_data": [],
+            "sender_address": "0x4",
+            "calldata": ["0x5"],
+            "transaction_hash": "0x6"
+        }))
+        .unwrap();
+        assert!(matches!(transaction, Transaction::Invoke(InvokeTransaction::Version3(_))));
+
+        let serialized = serde_json::to_value(super::transaction(&transaction)).unwrap();
+
+        assert_eq!(
+            serialized,
+            serde_json::json!({
+                "type": "INVOKE",
+                "transaction_hash": "0x6",
+                "max_fee": "0x30",
+                "version": "0x3",
+                "signature": ["0x1"],
+                "nonce": "0x2",
+                "sender_address": "0x4",
+                "calldata": ["0x5"]
+            })
+        );
+    }
+}
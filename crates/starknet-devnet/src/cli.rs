@@ -1,4 +1,5 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use server::ServerConfig;
 use starknet_core::constants::{
     DEVNET_DEFAULT_GAS_PRICE, DEVNET_DEFAULT_PORT, DEVNET_DEFAULT_TIMEOUT,
     DEVNET_DEFAULT_TOTAL_ACCOUNTS,
@@ -108,6 +109,71 @@ pub(crate) struct Args {
     #[arg(default_value = "none")]
     #[arg(help = "Specify the state archive capacity;")]
     state_archive: StateArchiveCapacity,
+
+    #[arg(long = "profile")]
+    #[arg(help = "Add to each simulated transaction the steps, builtins and traced syscalls used per \
+                  contract and entry point;")]
+    profile: bool,
+
+    #[arg(long = "record")]
+    #[arg(value_name = "FILE")]
+    #[arg(help = "Specify the file to which every request and response is recorded, as lines of \
+                  JSON;")]
+    record: Option<String>,
+
+    #[arg(long = "tls-cert")]
+    #[arg(value_name = "FILE")]
+    #[arg(requires = "tls_key")]
+    #[arg(help = "Specify the PEM certificate chain to serve HTTPS with; requires --tls-key;")]
+    tls_cert: Option<String>,
+
+    #[arg(long = "tls-key")]
+    #[arg(value_name = "FILE")]
+    #[arg(requires = "tls_cert")]
+    #[arg(help = "Specify the PEM private key of the certificate provided with --tls-cert;")]
+    tls_key: Option<String>,
+
+    #[arg(long = "unix-socket")]
+    #[arg(value_name = "PATH")]
+    #[arg(conflicts_with_all = ["tls_cert", "tls_key"])]
+    #[arg(help = "Specify the Unix socket to listen at, instead of the host and port; the file \
+                  must not exist;")]
+    unix_socket: Option<String>,
+
+    #[arg(long = "validate-rpc-schema")]
+    #[arg(help = "Check JSON-RPC requests and responses against the specification; violations \
+                  are returned as errors;")]
+    validate_rpc_schema: bool,
+
+    #[arg(long = "api-key")]
+    #[arg(value_name = "KEY")]
+    #[arg(help = "Specify the bearer token required by the endpoints which control Devnet, e.g. \
+                  minting or setting the time; read-only endpoints stay open;")]
+    api_key: Option<String>,
+
+    #[arg(long = "require-api-key-for-writes")]
+    #[arg(requires = "api_key")]
+    #[arg(help = "Require the API key also for adding transactions; requires --api-key;")]
+    require_api_key_for_writes: bool,
+
+    #[arg(long = "disable-devnet-api")]
+    #[arg(help = "Do not serve the endpoints which control Devnet; the JSON-RPC API of Starknet \
+                  stays available;")]
+    disable_devnet_api: bool,
+
+    #[command(subcommand)]
+    pub(crate) command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum Command {
+    /// Start Devnet, send it the requests of a recording and compare the responses with the
+    /// recorded ones
+    Replay {
+        #[arg(value_name = "FILE")]
+        #[arg(help = "The file created with --record;")]
+        recording_path: String,
+    },
 }
 
 impl Args {
@@ -137,8 +203,23 @@ impl Args {
             dump_path: self.dump_path.clone(),
             re_execute_on_init: true,
             state_archive: self.state_archive,
+            profile: self.profile,
         })
     }
+
+    pub(crate) fn to_server_config(&self) -> ServerConfig {
+        ServerConfig {
+            validate_rpc_schema: self.validate_rpc_schema,
+            record_path: self.record.clone(),
+            tls_cert_path: self.tls_cert.clone(),
+            tls_key_path: self.tls_key.clone(),
+            unix_socket_path: self.unix_socket.clone(),
+            api_key: self.api_key.clone(),
+            require_api_key_for_writes: self.require_api_key_for_writes,
+            disable_devnet_api: self.disable_devnet_api,
+            ..Default::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -149,7 +230,7 @@ mod tests {
     };
     use starknet_core::starknet::starknet_config::StateArchiveCapacity;
 
-    use super::Args;
+    use super::{Args, Command};
     use crate::ip_addr_wrapper::IpAddrWrapper;
 
     #[test]
@@ -192,6 +273,76 @@ mod tests {
         assert_eq!(args.to_starknet_config().unwrap().state_archive, StateArchiveCapacity::None);
     }
 
+    #[test]
+    fn rpc_schema_validation_disabled_by_default() {
+        let args = Args::parse_from(["--"]);
+        assert!(!args.to_server_config().validate_rpc_schema);
+
+        let args = Args::parse_from(["--", "--validate-rpc-schema"]);
+        assert!(args.to_server_config().validate_rpc_schema);
+    }
+
+    #[test]
+    fn parsing_replay_subcommand() {
+        let args = Args::parse_from(["--", "--seed", "1", "replay", "session.jsonl"]);
+        assert_eq!(args.seed, Some(1));
+        match args.command {
+            Some(Command::Replay { recording_path }) => assert_eq!(recording_path, "session.jsonl"),
+            other => panic!("Unexpected command: {other:?}"),
+        }
+
+        let args = Args::parse_from(["--", "--record", "session.jsonl"]);
+        assert!(args.command.is_none());
+        assert_eq!(args.to_server_config().record_path.as_deref(), Some("session.jsonl"));
+    }
+
+    #[test]
+    fn tls_cert_and_key_required_together() {
+        let config = Args::parse_from(["--", "--tls-cert", "cert.pem", "--tls-key", "key.pem"])
+            .to_server_config();
+        assert_eq!(config.tls_cert_path.as_deref(), Some("cert.pem"));
+        assert_eq!(config.tls_key_path.as_deref(), Some("key.pem"));
+
+        for partial_args in [["--", "--tls-cert", "cert.pem"], ["--", "--tls-key", "key.pem"]] {
+            if let Ok(parsed) = Args::try_parse_from(partial_args) {
+                panic!("Should have failed; got: {parsed:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn unix_socket_conflicts_with_tls() {
+        let args = Args::parse_from(["--", "--unix-socket", "devnet.sock"]);
+        assert_eq!(args.to_server_config().unix_socket_path.as_deref(), Some("devnet.sock"));
+
+        match Args::try_parse_from([
+            "--",
+            "--unix-socket",
+            "devnet.sock",
+            "--tls-cert",
+            "cert.pem",
+            "--tls-key",
+            "key.pem",
+        ]) {
+            Err(_) => (),
+            Ok(parsed) => panic!("Should have failed; got: {parsed:?}"),
+        }
+    }
+
+    #[test]
+    fn requiring_api_key_for_writes_requires_api_key() {
+        let config =
+            Args::parse_from(["--", "--api-key", "secret", "--require-api-key-for-writes"])
+                .to_server_config();
+        assert_eq!(config.api_key.as_deref(), Some("secret"));
+        assert!(config.require_api_key_for_writes);
+
+        match Args::try_parse_from(["--", "--require-api-key-for-writes"]) {
+            Err(_) => (),
+            Ok(parsed) => panic!("Should have failed; got: {parsed:?}"),
+        }
+    }
+
     #[test]
     fn state_archive_full() {
         let args = Args::parse_from(["--", "--state-archive-capacity", "full"]);
@@ -2,10 +2,10 @@ use std::net::SocketAddr;
 
 use anyhow::Ok;
 use clap::Parser;
-use cli::Args;
+use cli::{Args, Command};
 use server::api::Api;
+use server::listener::ListenAddress;
 use server::server::serve_http_api_json_rpc;
-use server::ServerConfig;
 use starknet_core::account::Account;
 use starknet_core::constants::{
     CAIRO_1_ERC20_CONTRACT_CLASS_HASH, ETH_ERC20_CONTRACT_ADDRESS, STRK_ERC20_CONTRACT_ADDRESS,
@@ -19,10 +19,13 @@ use starknet_types::traits::{ToDecimalString, ToHexString};
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
+use crate::replay::replay;
+
 mod cli;
 mod contract_class_choice;
 mod initial_balance_wrapper;
 mod ip_addr_wrapper;
+mod replay;
 
 /// Configures tracing with default level INFO,
 /// If the environment variable `RUST_LOG` is set, it will be used instead.
@@ -83,7 +86,7 @@ async fn main() -> Result<(), anyhow::Error> {
     // parse arguments
     let args = Args::parse();
     let starknet_config = args.to_starknet_config()?;
-    let mut addr: SocketAddr = SocketAddr::new(starknet_config.host, starknet_config.port);
+    let addr: SocketAddr = SocketAddr::new(starknet_config.host, starknet_config.port);
 
     let api = Api::new(Starknet::new(&starknet_config)?);
 
@@ -105,24 +108,54 @@ async fn main() -> Result<(), anyhow::Error> {
     );
 
     let server =
-        serve_http_api_json_rpc(addr, ServerConfig::default(), api.clone(), &starknet_config);
-    addr = server.local_addr();
+        serve_http_api_json_rpc(addr, args.to_server_config(), api.clone(), &starknet_config)?;
+    let listen_address = server.local_addr().clone();
 
-    info!("Starknet Devnet listening on {}", addr);
+    info!("Starknet Devnet listening on {}", listen_address);
 
-    // spawn the server on a new task
-    let serve = if starknet_config.dump_on == Some(DumpOn::Exit) {
-        tokio::task::spawn(server.with_graceful_shutdown(shutdown_signal(api.clone())))
-    } else {
-        tokio::task::spawn(server)
-    };
+    // spawn the server on a new task; on a shutdown signal it stops accepting connections and
+    // answers the requests in flight
+    let serve = tokio::task::spawn(server.serve_with_graceful_shutdown(shutdown_signal()));
 
-    Ok(serve.await??)
-}
+    if let Some(Command::Replay { recording_path }) = &args.command {
+        let ListenAddress::Tcp(address) = listen_address else {
+            anyhow::bail!("Replaying is supported only with plain HTTP over TCP");
+        };
+        return replay(recording_path, &format!("http://{address}")).await;
+    }
 
-pub async fn shutdown_signal(api: Api) {
-    tokio::signal::ctrl_c().await.expect("Failed to install CTRL+C signal handler");
+    serve.await??;
 
+    // blocks are created when transactions are accepted, so there is no pending block to seal;
+    // only the events kept for dumping on exit need to be flushed
     let starknet = api.starknet.read().await;
-    starknet.dump_events().expect("Failed to dump starknet transactions");
+    if starknet.config.dump_on == Some(DumpOn::Exit) {
+        starknet.dump_events()?;
+    }
+
+    Ok(())
+}
+
+/// Completes on SIGINT (e.g. Ctrl-C) or, on Unix, on SIGTERM (e.g. `docker stop`)
+async fn shutdown_signal() {
+    let interrupt = async {
+        tokio::signal::ctrl_c().await.expect("Failed to install SIGINT signal handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM signal handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = interrupt => {},
+        _ = terminate => {},
+    }
+
+    info!("Shutting down, waiting for the requests in flight");
 }
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fs;
+
+use anyhow::{anyhow, bail};
+use hyper::client::HttpConnector;
+use hyper::{header, Body, Client, Method, Request};
+use serde_json::Value;
+use server::recorder::{body_to_value, RecordedExchange};
+
+/// Fields which depend on the time of execution: block timestamps and the block hashes derived
+/// from them. Their values are not compared, but they have to be present in both responses.
+const TIME_DEPENDENT_FIELDS: [&str; 4] =
+    ["timestamp", "block_timestamp", "block_hash", "parent_hash"];
+
+/// Sends the recorded requests in their original order to the devnet at `url` and prints the
+/// differences between the received and the recorded responses, ignoring the values of
+/// [`TIME_DEPENDENT_FIELDS`]. Recorded block hashes in later requests are replaced with the
+/// replayed ones. Fails if any response differs.
+pub(crate) async fn replay(recording_path: &str, url: &str) -> Result<(), anyhow::Error> {
+    let recording = fs::read_to_string(recording_path)
+        .map_err(|err| anyhow!("Could not read the recording {recording_path}: {err}"))?;
+    let client = Client::new();
+
+    let mut replaced_hashes = HashMap::new();
+    let mut replayed = 0;
+    let mut mismatched = 0;
+    for (line_index, line) in recording.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut exchange: RecordedExchange = serde_json::from_str(line)
+            .map_err(|err| anyhow!("Invalid exchange at line {}: {err}", line_index + 1))?;
+        replace_hashes(&mut exchange.request, &replaced_hashes);
+        let (status, response) = send(&client, url, &exchange).await?;
+        collect_replaced_hashes(&exchange.response, &response, &mut replaced_hashes);
+        replayed += 1;
+
+        let mut differences = vec![];
+        if status != exchange.status {
+            differences.push(format!("status: expected {}, got {status}", exchange.status));
+        }
+        diff_values(&exchange.response, &response, "response", &mut differences);
+
+        if !differences.is_empty() {
+            mismatched += 1;
+            println!("{} {} (line {}):", exchange.method, exchange.path, line_index + 1);
+            for difference in differences {
+                println!("    {difference}");
+            }
+        }
+    }
+
+    println!("Replayed {replayed} requests, {mismatched} responses differ from the recording");
+    if mismatched > 0 {
+        bail!("{mismatched} responses differ from the recording");
+    }
+
+    Ok(())
+}
+
+async fn send(
+    client: &Client<HttpConnector>,
+    url: &str,
+    exchange: &RecordedExchange,
+) -> Result<(u16, Value), anyhow::Error> {
+    let body = match &exchange.request {
+        Value::Null => Body::empty(),
+        Value::String(raw_body) => Body::from(raw_body.clone()),
+        json_body => Body::from(json_body.to_string()),
+    };
+
+    let request = Request::builder()
+        .method(Method::from_bytes(exchange.method.as_bytes())?)
+        .uri(format!("{url}{}", exchange.path))
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body)?;
+
+    let response = client.request(request).await?;
+    let status = response.status().as_u16();
+    let response_body = hyper::body::to_bytes(response.into_body()).await?;
+
+    Ok((status, body_to_value(&response_body)))
+}
+
+/// Maps the block hashes of the recorded response to the ones at the same paths of the replayed
+/// response
+fn collect_replaced_hashes(
+    recorded: &Value,
+    replayed: &Value,
+    replaced_hashes: &mut HashMap<String, String>,
+) {
+    match (recorded, replayed) {
+        (Value::Object(recorded_fields), Value::Object(replayed_fields)) => {
+            for (name, recorded_field) in recorded_fields {
+                let Some(replayed_field) = replayed_fields.get(name) else { continue };
+                match (name.as_str(), recorded_field, replayed_field) {
+                    (
+                        "block_hash" | "parent_hash",
+                        Value::String(recorded_hash),
+                        Value::String(replayed_hash),
+                    ) if recorded_hash != replayed_hash => {
+                        replaced_hashes.insert(recorded_hash.clone(), replayed_hash.clone());
+                    }
+                    _ => collect_replaced_hashes(recorded_field, replayed_field, replaced_hashes),
+                }
+            }
+        }
+        (Value::Array(recorded_elements), Value::Array(replayed_elements)) => {
+            for (recorded_element, replayed_element) in
+                recorded_elements.iter().zip(replayed_elements)
+            {
+                collect_replaced_hashes(recorded_element, replayed_element, replaced_hashes);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Replaces the recorded block hashes in `value` with the replayed ones
+fn replace_hashes(value: &mut Value, replaced_hashes: &HashMap<String, String>) {
+    match value {
+        Value::String(string) => {
+            if let Some(replaced_hash) = replaced_hashes.get(string) {
+                *string = replaced_hash.clone();
+            }
+        }
+        Value::Array(elements) => {
+            elements.iter_mut().for_each(|element| replace_hashes(element, replaced_hashes))
+        }
+        Value::Object(fields) => {
+            fields.values_mut().for_each(|field| replace_hashes(field, replaced_hashes))
+        }
+        _ => (),
+    }
+}
+
+/// Collects the paths at which `actual` differs from `expected`
+fn diff_values(expected: &Value, actual: &Value, path: &str, differences: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected_fields), Value::Object(actual_fields)) => {
+            for (name, expected_field) in expected_fields {
+                let field_path = format!("{path}.{name}");
+                match actual_fields.get(name) {
+                    Some(_) if TIME_DEPENDENT_FIELDS.contains(&name.as_str()) => (),
+                    Some(actual_field) => {
+                        diff_values(expected_field, actual_field, &field_path, differences)
+                    }
+                    None => differences.push(format!("{field_path}: missing")),
+                }
+            }
+
+            for name in actual_fields.keys().filter(|name| !expected_fields.contains_key(*name)) {
+                differences.push(format!("{path}.{name}: unexpected"));
+            }
+        }
+        (Value::Array(expected_elements), Value::Array(actual_elements))
+            if expected_elements.len() == actual_elements.len() =>
+        {
+            for (index, (expected_element, actual_element)) in
+                expected_elements.iter().zip(actual_elements).enumerate()
+            {
+                diff_values(
+                    expected_element,
+                    actual_element,
+                    &format!("{path}[{index}]"),
+                    differences,
+                );
+            }
+        }
+        _ if expected != actual => {
+            differences.push(format!("{path}: expected {expected}, got {actual}"));
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::{collect_replaced_hashes, diff_values, replace_hashes};
+
+    fn diff(expected: serde_json::Value, actual: serde_json::Value) -> Vec<String> {
+        let mut differences = vec![];
+        diff_values(&expected, &actual, "response", &mut differences);
+        differences
+    }
+
+    #[test]
+    fn equal_values_have_no_differences() {
+        let value = json!({ "result": { "block_number": 1, "transactions": ["0x1", "0x2"] } });
+        assert!(diff(value.clone(), value).is_empty());
+    }
+
+    #[test]
+    fn differences_are_reported_with_their_paths() {
+        let differences = diff(
+            json!({ "result": { "new_root": "0x1", "transactions": ["0x1"], "old": 1 } }),
+            json!({ "result": { "new_root": "0x2", "transactions": ["0x1"], "new": 1 } }),
+        );
+
+        assert_eq!(
+            differences,
+            vec![
+                "response.result.new_root: expected \"0x1\", got \"0x2\"",
+                "response.result.old: missing",
+                "response.result.new: unexpected",
+            ]
+        );
+    }
+
+    #[test]
+    fn arrays_of_different_length_are_reported_as_a_whole() {
+        let differences = diff(json!({ "result": [1] }), json!({ "result": [1, 2] }));
+        assert_eq!(differences, vec!["response.result: expected [1], got [1,2]"]);
+    }
+
+    #[test]
+    fn values_of_time_dependent_fields_are_not_compared() {
+        let differences = diff(
+            json!({ "result": { "block_hash": "0x1", "timestamp": 1, "parent_hash": "0x0" } }),
+            json!({ "result": { "block_hash": "0x2", "timestamp": 2 } }),
+        );
+        assert_eq!(differences, vec!["response.result.parent_hash: missing"]);
+    }
+
+    #[test]
+    fn recorded_block_hashes_are_replaced_with_the_replayed_ones() {
+        let mut replaced_hashes = HashMap::new();
+        collect_replaced_hashes(
+            &json!({ "result": [{ "block_hash": "0x1", "transaction_hash": "0xa" }] }),
+            &json!({ "result": [{ "block_hash": "0x2", "transaction_hash": "0xa" }] }),
+            &mut replaced_hashes,
+        );
+
+        let mut request = json!({ "params": { "block_id": { "block_hash": "0x1" }, "x": "0xa" } });
+        replace_hashes(&mut request, &replaced_hashes);
+        assert_eq!(
+            request,
+            json!({ "params": { "block_id": { "block_hash": "0x2" }, "x": "0xa" } })
+        );
+    }
+}
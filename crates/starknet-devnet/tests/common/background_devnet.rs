@@ -44,7 +44,7 @@ pub struct BackgroundDevnet {
     rpc_url: Url,
 }
 
-fn get_free_port() -> Result<u16, TestError> {
+pub(crate) fn get_free_port() -> Result<u16, TestError> {
     for port in MIN_PORT..=MAX_PORT {
         if let Ok(listener) = TcpListener::bind(("127.0.0.1", port)) {
             return Ok(listener.local_addr().expect("No local addr").port());
@@ -152,13 +152,23 @@ impl BackgroundDevnet {
         path: String,
         body: hyper::Body,
     ) -> Result<Response<hyper::Body>, hyper::Error> {
-        let req = request::Request::builder()
+        self.post_json_with_headers(path, body, &[]).await
+    }
+
+    pub async fn post_json_with_headers(
+        &self,
+        path: String,
+        body: hyper::Body,
+        headers: &[(&str, &str)],
+    ) -> Result<Response<hyper::Body>, hyper::Error> {
+        let mut req_builder = request::Request::builder()
             .method("POST")
             .uri(format!("{}{}", self.url.as_str(), path))
-            .header("content-type", "application/json")
-            .body(body)
-            .unwrap();
-        self.http_client.request(req).await
+            .header("content-type", "application/json");
+        for (name, value) in headers {
+            req_builder = req_builder.header(*name, *value);
+        }
+        self.http_client.request(req_builder.body(body).unwrap()).await
     }
 
     pub async fn send_custom_rpc(
@@ -9,6 +9,10 @@ pub const ACCOUNTS: usize = 3;
 pub const CHAIN_ID: FieldElement = starknet_rs_core::chain_id::TESTNET;
 pub const CHAIN_ID_CLI_PARAM: &str = "TESTNET";
 
+// recipient and amount of the ETH transfers in tests
+pub const DUMMY_ADDRESS: u128 = 0x42;
+pub const DUMMY_AMOUNT: u128 = 123;
+
 // URL paths
 pub const RPC_PATH: &str = "/rpc";
 pub const HEALTHCHECK_PATH: &str = "/is_alive";
@@ -50,3 +54,7 @@ pub const MESSAGING_L1_CONTRACT_ADDRESS: &str = "0xe7f1725e7734ce288f8367e1bb143
 /// Cairo 1 account which panics on validation
 pub const INVALID_ACCOUNT_SIERRA_PATH: &str =
     "test_data/cairo1/invalid_account/invalid_account.sierra";
+
+/// Self-signed certificate of localhost and its key, for serving HTTPS
+pub const TLS_CERT_PATH: &str = "test_data/tls/cert.pem";
+pub const TLS_KEY_PATH: &str = "test_data/tls/key.pem";
@@ -5,13 +5,23 @@ use std::process::{Child, Command};
 
 use cairo_lang_starknet::casm_contract_class::CasmContractClass;
 use hyper::{Body, Response};
+use serde_json::json;
+use starknet_core::constants::ETH_ERC20_CONTRACT_ADDRESS;
 use starknet_core::random_number_generator::generate_u32_random_number;
+use starknet_rs_accounts::{Account, Call, ExecutionEncoding, SingleOwnerAccount};
 use starknet_rs_core::types::contract::SierraClass;
-use starknet_rs_core::types::{ExecutionResult, FieldElement, FlattenedSierraClass};
-use starknet_rs_providers::Provider;
+use starknet_rs_core::types::{
+    BroadcastedInvokeTransaction, ExecutionResult, FieldElement, FlattenedSierraClass,
+};
+use starknet_rs_core::utils::get_selector_from_name;
+use starknet_rs_providers::jsonrpc::HttpTransport;
+use starknet_rs_providers::{JsonRpcClient, Provider};
 use starknet_rs_signers::LocalWallet;
 use starknet_types::contract_class::compute_casm_class_hash;
 
+use super::background_devnet::BackgroundDevnet;
+use super::constants::{CHAIN_ID, DUMMY_ADDRESS, DUMMY_AMOUNT};
+
 pub async fn get_json_body(resp: Response<Body>) -> serde_json::Value {
     let resp_body = resp.into_body();
     let resp_body_bytes = hyper::body::to_bytes(resp_body).await.unwrap();
@@ -131,6 +141,74 @@ pub fn iter_to_hex_felt<T: LowerHex>(iterable: &[T]) -> Vec<String> {
     iterable.iter().map(to_hex_felt).collect()
 }
 
+pub async fn get_first_predeployed_single_owner_account(
+    devnet: &BackgroundDevnet,
+) -> SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet> {
+    let (signer, account_address) = devnet.get_first_predeployed_account().await;
+    SingleOwnerAccount::new(
+        devnet.clone_provider(),
+        signer,
+        account_address,
+        CHAIN_ID,
+        ExecutionEncoding::New,
+    )
+}
+
+/// The transfer of `DUMMY_AMOUNT` of ETH to `DUMMY_ADDRESS`
+pub fn dummy_transfer_call() -> Call {
+    Call {
+        to: FieldElement::from_hex_be(ETH_ERC20_CONTRACT_ADDRESS).unwrap(),
+        selector: get_selector_from_name("transfer").unwrap(),
+        calldata: vec![
+            FieldElement::from(DUMMY_ADDRESS),
+            FieldElement::from(DUMMY_AMOUNT),
+            FieldElement::ZERO,
+        ],
+    }
+}
+
+/// The `balanceOf` call of the ETH contract for the address, as a JSON-RPC param
+pub fn eth_balance_of_call(address: u128) -> serde_json::Value {
+    json!({
+        "contract_address": ETH_ERC20_CONTRACT_ADDRESS,
+        "entry_point_selector": to_hex_felt(&get_selector_from_name("balanceOf").unwrap()),
+        "calldata": [to_hex_felt(&FieldElement::from(address))],
+    })
+}
+
+/// Signs the invoke transaction V1 of the calls with the nonce and returns it as a JSON-RPC param,
+/// without sending it
+pub async fn signed_invoke_v1_json(
+    account: &SingleOwnerAccount<JsonRpcClient<HttpTransport>, LocalWallet>,
+    calls: Vec<Call>,
+    nonce: FieldElement,
+) -> serde_json::Value {
+    let max_fee = FieldElement::from(1e18 as u128);
+    let invoke_request = match account
+        .execute(calls)
+        .max_fee(max_fee)
+        .nonce(nonce)
+        .prepared()
+        .unwrap()
+        .get_invoke_request(false)
+        .await
+        .unwrap()
+    {
+        BroadcastedInvokeTransaction::V1(invoke_v1) => invoke_v1,
+        _ => panic!("wrong txn type"),
+    };
+
+    json!({
+        "type": "INVOKE",
+        "max_fee": to_hex_felt(&max_fee),
+        "version": "0x1",
+        "signature": iter_to_hex_felt(&invoke_request.signature),
+        "nonce": to_num_as_hex(&nonce),
+        "calldata": iter_to_hex_felt(&invoke_request.calldata),
+        "sender_address": to_hex_felt(&account.address()),
+    })
+}
+
 pub fn get_unix_timestamp_as_seconds() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -143,6 +221,17 @@ pub async fn send_ctrl_c_signal_and_wait(process: &Child) {
     std::thread::sleep(std::time::Duration::from_secs(1));
 }
 
+/// Sends SIGTERM, as e.g. `docker stop` does, and waits for the process to shut down
+#[cfg(unix)]
+pub async fn send_sigterm_signal_and_wait(process: &Child) {
+    let mut kill = Command::new("kill")
+        .args(["-s", "SIGTERM", process.id().to_string().as_str()])
+        .spawn()
+        .unwrap();
+    kill.wait().unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(1));
+}
+
 async fn send_ctrl_c_signal(process: &Child) {
     #[cfg(windows)]
     {
@@ -2,11 +2,51 @@
 pub mod common;
 
 mod general_integration_tests {
+    use hyper::StatusCode;
+    use serde_json::json;
+
     use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants::{ACCOUNTS, PREDEPLOYED_ACCOUNT_INITIAL_BALANCE, SEED};
+    use crate::common::utils::get_json_body;
 
     #[tokio::test]
     /// Asserts that a background instance can be spawned
     async fn spawnable() {
         BackgroundDevnet::spawn().await.expect("Could not start Devnet");
     }
+
+    #[tokio::test]
+    async fn config_is_exposed() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--gas-price",
+            "123",
+            "--state-archive-capacity",
+            "full",
+            "--api-key",
+            "secret",
+        ])
+        .await
+        .unwrap();
+
+        let resp = devnet.get("/config", None).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let config = get_json_body(resp).await;
+
+        assert_eq!(config["seed"], SEED);
+        assert_eq!(config["total_accounts"], ACCOUNTS);
+        assert_eq!(
+            config["predeployed_accounts_initial_balance"],
+            PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string()
+        );
+        assert!(config["account_contract_class_hash"].as_str().unwrap().starts_with("0x"));
+        assert_eq!(config["gas_price"], 123);
+        assert_eq!(config["chain_id"], "SN_GOERLI");
+        assert_eq!(config["dump_on"], json!(null));
+        assert_eq!(config["state_archive"], "full");
+        assert_eq!(config["timeout"], 120);
+        assert_eq!(config["server_config"]["allow_origin"], "*");
+        // the API key is not revealed
+        assert!(config["server_config"].get("api_key").is_none(), "{config}");
+        assert!(config.get("account_contract_class").is_none());
+    }
 }
@@ -4,7 +4,7 @@ pub mod common;
 mod general_rpc_tests {
     use hyper::Body;
     use serde_json::json;
-    use server::api::json_rpc::RPC_SPEC_VERSION;
+    use server::api::json_rpc::{RPC_SPEC_VERSION, RPC_SPEC_VERSION_0_5};
 
     use crate::common::background_devnet::BackgroundDevnet;
     use crate::common::constants::RPC_PATH;
@@ -36,6 +36,72 @@ mod general_rpc_tests {
         }
     }
 
+    #[tokio::test]
+    async fn rpc_discover_returns_openrpc_document() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let resp_body = devnet.send_custom_rpc("rpc.discover", json!([])).await;
+        let document = &resp_body["result"];
+        assert_eq!(document["info"]["version"], RPC_SPEC_VERSION);
+
+        let method_names: Vec<&str> = document["methods"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|method| method["name"].as_str().unwrap())
+            .collect();
+        for expected_method in
+            ["starknet_getEvents", "starknet_addInvokeTransaction", "devnet_mint"]
+        {
+            assert!(method_names.contains(&expected_method), "Missing {expected_method}");
+        }
+    }
+
+    #[tokio::test]
+    async fn rpc_discover_is_not_served_on_v0_5() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let resp = devnet
+            .post_json(
+                "/rpc/v0_5".into(),
+                Body::from(
+                    json!({ "jsonrpc": "2.0", "id": 0, "method": "rpc.discover" }).to_string(),
+                ),
+            )
+            .await
+            .unwrap();
+        let resp_body = get_json_body(resp).await;
+
+        assert_eq!(resp_body["error"]["code"], -32601, "{resp_body}");
+    }
+
+    #[tokio::test]
+    async fn versioned_rpc_routes_return_their_spec_version() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        for (path, expected_version) in
+            [("/rpc/v0_5", RPC_SPEC_VERSION_0_5), ("/rpc/v0_6", RPC_SPEC_VERSION)]
+        {
+            let resp = devnet
+                .post_json(
+                    path.into(),
+                    Body::from(
+                        json!({
+                            "jsonrpc": "2.0",
+                            "id": 0,
+                            "method": "starknet_specVersion",
+                        })
+                        .to_string(),
+                    ),
+                )
+                .await
+                .unwrap();
+            let resp_body = get_json_body(resp).await;
+
+            assert_eq!(resp_body["result"], json!(expected_version));
+        }
+    }
+
     #[tokio::test]
     async fn rpc_returns_method_not_found() {
         let devnet = BackgroundDevnet::spawn().await.unwrap();
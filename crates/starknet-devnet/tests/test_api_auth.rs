@@ -0,0 +1,116 @@
+// must use `pub`: https://github.com/rust-lang/rust/issues/46379#issuecomment-548787629
+pub mod common;
+
+mod test_api_auth {
+    use hyper::{Body, StatusCode};
+    use serde_json::json;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants::RPC_PATH;
+    use crate::common::utils::get_json_body;
+
+    const API_KEY: &str = "secret";
+    const AUTHORIZATION: (&str, &str) = ("authorization", "Bearer secret");
+    const UNAUTHORIZED_CODE: i64 = -32001;
+
+    fn rpc_body(method: &str, params: serde_json::Value) -> Body {
+        Body::from(
+            json!({ "jsonrpc": "2.0", "id": 0, "method": method, "params": params }).to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn control_endpoints_require_api_key() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--api-key", API_KEY]).await.unwrap();
+
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let resp = devnet
+            .post_json_with_headers(
+                "/create_block".into(),
+                Body::empty(),
+                &[("authorization", "Bearer wrong")],
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let resp = devnet
+            .post_json_with_headers("/create_block".into(), Body::empty(), &[AUTHORIZATION])
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp_body = devnet.send_custom_rpc("devnet_createBlock", json!([])).await;
+        assert_eq!(resp_body["error"]["code"], UNAUTHORIZED_CODE, "{resp_body}");
+
+        let resp = devnet
+            .post_json_with_headers(
+                RPC_PATH.into(),
+                rpc_body("devnet_createBlock", json!([])),
+                &[AUTHORIZATION],
+            )
+            .await
+            .unwrap();
+        let resp_body = get_json_body(resp).await;
+        assert!(resp_body.get("result").is_some(), "{resp_body}");
+
+        // public endpoints stay open
+        let resp_body = devnet.send_custom_rpc("starknet_blockNumber", json!([])).await;
+        assert_eq!(resp_body["result"], 1, "{resp_body}");
+        let resp = devnet.get("/predeployed_accounts", None).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn writes_require_api_key_if_configured() {
+        let invalid_params = json!({ "invalid_transaction": {} });
+
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--api-key", API_KEY]).await.unwrap();
+        let resp_body =
+            devnet.send_custom_rpc("starknet_addInvokeTransaction", invalid_params.clone()).await;
+        assert_ne!(resp_body["error"]["code"], UNAUTHORIZED_CODE, "{resp_body}");
+
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--api-key",
+            API_KEY,
+            "--require-api-key-for-writes",
+        ])
+        .await
+        .unwrap();
+        let resp_body =
+            devnet.send_custom_rpc("starknet_addInvokeTransaction", invalid_params.clone()).await;
+        assert_eq!(resp_body["error"]["code"], UNAUTHORIZED_CODE, "{resp_body}");
+
+        let resp = devnet
+            .post_json_with_headers(
+                RPC_PATH.into(),
+                rpc_body("starknet_addInvokeTransaction", invalid_params),
+                &[AUTHORIZATION],
+            )
+            .await
+            .unwrap();
+        let resp_body = get_json_body(resp).await;
+        assert_ne!(resp_body["error"]["code"], UNAUTHORIZED_CODE, "{resp_body}");
+    }
+
+    #[tokio::test]
+    async fn control_endpoints_are_not_served_if_disabled() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--disable-devnet-api"]).await.unwrap();
+
+        let resp = devnet.post_json("/create_block".into(), Body::empty()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp_body = devnet.send_custom_rpc("devnet_createBlock", json!([])).await;
+        assert_eq!(resp_body["error"]["code"], -32601, "{resp_body}");
+
+        let resp_body = devnet.send_custom_rpc("starknet_chainId", json!([])).await;
+        assert!(resp_body.get("result").is_some(), "{resp_body}");
+        let resp = devnet.get("/predeployed_accounts", None).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
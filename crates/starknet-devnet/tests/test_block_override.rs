@@ -0,0 +1,129 @@
+pub mod common;
+
+mod block_override_tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+    use starknet_rs_accounts::{Account, Call, ConnectedAccount};
+    use starknet_rs_contract::ContractFactory;
+    use starknet_rs_core::types::FieldElement;
+    use starknet_rs_core::utils::{
+        get_selector_from_name, get_udc_deployed_address, UdcUniqueness,
+    };
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::utils::{
+        dummy_transfer_call, get_first_predeployed_single_owner_account,
+        get_timestamp_contract_in_sierra_and_compiled_class_hash, signed_invoke_v1_json,
+        to_hex_felt,
+    };
+
+    const FUTURE_TIMESTAMP: u64 = 4_000_000_000;
+
+    #[tokio::test]
+    async fn simulation_at_future_timestamp() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let account = Arc::new(get_first_predeployed_single_owner_account(&devnet).await);
+
+        let (contract_class, casm_class_hash) =
+            get_timestamp_contract_in_sierra_and_compiled_class_hash();
+        let declaration_result = account
+            .declare(Arc::new(contract_class), casm_class_hash)
+            .max_fee(FieldElement::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+        ContractFactory::new(declaration_result.class_hash, account.clone())
+            .deploy(vec![], FieldElement::ZERO, false)
+            .max_fee(FieldElement::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+        let contract_address = get_udc_deployed_address(
+            FieldElement::ZERO,
+            declaration_result.class_hash,
+            &UdcUniqueness::NotUnique,
+            &[],
+        );
+
+        let get_timestamp_call = Call {
+            to: contract_address,
+            selector: get_selector_from_name("get_timestamp").unwrap(),
+            calldata: vec![],
+        };
+        let transaction = signed_invoke_v1_json(
+            &account,
+            vec![get_timestamp_call],
+            account.get_nonce().await.unwrap(),
+        )
+        .await;
+
+        let simulation = &devnet
+            .send_custom_rpc(
+                "starknet_simulateTransactions",
+                json!({
+                    "block_id": "latest",
+                    "simulation_flags": [],
+                    "transactions": [transaction],
+                    "block_override": { "timestamp": FUTURE_TIMESTAMP, "block_number": 1000 },
+                }),
+            )
+            .await["result"][0];
+        let get_timestamp_invocation =
+            &simulation["transaction_trace"]["execute_invocation"]["calls"][0];
+        assert_eq!(
+            get_timestamp_invocation["result"],
+            json!([to_hex_felt(&FieldElement::from(FUTURE_TIMESTAMP))]),
+            "{simulation}"
+        );
+
+        // the block context of Devnet is not changed
+        let latest_block = &devnet
+            .send_custom_rpc("starknet_getBlockWithTxHashes", json!({ "block_id": "latest" }))
+            .await["result"];
+        assert!(latest_block["timestamp"].as_u64().unwrap() < FUTURE_TIMESTAMP);
+        assert!(latest_block["block_number"].as_u64().unwrap() < 1000);
+    }
+
+    #[tokio::test]
+    async fn estimation_with_gas_price_override() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let account = get_first_predeployed_single_owner_account(&devnet).await;
+
+        let transaction =
+            signed_invoke_v1_json(&account, vec![dummy_transfer_call()], FieldElement::ZERO).await;
+
+        let estimate = |block_override: serde_json::Value| {
+            let devnet = &devnet;
+            let transaction = transaction.clone();
+            async move {
+                devnet
+                    .send_custom_rpc(
+                        "starknet_estimateFee",
+                        json!({
+                            "block_id": "latest",
+                            "simulation_flags": [],
+                            "request": [transaction],
+                            "block_override": block_override,
+                        }),
+                    )
+                    .await["result"][0]
+                    .clone()
+            }
+        };
+
+        let default_estimation = estimate(json!({})).await;
+        let default_gas_price =
+            FieldElement::from_hex_be(default_estimation["gas_price"].as_str().unwrap()).unwrap();
+        let doubled_gas_price = default_gas_price * FieldElement::TWO;
+
+        let estimation =
+            estimate(json!({ "eth_l1_gas_price": to_hex_felt(&doubled_gas_price) })).await;
+        assert_eq!(estimation["gas_price"], to_hex_felt(&doubled_gas_price));
+        assert_eq!(estimation["gas_consumed"], default_estimation["gas_consumed"]);
+
+        let default_fee =
+            FieldElement::from_hex_be(default_estimation["overall_fee"].as_str().unwrap()).unwrap();
+        assert_eq!(estimation["overall_fee"], to_hex_felt(&(default_fee * FieldElement::TWO)));
+    }
+}
@@ -1,14 +1,24 @@
 pub mod common;
 
 mod call {
+    use std::sync::Arc;
+
+    use serde_json::json;
     use starknet_core::constants::ETH_ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_accounts::{Account, ExecutionEncoding, SingleOwnerAccount};
+    use starknet_rs_contract::ContractFactory;
+    use starknet_rs_core::chain_id;
     use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, FunctionCall, StarknetError};
+    use starknet_rs_core::utils::{
+        get_selector_from_name, get_storage_var_address, get_udc_deployed_address, UdcUniqueness,
+    };
     use starknet_rs_providers::{Provider, ProviderError};
 
     use crate::common::background_devnet::BackgroundDevnet;
     use crate::common::constants::{
         PREDEPLOYED_ACCOUNT_ADDRESS, PREDEPLOYED_ACCOUNT_INITIAL_BALANCE,
     };
+    use crate::common::utils::get_events_contract_in_sierra_and_compiled_class_hash;
 
     #[tokio::test]
     /// This test doesn't rely on devnet.get_balance because it's not supposed to call ERC20
@@ -76,4 +86,137 @@ mod call {
         let expected_balance = FieldElement::from_hex_be(expected_hex_balance.as_str()).unwrap();
         assert_eq!(retrieved_result, expected_balance);
     }
+
+    #[tokio::test]
+    async fn tracing_call_returns_its_result_and_resources() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let account_address = FieldElement::from_hex_be(PREDEPLOYED_ACCOUNT_ADDRESS).unwrap();
+
+        let trace = &devnet
+            .send_custom_rpc(
+                "devnet_traceCall",
+                json!({
+                    "request": {
+                        "contract_address": ETH_ERC20_CONTRACT_ADDRESS,
+                        "entry_point_selector":
+                            format!("{:#x}", get_selector_from_name("balanceOf").unwrap()),
+                        "calldata": [PREDEPLOYED_ACCOUNT_ADDRESS]
+                    },
+                    "block_id": "latest"
+                }),
+            )
+            .await["result"];
+
+        let balance = devnet.get_balance(&account_address).await.unwrap();
+        assert_eq!(trace["result"], json!([format!("{balance:#x}"), "0x0"]));
+        assert_eq!(trace["call_type"], "CALL");
+        assert!(trace["execution_resources"]["steps"].as_u64().unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn tracing_call_returns_emitted_events() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        let account = Arc::new(SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer,
+            account_address,
+            chain_id::TESTNET,
+            ExecutionEncoding::New,
+        ));
+
+        let (contract_artifact, casm_hash) =
+            get_events_contract_in_sierra_and_compiled_class_hash();
+        let class_hash = contract_artifact.class_hash();
+        account
+            .declare(Arc::new(contract_artifact), casm_hash)
+            .max_fee(FieldElement::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+        ContractFactory::new(class_hash, account.clone())
+            .deploy(vec![], FieldElement::ZERO, false)
+            .max_fee(FieldElement::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+        let contract_address = get_udc_deployed_address(
+            FieldElement::ZERO,
+            class_hash,
+            &UdcUniqueness::NotUnique,
+            &[],
+        );
+        let nonce_before_call = devnet
+            .json_rpc_client
+            .get_nonce(BlockId::Tag(BlockTag::Latest), account_address)
+            .await
+            .unwrap();
+
+        let trace = &devnet
+            .send_custom_rpc(
+                "devnet_traceCall",
+                json!({
+                    "request": {
+                        "contract_address": format!("{contract_address:#x}"),
+                        "entry_point_selector":
+                            format!("{:#x}", get_selector_from_name("emit_event").unwrap()),
+                        // incremental, i.e. writing to the storage
+                        "calldata": ["0x1"]
+                    },
+                    "block_id": "latest"
+                }),
+            )
+            .await["result"];
+
+        assert_eq!(trace["contract_address"], format!("{contract_address:#x}"));
+        assert_eq!(trace["class_hash"], format!("{class_hash:#x}"));
+        assert_eq!(
+            trace["events"],
+            json!([{
+                "keys": [format!("{:#x}", get_selector_from_name("IncrementalEvent").unwrap())],
+                "data": ["0x0"],
+                "order": 0
+            }])
+        );
+
+        // the call doesn't change the state
+        let value = devnet
+            .json_rpc_client
+            .get_storage_at(
+                contract_address,
+                get_storage_var_address("value", &[]).unwrap(),
+                BlockId::Tag(BlockTag::Latest),
+            )
+            .await
+            .unwrap();
+        assert_eq!(value, FieldElement::ZERO);
+
+        let nonce = devnet
+            .json_rpc_client
+            .get_nonce(BlockId::Tag(BlockTag::Latest), account_address)
+            .await
+            .unwrap();
+        assert_eq!(nonce, nonce_before_call);
+    }
+
+    #[tokio::test]
+    async fn tracing_call_of_undeployed_contract() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let error = &devnet
+            .send_custom_rpc(
+                "devnet_traceCall",
+                json!({
+                    "request": {
+                        "contract_address": "0x1234",
+                        "entry_point_selector": "0x1",
+                        "calldata": []
+                    },
+                    "block_id": "latest"
+                }),
+            )
+            .await["error"];
+
+        assert_eq!(error["code"], 20);
+    }
 }
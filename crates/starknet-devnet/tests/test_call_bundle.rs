@@ -0,0 +1,66 @@
+pub mod common;
+
+mod call_bundle_tests {
+    use serde_json::json;
+    use starknet_rs_core::types::FieldElement;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::utils::{
+        dummy_transfer_call, eth_balance_of_call, get_first_predeployed_single_owner_account,
+        signed_invoke_v1_json, to_hex_felt,
+    };
+
+    #[tokio::test]
+    async fn calls_see_the_state_after_the_transactions() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--validate-rpc-schema"])
+            .await
+            .expect("Could not start Devnet");
+
+        let account = get_first_predeployed_single_owner_account(&devnet).await;
+        let transaction =
+            signed_invoke_v1_json(&account, vec![dummy_transfer_call()], FieldElement::ZERO).await;
+        let result = &devnet
+            .send_custom_rpc(
+                "devnet_callBundle",
+                json!({
+                    "transactions": [transaction],
+                    "calls": [eth_balance_of_call(DUMMY_ADDRESS), eth_balance_of_call(0x43)],
+                }),
+            )
+            .await["result"];
+
+        let traces = result["transaction_traces"].as_array().unwrap();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0]["type"], "INVOKE");
+        assert_eq!(
+            result["call_results"],
+            json!([[to_hex_felt(&FieldElement::from(DUMMY_AMOUNT)), "0x0"], ["0x0", "0x0"]])
+        );
+
+        // the transactions are not committed
+        let balance = devnet.get_balance(&FieldElement::from(DUMMY_ADDRESS)).await.unwrap();
+        assert_eq!(balance, FieldElement::ZERO);
+    }
+
+    #[tokio::test]
+    async fn call_of_undeployed_contract_fails_the_bundle() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let error = &devnet
+            .send_custom_rpc(
+                "devnet_callBundle",
+                json!({
+                    "block_id": "latest",
+                    "transactions": [],
+                    "calls": [{
+                        "contract_address": "0x1234",
+                        "entry_point_selector": "0x1",
+                        "calldata": [],
+                    }],
+                }),
+            )
+            .await["error"];
+        assert_eq!(error["code"], 20);
+    }
+}
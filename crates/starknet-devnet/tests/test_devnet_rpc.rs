@@ -0,0 +1,89 @@
+// must use `pub`: https://github.com/rust-lang/rust/issues/46379#issuecomment-548787629
+pub mod common;
+
+mod test_devnet_rpc {
+    use hyper::Body;
+    use serde_json::json;
+    use starknet_rs_core::types::{BlockId, BlockTag, FieldElement, MaybePendingBlockWithTxHashes};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants::RPC_PATH;
+    use crate::common::utils::get_json_body;
+
+    #[tokio::test]
+    async fn mint_over_json_rpc() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let address = FieldElement::ONE;
+
+        let resp_body = devnet
+            .send_custom_rpc(
+                "devnet_mint",
+                json!({ "address": format!("{address:#x}"), "amount": 100 }),
+            )
+            .await;
+        assert_eq!(resp_body["result"]["new_balance"], "100");
+        assert_eq!(resp_body["result"]["unit"], "WEI");
+
+        let balance = devnet.get_balance(&address).await.unwrap();
+        assert_eq!(balance, FieldElement::from(100_u128));
+    }
+
+    #[tokio::test]
+    async fn devnet_methods_in_batch_request() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let batch = json!([
+            { "jsonrpc": "2.0", "id": 0, "method": "devnet_setTime", "params": { "time": 1 } },
+            { "jsonrpc": "2.0", "id": 1, "method": "devnet_getPredeployedAccounts" },
+        ]);
+        let resp = devnet.post_json(RPC_PATH.into(), Body::from(batch.to_string())).await.unwrap();
+        let resp_body = get_json_body(resp).await;
+
+        let responses = resp_body.as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        for response in responses {
+            match response["id"].as_u64() {
+                Some(0) => assert_eq!(response["result"]["block_timestamp"], 1),
+                Some(1) => assert!(!response["result"].as_array().unwrap().is_empty()),
+                _ => panic!("Unexpected response: {response}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn create_block_and_restart_over_json_rpc() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let resp_body = devnet.send_custom_rpc("devnet_createBlock", json!({})).await;
+        let block_hash =
+            FieldElement::from_hex_be(resp_body["result"]["block_hash"].as_str().unwrap()).unwrap();
+
+        match devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap()
+        {
+            MaybePendingBlockWithTxHashes::Block(block) => assert_eq!(block.block_hash, block_hash),
+            other => panic!("Unexpected block: {other:?}"),
+        }
+
+        let resp_body = devnet.send_custom_rpc("devnet_restart", json!([])).await;
+        assert_eq!(resp_body["result"], json!(null));
+        assert!(devnet.json_rpc_client.block_number().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn devnet_method_errors_are_json_rpc_errors() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+
+        let resp_body =
+            devnet.send_custom_rpc("devnet_load", json!({ "path": "nonexistent_file" })).await;
+        assert_eq!(resp_body["error"]["code"], -1);
+        assert_eq!(resp_body["error"]["message"], "The file does not exist");
+
+        let resp_body = devnet.send_custom_rpc("devnet_mint", json!({ "address": "0x1" })).await;
+        assert_eq!(resp_body["error"]["code"], -32602);
+    }
+}
@@ -8,6 +8,8 @@ mod dump_and_load_tests {
     use starknet_rs_providers::Provider;
 
     use crate::common::background_devnet::BackgroundDevnet;
+    #[cfg(unix)]
+    use crate::common::utils::send_sigterm_signal_and_wait;
     use crate::common::utils::{send_ctrl_c_signal_and_wait, UniqueAutoDeletableFile};
 
     static DUMMY_ADDRESS: u128 = 1;
@@ -182,6 +184,31 @@ mod dump_and_load_tests {
         }
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn mint_dump_on_sigterm_and_load() {
+        let dump_file = UniqueAutoDeletableFile::new("dump_on_sigterm");
+        let devnet_dump = BackgroundDevnet::spawn_with_additional_args(&[
+            "--dump-path",
+            dump_file.path.as_str(),
+            "--dump-on",
+            "exit",
+        ])
+        .await
+        .expect("Could not start Devnet");
+        let mint_tx_hash = devnet_dump.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        send_sigterm_signal_and_wait(&devnet_dump.process).await;
+
+        let devnet_load =
+            BackgroundDevnet::spawn_with_additional_args(&["--dump-path", &dump_file.path])
+                .await
+                .expect("Could not start Devnet");
+        let loaded_transaction =
+            devnet_load.json_rpc_client.get_transaction_by_hash(mint_tx_hash).await.unwrap();
+        assert_eq!(loaded_transaction.transaction_hash(), &mint_tx_hash);
+    }
+
     #[tokio::test]
     async fn declare_deploy() {
         let dump_file = UniqueAutoDeletableFile::new("dump_declare_deploy");
@@ -420,6 +420,13 @@ mod estimate_fee_tests {
         let revert_error = result["error"]["data"]["revert_error"].as_str().unwrap();
 
         assert!(revert_error.contains(panic_reason));
+        assert!(
+            revert_error.starts_with(&format!(
+                "Panic reason: '{panic_reason}'\nPanic data: [{:#x}]\n",
+                cairo_short_string_to_felt(panic_reason).unwrap()
+            )),
+            "{revert_error}"
+        );
     }
 
     #[tokio::test]
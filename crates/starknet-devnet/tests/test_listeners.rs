@@ -0,0 +1,142 @@
+// must use `pub`: https://github.com/rust-lang/rust/issues/46379#issuecomment-548787629
+pub mod common;
+
+mod test_listeners {
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::process::{Child, Command, Stdio};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use hyper::{Body, Request, StatusCode};
+    use serde_json::json;
+    use tokio::io::{AsyncRead, AsyncWrite};
+    use tokio::net::TcpStream;
+    use tokio_rustls::rustls::{Certificate, ClientConfig, RootCertStore, ServerName};
+    use tokio_rustls::TlsConnector;
+
+    use crate::common::background_devnet::get_free_port;
+    use crate::common::constants::{RPC_PATH, TLS_CERT_PATH, TLS_KEY_PATH};
+    #[cfg(unix)]
+    use crate::common::utils::send_sigterm_signal_and_wait;
+    use crate::common::utils::{get_json_body, UniqueAutoDeletableFile};
+
+    /// Devnet process that is killed when dropped
+    struct DevnetProcess(Child);
+
+    impl DevnetProcess {
+        fn spawn(args: &[&str]) -> Self {
+            let process = Command::new("cargo")
+                .args(["run", "--release", "--"])
+                .args(args)
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("Could not start background devnet");
+            Self(process)
+        }
+    }
+
+    impl Drop for DevnetProcess {
+        fn drop(&mut self) {
+            self.0.kill().expect("Cannot kill process");
+        }
+    }
+
+    /// Sends a JSON-RPC request over an established connection and returns the response body
+    async fn send_rpc_over<T>(stream: T) -> serde_json::Value
+    where
+        T: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        let (mut sender, connection) = hyper::client::conn::handshake(stream).await.unwrap();
+        tokio::spawn(connection);
+
+        let request = Request::post(RPC_PATH)
+            .header("content-type", "application/json")
+            .header("host", "localhost")
+            .body(Body::from(
+                json!({ "jsonrpc": "2.0", "id": 0, "method": "starknet_chainId" }).to_string(),
+            ))
+            .unwrap();
+        let response = sender.send_request(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        get_json_body(response).await
+    }
+
+    /// Retries `connect` until devnet accepts connections
+    async fn connect_with_retries<T, F, Fut>(connect: F) -> T
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = std::io::Result<T>>,
+    {
+        for _ in 0..30 {
+            if let Ok(stream) = connect().await {
+                return stream;
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+
+        panic!("Could not connect to devnet");
+    }
+
+    fn tls_connector() -> TlsConnector {
+        let mut root_certs = RootCertStore::empty();
+        let certs = rustls_pemfile::certs(&mut BufReader::new(File::open(TLS_CERT_PATH).unwrap()))
+            .unwrap();
+        for cert in certs {
+            root_certs.add(&Certificate(cert)).unwrap();
+        }
+
+        let config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(root_certs)
+            .with_no_client_auth();
+        TlsConnector::from(Arc::new(config))
+    }
+
+    #[tokio::test]
+    async fn serving_over_tls() {
+        let port = get_free_port().unwrap().to_string();
+        let _devnet = DevnetProcess::spawn(&[
+            "--port",
+            &port,
+            "--tls-cert",
+            TLS_CERT_PATH,
+            "--tls-key",
+            TLS_KEY_PATH,
+        ]);
+
+        let address = format!("127.0.0.1:{port}");
+        let stream = connect_with_retries(|| TcpStream::connect(&address)).await;
+        let tls_stream = tls_connector()
+            .connect(ServerName::try_from("localhost").unwrap(), stream)
+            .await
+            .unwrap();
+
+        let resp_body = send_rpc_over(tls_stream).await;
+        assert!(resp_body.get("result").is_some(), "Invalid resp: {resp_body}");
+
+        // plain HTTP is not served
+        let stream = TcpStream::connect(&address).await.unwrap();
+        let (mut sender, connection) = hyper::client::conn::handshake(stream).await.unwrap();
+        tokio::spawn(connection);
+        let request = Request::get("/is_alive").body(Body::empty()).unwrap();
+        assert!(sender.send_request(request).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn serving_over_unix_socket() {
+        let socket_file = UniqueAutoDeletableFile::new("devnet.sock");
+        let devnet = DevnetProcess::spawn(&["--unix-socket", &socket_file.path]);
+
+        let stream =
+            connect_with_retries(|| tokio::net::UnixStream::connect(&socket_file.path)).await;
+        let resp_body = send_rpc_over(stream).await;
+        assert!(resp_body.get("result").is_some(), "Invalid resp: {resp_body}");
+
+        // the socket file is removed on shutdown
+        send_sigterm_signal_and_wait(&devnet.0).await;
+        assert!(!std::path::Path::new(&socket_file.path).exists());
+    }
+}
@@ -0,0 +1,146 @@
+pub mod common;
+
+mod mempool_tests {
+    use serde_json::json;
+    use starknet_rs_accounts::{Account, ExecutionEncoding, SingleOwnerAccount};
+    use starknet_rs_core::types::{FieldElement, TransactionExecutionStatus, TransactionStatus};
+    use starknet_rs_providers::Provider;
+    use starknet_rs_signers::{LocalWallet, SigningKey};
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants::{CHAIN_ID, DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::utils::{
+        dummy_transfer_call, get_first_predeployed_single_owner_account, to_hex_felt,
+    };
+
+    #[tokio::test]
+    async fn transaction_with_future_nonce_waits_for_the_missing_nonce() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--validate-rpc-schema"])
+            .await
+            .expect("Could not start Devnet");
+
+        let account = get_first_predeployed_single_owner_account(&devnet).await;
+        let account_address = account.address();
+
+        let transfer = |nonce: FieldElement| {
+            account
+                .execute(vec![dummy_transfer_call()])
+                .nonce(nonce)
+                .max_fee(FieldElement::from(1e18 as u128))
+        };
+
+        let future_transaction = transfer(FieldElement::ONE).send().await.unwrap();
+        let status = devnet
+            .json_rpc_client
+            .get_transaction_status(future_transaction.transaction_hash)
+            .await
+            .unwrap();
+        assert_eq!(status, TransactionStatus::Received);
+
+        let mempool = &devnet.send_custom_rpc("devnet_getMempool", json!({})).await["result"];
+        assert_eq!(
+            mempool,
+            &json!([{
+                "transaction_hash": to_hex_felt(&future_transaction.transaction_hash),
+                "type": "INVOKE",
+                "sender_address": to_hex_felt(&account_address),
+                "nonce": "0x1",
+            }])
+        );
+
+        // the gap is filled, so both transactions are executed
+        transfer(FieldElement::ZERO).send().await.unwrap();
+        let status = devnet
+            .json_rpc_client
+            .get_transaction_status(future_transaction.transaction_hash)
+            .await
+            .unwrap();
+        assert_eq!(status, TransactionStatus::AcceptedOnL2(TransactionExecutionStatus::Succeeded));
+
+        let balance = devnet.get_balance(&FieldElement::from(DUMMY_ADDRESS)).await.unwrap();
+        assert_eq!(balance, FieldElement::from(2 * DUMMY_AMOUNT));
+
+        let mempool = &devnet.send_custom_rpc("devnet_getMempool", json!({})).await["result"];
+        assert_eq!(mempool, &json!([]));
+    }
+
+    #[tokio::test]
+    async fn transaction_whose_nonce_is_passed_in_the_mempool_is_rejected() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let account = get_first_predeployed_single_owner_account(&devnet).await;
+
+        let transfer = |nonce: FieldElement, max_fee: u128| {
+            account
+                .execute(vec![dummy_transfer_call()])
+                .nonce(nonce)
+                .max_fee(FieldElement::from(max_fee))
+        };
+
+        // both wait for nonce 0, but only the first received can be executed
+        let first_transaction = transfer(FieldElement::ONE, 1e18 as u128).send().await.unwrap();
+        let second_transaction = transfer(FieldElement::ONE, 2e18 as u128).send().await.unwrap();
+        transfer(FieldElement::ZERO, 1e18 as u128).send().await.unwrap();
+
+        let status = devnet
+            .json_rpc_client
+            .get_transaction_status(first_transaction.transaction_hash)
+            .await
+            .unwrap();
+        assert_eq!(status, TransactionStatus::AcceptedOnL2(TransactionExecutionStatus::Succeeded));
+
+        let status = devnet
+            .json_rpc_client
+            .get_transaction_status(second_transaction.transaction_hash)
+            .await
+            .unwrap();
+        assert_eq!(status, TransactionStatus::Rejected);
+
+        let balance = devnet.get_balance(&FieldElement::from(DUMMY_ADDRESS)).await.unwrap();
+        assert_eq!(balance, FieldElement::from(2 * DUMMY_AMOUNT));
+
+        let mempool = &devnet.send_custom_rpc("devnet_getMempool", json!({})).await["result"];
+        assert_eq!(mempool, &json!([]));
+    }
+
+    #[tokio::test]
+    async fn transaction_failing_validation_in_the_mempool_is_rejected() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let account = get_first_predeployed_single_owner_account(&devnet).await;
+
+        // signed with a key which isn't the key of the account
+        let impostor = SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            LocalWallet::from(SigningKey::from_secret_scalar(FieldElement::ONE)),
+            account.address(),
+            CHAIN_ID,
+            ExecutionEncoding::New,
+        );
+        let invalid_transaction = impostor
+            .execute(vec![dummy_transfer_call()])
+            .nonce(FieldElement::ONE)
+            .max_fee(FieldElement::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+
+        account
+            .execute(vec![dummy_transfer_call()])
+            .nonce(FieldElement::ZERO)
+            .max_fee(FieldElement::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+
+        let status = &devnet
+            .send_custom_rpc(
+                "starknet_getTransactionStatus",
+                json!({ "transaction_hash": to_hex_felt(&invalid_transaction.transaction_hash) }),
+            )
+            .await["result"];
+        assert_eq!(status["finality_status"], "REJECTED");
+        assert!(status["failure_reason"].is_string());
+
+        let mempool = &devnet.send_custom_rpc("devnet_getMempool", json!({})).await["result"];
+        assert_eq!(mempool, &json!([]));
+    }
+}
@@ -0,0 +1,64 @@
+// must use `pub`: https://github.com/rust-lang/rust/issues/46379#issuecomment-548787629
+pub mod common;
+
+mod test_metrics {
+    use hyper::StatusCode;
+    use serde_json::json;
+    use starknet_rs_core::types::FieldElement;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::utils::get_string_body;
+
+    /// Returns the value of the sample with the given name and labels, e.g.
+    /// `devnet_blocks_produced_total` or `devnet_transactions_total{status="accepted"}`
+    fn sample_value(metrics: &str, sample: &str) -> Option<f64> {
+        metrics.lines().find_map(|line| {
+            let value = line.strip_prefix(sample)?.strip_prefix(' ')?;
+            value.parse().ok()
+        })
+    }
+
+    #[tokio::test]
+    async fn metrics_are_exposed_in_prometheus_format() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        devnet.mint(FieldElement::ONE, 100).await;
+        devnet.send_custom_rpc("starknet_blockNumber", json!([])).await;
+        devnet.send_custom_rpc("starknet_getNonce", json!({})).await;
+        devnet.send_custom_rpc("nonexistent_method", json!([])).await;
+
+        let resp = devnet.get("/metrics", None).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers()["content-type"].to_str().unwrap().starts_with("text/plain"));
+        let metrics = get_string_body(resp).await;
+
+        for (sample, expected_value) in [
+            (r#"devnet_rpc_requests_total{method="starknet_blockNumber",outcome="success"}"#, 1.0),
+            (r#"devnet_rpc_requests_total{method="starknet_getNonce",outcome="error"}"#, 1.0),
+            (r#"devnet_rpc_requests_total{method="unknown",outcome="error"}"#, 1.0),
+            (r#"devnet_rpc_request_duration_seconds_count{method="starknet_blockNumber"}"#, 1.0),
+            (r#"devnet_transactions_total{status="accepted"}"#, 1.0),
+            ("devnet_blocks_produced_total", 1.0),
+            (r#"devnet_execution_duration_seconds_count{kind="transaction"}"#, 1.0),
+        ] {
+            assert_eq!(sample_value(&metrics, sample), Some(expected_value), "{sample}\n{metrics}");
+        }
+
+        assert!(sample_value(&metrics, "devnet_state_storage_entries").unwrap() > 0.0);
+        assert_eq!(sample_value(&metrics, "devnet_state_archive_bytes"), Some(0.0));
+    }
+
+    #[tokio::test]
+    async fn state_archive_is_measured() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--state-archive-capacity",
+            "full",
+        ])
+        .await
+        .unwrap();
+        devnet.mint(FieldElement::ONE, 100).await;
+
+        let resp = devnet.get("/metrics", None).await.unwrap();
+        let metrics = get_string_body(resp).await;
+        assert!(sample_value(&metrics, "devnet_state_archive_bytes").unwrap() > 0.0, "{metrics}");
+    }
+}
@@ -0,0 +1,65 @@
+// must use `pub`: https://github.com/rust-lang/rust/issues/46379#issuecomment-548787629
+pub mod common;
+
+mod test_pending_block {
+    use starknet_rs_core::types::{
+        BlockId, BlockTag, FieldElement, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs,
+    };
+    use starknet_rs_providers::Provider;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+
+    #[tokio::test]
+    async fn pending_block_is_returned_without_hash_and_number() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        devnet.mint(FieldElement::ONE, 100).await;
+
+        let latest_block = match devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Latest))
+            .await
+            .unwrap()
+        {
+            MaybePendingBlockWithTxHashes::Block(block) => block,
+            other => panic!("Unexpected block: {other:?}"),
+        };
+
+        match devnet
+            .json_rpc_client
+            .get_block_with_tx_hashes(BlockId::Tag(BlockTag::Pending))
+            .await
+            .unwrap()
+        {
+            MaybePendingBlockWithTxHashes::PendingBlock(pending_block) => {
+                assert_eq!(pending_block.parent_hash, latest_block.block_hash);
+                assert!(pending_block.transactions.is_empty());
+            }
+            other => panic!("Unexpected block: {other:?}"),
+        }
+
+        match devnet
+            .json_rpc_client
+            .get_block_with_txs(BlockId::Tag(BlockTag::Pending))
+            .await
+            .unwrap()
+        {
+            MaybePendingBlockWithTxs::PendingBlock(pending_block) => {
+                assert_eq!(pending_block.parent_hash, latest_block.block_hash);
+            }
+            other => panic!("Unexpected block: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn storage_can_be_read_at_pending_block() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let (_, account_address) = devnet.get_first_predeployed_account().await;
+
+        let nonce = devnet
+            .json_rpc_client
+            .get_nonce(BlockId::Tag(BlockTag::Pending), account_address)
+            .await
+            .unwrap();
+        assert_eq!(nonce, FieldElement::ZERO);
+    }
+}
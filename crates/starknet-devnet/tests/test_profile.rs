@@ -0,0 +1,125 @@
+pub mod common;
+
+mod profile_tests {
+    use serde_json::json;
+    use starknet_core::constants::{CHARGEABLE_ACCOUNT_ADDRESS, ETH_ERC20_CONTRACT_ADDRESS};
+    use starknet_rs_core::types::FieldElement;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants::{DUMMY_ADDRESS, DUMMY_AMOUNT};
+    use crate::common::utils::{
+        dummy_transfer_call, get_first_predeployed_single_owner_account, signed_invoke_v1_json,
+    };
+
+    /// The address as it is serialized by Devnet
+    fn normalized(address: &str) -> String {
+        format!("{:#x}", FieldElement::from_hex_be(address).unwrap())
+    }
+
+    fn find_entry_point<'a>(
+        profile: &'a serde_json::Value,
+        entry_point: &str,
+    ) -> &'a serde_json::Value {
+        profile["entry_points"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|profile| profile["entry_point"] == entry_point)
+            .unwrap_or_else(|| panic!("No {entry_point} in {profile}"))
+    }
+
+    #[tokio::test]
+    async fn profile_of_mint_transaction() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let mint_tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let profile = &devnet
+            .send_custom_rpc(
+                "devnet_profileTransaction",
+                json!({ "transaction_hash": format!("{mint_tx_hash:#x}") }),
+            )
+            .await["result"];
+
+        let erc20_address = normalized(ETH_ERC20_CONTRACT_ADDRESS);
+        let account_address = normalized(CHARGEABLE_ACCOUNT_ADDRESS);
+
+        let mint_profile = find_entry_point(profile, "mint");
+        assert_eq!(mint_profile["contract_address"], erc20_address);
+        assert_eq!(mint_profile["calls"], 1);
+        assert!(mint_profile["steps"].as_u64().unwrap() > 0);
+        assert_eq!(mint_profile["traced_syscalls"]["emit_event"], 1);
+
+        let execute_profile = find_entry_point(profile, "__execute__");
+        assert_eq!(execute_profile["contract_address"], account_address);
+        assert_eq!(execute_profile["traced_syscalls"]["call_contract"], 1);
+
+        let mint_stack = format!(
+            "execute;{account_address}::__execute__;{erc20_address}::mint {}",
+            mint_profile["steps"]
+        );
+        let folded_stacks = profile["folded_stacks"].as_str().unwrap();
+        assert!(folded_stacks.lines().any(|line| line == mint_stack), "{folded_stacks}");
+        assert!(folded_stacks.lines().any(|line| line.starts_with("validate;")), "{folded_stacks}");
+    }
+
+    #[tokio::test]
+    async fn profile_of_unknown_transaction() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let error = &devnet
+            .send_custom_rpc("devnet_profileTransaction", json!({ "transaction_hash": "0x1" }))
+            .await["error"];
+        assert_eq!(error["code"], 29);
+    }
+
+    async fn simulate_transfer(devnet: &BackgroundDevnet) -> serde_json::Value {
+        let account = get_first_predeployed_single_owner_account(devnet).await;
+        let transaction =
+            signed_invoke_v1_json(&account, vec![dummy_transfer_call()], FieldElement::ZERO).await;
+
+        devnet
+            .send_custom_rpc(
+                "starknet_simulateTransactions",
+                json!({
+                    "block_id": "latest",
+                    "simulation_flags": [],
+                    "transactions": [transaction],
+                }),
+            )
+            .await["result"][0]
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn simulation_is_profiled_if_enabled() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--profile"])
+            .await
+            .expect("Could not start Devnet");
+
+        let simulation = simulate_transfer(&devnet).await;
+
+        // the transfer of the account and the transfer of the fee
+        let profile = &simulation["profile"];
+        let erc20_address = normalized(ETH_ERC20_CONTRACT_ADDRESS);
+        let transfer_profile = find_entry_point(profile, "transfer");
+        assert_eq!(transfer_profile["calls"], 2);
+        assert_eq!(transfer_profile["contract_address"], erc20_address);
+
+        let fee_transfer_stack = format!("fee_transfer;{erc20_address}::transfer ");
+        let folded_stacks = profile["folded_stacks"].as_str().unwrap();
+        assert!(
+            folded_stacks.lines().any(|line| line.starts_with(&fee_transfer_stack)),
+            "{folded_stacks}"
+        );
+    }
+
+    #[tokio::test]
+    async fn simulation_is_not_profiled_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let simulation = simulate_transfer(&devnet).await;
+        assert!(simulation["transaction_trace"].is_object());
+        assert!(simulation.get("profile").is_none());
+    }
+}
@@ -0,0 +1,96 @@
+// must use `pub`: https://github.com/rust-lang/rust/issues/46379#issuecomment-548787629
+pub mod common;
+
+mod test_recording {
+    use std::process::Command;
+
+    use hyper::Body;
+    use serde_json::json;
+    use server::recorder::RecordedExchange;
+
+    use crate::common::background_devnet::{get_free_port, BackgroundDevnet};
+    use crate::common::constants::{
+        CHAIN_ID_CLI_PARAM, PREDEPLOYED_ACCOUNT_INITIAL_BALANCE, RPC_PATH, SEED,
+    };
+    use crate::common::utils::{get_json_body, UniqueAutoDeletableFile};
+
+    fn read_recording(path: &str) -> Vec<RecordedExchange> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn requests_and_responses_are_recorded_in_order() {
+        let recording_file = UniqueAutoDeletableFile::new("recording");
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--record", &recording_file.path])
+                .await
+                .unwrap();
+
+        let rpc_response = devnet.send_custom_rpc("starknet_blockNumber", json!([])).await;
+        devnet.get("/predeployed_accounts", None).await.unwrap();
+
+        let exchanges = read_recording(&recording_file.path);
+        let recorded_paths: Vec<&str> =
+            exchanges.iter().map(|exchange| exchange.path.as_str()).collect();
+        // the healthchecks sent while devnet was starting are recorded as well
+        assert_eq!(recorded_paths[recorded_paths.len() - 2..], [RPC_PATH, "/predeployed_accounts"]);
+
+        let rpc_exchange = &exchanges[exchanges.len() - 2];
+        assert_eq!(rpc_exchange.method, "POST");
+        assert_eq!(rpc_exchange.request["method"], "starknet_blockNumber");
+        assert_eq!(rpc_exchange.status, 200);
+        assert_eq!(rpc_exchange.response, rpc_response);
+
+        let http_exchange = &exchanges[exchanges.len() - 1];
+        assert_eq!(http_exchange.method, "GET");
+        assert_eq!(http_exchange.request, serde_json::Value::Null);
+        assert!(http_exchange.response.as_array().is_some());
+    }
+
+    #[tokio::test]
+    async fn recording_with_blocks_is_replayed_without_differences() {
+        let recording_file = UniqueAutoDeletableFile::new("recording_with_blocks");
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&[
+            "--record",
+            &recording_file.path,
+            "--accounts",
+            "1",
+        ])
+        .await
+        .unwrap();
+
+        devnet.mint(0x1, 1000).await;
+        let created_block = devnet
+            .post_json("/create_block".into(), Body::from(json!({}).to_string()))
+            .await
+            .unwrap();
+        let block_hash = get_json_body(created_block).await["block_hash"].clone();
+
+        // block hashes depend on the time of execution, so they are replaced when replaying
+        let block = devnet
+            .send_custom_rpc(
+                "starknet_getBlockWithTxHashes",
+                json!({ "block_id": { "block_hash": block_hash } }),
+            )
+            .await;
+        assert!(block.get("result").is_some(), "Invalid resp: {block}");
+        drop(devnet);
+
+        // the same options as the recording instance, which was started with the default ones
+        let output = Command::new("cargo")
+            .args(["run", "--release", "--", "--seed", &SEED.to_string(), "--accounts", "1"])
+            .args(["--initial-balance", &PREDEPLOYED_ACCOUNT_INITIAL_BALANCE.to_string()])
+            .args(["--chain-id", CHAIN_ID_CLI_PARAM])
+            .args(["--port", &get_free_port().unwrap().to_string()])
+            .args(["replay", &recording_file.path])
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{stdout}");
+        assert!(stdout.contains(", 0 responses differ from the recording"), "{stdout}");
+    }
+}
@@ -0,0 +1,89 @@
+// must use `pub`: https://github.com/rust-lang/rust/issues/46379#issuecomment-548787629
+pub mod common;
+
+mod test_rpc_schema_validation {
+    use serde_json::json;
+    use starknet_rs_core::types::{BlockId, BlockTag, EventFilter, FieldElement};
+    use starknet_rs_providers::Provider;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+
+    #[tokio::test]
+    async fn responses_of_devnet_match_the_spec() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--validate-rpc-schema"]).await.unwrap();
+        let tx_hash = devnet.mint(FieldElement::ONE, 100).await;
+        let (_, account_address) = devnet.get_first_predeployed_account().await;
+
+        let client = &devnet.json_rpc_client;
+        let latest = BlockId::Tag(BlockTag::Latest);
+        let pending = BlockId::Tag(BlockTag::Pending);
+
+        client.block_number().await.unwrap();
+        client.block_hash_and_number().await.unwrap();
+        client.chain_id().await.unwrap();
+        client.get_block_with_tx_hashes(latest).await.unwrap();
+        client.get_block_with_txs(latest).await.unwrap();
+        client.get_block_with_tx_hashes(pending).await.unwrap();
+        client.get_block_with_txs(pending).await.unwrap();
+        client.get_state_update(latest).await.unwrap();
+        client.get_transaction_by_hash(tx_hash).await.unwrap();
+        client.get_transaction_receipt(tx_hash).await.unwrap();
+        client.get_transaction_status(tx_hash).await.unwrap();
+        client.get_class_at(latest, account_address).await.unwrap();
+        client.get_class_hash_at(latest, account_address).await.unwrap();
+        client.get_nonce(latest, account_address).await.unwrap();
+        client.trace_transaction(tx_hash).await.unwrap();
+        client
+            .get_events(
+                EventFilter { from_block: None, to_block: None, address: None, keys: None },
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+
+        for method in ["starknet_specVersion", "devnet_getPredeployedAccounts"] {
+            let resp_body = devnet.send_custom_rpc(method, json!([])).await;
+            assert!(resp_body.get("result").is_some(), "Invalid resp: {resp_body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_not_matching_the_spec_are_rejected() {
+        let devnet =
+            BackgroundDevnet::spawn_with_additional_args(&["--validate-rpc-schema"]).await.unwrap();
+        let (_, account_address) = devnet.get_first_predeployed_account().await;
+        let padded_address = format!("{account_address:#066x}");
+
+        let resp_body = devnet
+            .send_custom_rpc(
+                "starknet_getStorageAt",
+                // leading zeros are not allowed by the spec in addresses, but required in keys
+                json!({ "contract_address": padded_address, "key": "0x00", "block_id": "latest" }),
+            )
+            .await;
+        assert_eq!(resp_body["error"]["code"], -32602);
+        let message = resp_body["error"]["message"].as_str().unwrap();
+        assert!(
+            message.starts_with("Request does not match the spec: params.contract_address"),
+            "{message}"
+        );
+    }
+
+    #[tokio::test]
+    async fn requests_are_not_validated_by_default() {
+        let devnet = BackgroundDevnet::spawn().await.unwrap();
+        let (_, account_address) = devnet.get_first_predeployed_account().await;
+        let padded_address = format!("{account_address:#066x}");
+
+        let resp_body = devnet
+            .send_custom_rpc(
+                "starknet_getStorageAt",
+                // leading zeros are not allowed by the spec
+                json!({ "contract_address": padded_address, "key": "0x0", "block_id": "latest" }),
+            )
+            .await;
+        assert!(resp_body.get("result").is_some(), "Invalid resp: {resp_body}");
+    }
+}
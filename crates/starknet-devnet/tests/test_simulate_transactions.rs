@@ -19,10 +19,11 @@ mod estimate_fee_tests {
     use starknet_rs_signers::Signer;
 
     use crate::common::background_devnet::BackgroundDevnet;
-    use crate::common::constants::{CAIRO_1_CONTRACT_PATH, CHAIN_ID};
+    use crate::common::constants::{CAIRO_1_CONTRACT_PATH, CHAIN_ID, DUMMY_ADDRESS};
     use crate::common::utils::{
-        get_deployable_account_signer, get_flattened_sierra_contract_and_casm_hash,
-        iter_to_hex_felt, to_hex_felt, to_num_as_hex,
+        dummy_transfer_call, eth_balance_of_call, get_deployable_account_signer,
+        get_first_predeployed_single_owner_account, get_flattened_sierra_contract_and_casm_hash,
+        iter_to_hex_felt, signed_invoke_v1_json, to_hex_felt, to_num_as_hex,
     };
 
     fn extract_overall_fee(simulation_result: &serde_json::Value) -> u128 {
@@ -428,4 +429,65 @@ mod estimate_fee_tests {
             max_fee == FieldElement::ZERO,
         );
     }
+
+    #[tokio::test]
+    async fn parallel_simulations_and_reads_are_consistent() {
+        let devnet = Arc::new(BackgroundDevnet::spawn().await.expect("Could not start Devnet"));
+
+        let account = get_first_predeployed_single_owner_account(&devnet).await;
+        let transaction =
+            signed_invoke_v1_json(&account, vec![dummy_transfer_call()], FieldElement::ZERO).await;
+        let simulation_params =
+            json!({ "block_id": "latest", "simulation_flags": [], "transactions": [transaction] });
+        let call_params =
+            json!({ "request": eth_balance_of_call(DUMMY_ADDRESS), "block_id": "latest" });
+
+        // the entries of state diffs are not ordered, so only the other parts are compared
+        let simulation_without_state_diff = |mut response: serde_json::Value| {
+            response["result"][0]["transaction_trace"]
+                .as_object_mut()
+                .and_then(|trace| trace.remove("state_diff"))
+                .expect("No state diff in the simulation");
+            response["result"].clone()
+        };
+
+        let expected_simulation = simulation_without_state_diff(
+            devnet
+                .send_custom_rpc("starknet_simulateTransactions", simulation_params.clone())
+                .await,
+        );
+        let expected_call_result =
+            devnet.send_custom_rpc("starknet_call", call_params.clone()).await["result"].clone();
+        assert_eq!(expected_call_result, json!(["0x0", "0x0"]));
+
+        // simulations don't change the state, so reads in between see the same balance
+        let mut requests = tokio::task::JoinSet::new();
+        for _ in 0..20 {
+            let devnet_for_simulation = devnet.clone();
+            let simulation_params = simulation_params.clone();
+            let expected_simulation = expected_simulation.clone();
+            requests.spawn(async move {
+                let simulation = simulation_without_state_diff(
+                    devnet_for_simulation
+                        .send_custom_rpc("starknet_simulateTransactions", simulation_params)
+                        .await,
+                );
+                assert_eq!(simulation, expected_simulation);
+            });
+
+            let devnet_for_call = devnet.clone();
+            let call_params = call_params.clone();
+            let expected_call_result = expected_call_result.clone();
+            requests.spawn(async move {
+                let call_result =
+                    devnet_for_call.send_custom_rpc("starknet_call", call_params).await["result"]
+                        .clone();
+                assert_eq!(call_result, expected_call_result);
+            });
+        }
+
+        while let Some(request) = requests.join_next().await {
+            request.unwrap();
+        }
+    }
 }
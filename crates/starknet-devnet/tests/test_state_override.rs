@@ -0,0 +1,140 @@
+pub mod common;
+
+mod state_override_tests {
+    use serde_json::json;
+    use starknet_core::constants::ETH_ERC20_CONTRACT_ADDRESS;
+    use starknet_rs_accounts::Account;
+    use starknet_rs_core::types::FieldElement;
+
+    use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants::DUMMY_ADDRESS;
+    use crate::common::utils::{
+        dummy_transfer_call, eth_balance_of_call, get_first_predeployed_single_owner_account,
+        signed_invoke_v1_json, to_hex_felt, to_num_as_hex,
+    };
+
+    #[tokio::test]
+    async fn call_with_balance_override() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let result = &devnet
+            .send_custom_rpc(
+                "starknet_call",
+                json!({
+                    "request": eth_balance_of_call(DUMMY_ADDRESS),
+                    "block_id": "latest",
+                    "state_override": {
+                        to_hex_felt(&FieldElement::from(DUMMY_ADDRESS)): {
+                            "eth_balance": "0x200000000000000000000000000000001"
+                        }
+                    }
+                }),
+            )
+            .await["result"];
+        assert_eq!(result, &json!(["0x1", "0x2"]));
+
+        // the override is not committed
+        let balance = devnet.get_balance(&FieldElement::from(DUMMY_ADDRESS)).await.unwrap();
+        assert_eq!(balance, FieldElement::ZERO);
+    }
+
+    #[tokio::test]
+    async fn call_with_undeclared_class_hash_override() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let error = &devnet
+            .send_custom_rpc(
+                "starknet_call",
+                json!({
+                    "request": eth_balance_of_call(DUMMY_ADDRESS),
+                    "block_id": "latest",
+                    "state_override": { "0x1234": { "class_hash": "0x1" } }
+                }),
+            )
+            .await["error"];
+        assert_eq!(error["code"], 28);
+    }
+
+    #[tokio::test]
+    async fn simulation_and_estimation_with_nonce_override() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let account = get_first_predeployed_single_owner_account(&devnet).await;
+        let account_address = account.address();
+
+        // the account has not sent any transactions, so this nonce is only valid with the override
+        let nonce = FieldElement::from(5_u32);
+        let transaction = signed_invoke_v1_json(&account, vec![dummy_transfer_call()], nonce).await;
+        let state_override =
+            json!({ to_hex_felt(&account_address): { "nonce": to_num_as_hex(&nonce) } });
+
+        let simulation_without_override = devnet
+            .send_custom_rpc(
+                "starknet_simulateTransactions",
+                json!({
+                    "block_id": "latest",
+                    "simulation_flags": [],
+                    "transactions": [transaction],
+                }),
+            )
+            .await;
+        assert!(simulation_without_override["error"].is_object(), "{simulation_without_override}");
+
+        let simulation = &devnet
+            .send_custom_rpc(
+                "starknet_simulateTransactions",
+                json!({
+                    "block_id": "latest",
+                    "simulation_flags": [],
+                    "transactions": [transaction],
+                    "state_override": state_override,
+                }),
+            )
+            .await["result"][0];
+        assert!(simulation["transaction_trace"]["execute_invocation"].is_object(), "{simulation}");
+        assert!(simulation["fee_estimation"]["overall_fee"].is_string(), "{simulation}");
+
+        let estimation = &devnet
+            .send_custom_rpc(
+                "starknet_estimateFee",
+                json!({
+                    "block_id": "latest",
+                    "simulation_flags": [],
+                    "request": [transaction],
+                    "state_override": state_override,
+                }),
+            )
+            .await["result"][0];
+        assert_eq!(estimation, &simulation["fee_estimation"]);
+
+        // the override is not committed
+        let nonce = &devnet
+            .send_custom_rpc(
+                "starknet_getNonce",
+                json!({ "block_id": "latest", "contract_address": to_hex_felt(&account_address) }),
+            )
+            .await["result"];
+        assert_eq!(nonce, "0x0");
+    }
+
+    #[tokio::test]
+    async fn state_override_matches_the_spec() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--validate-rpc-schema"])
+            .await
+            .expect("Could not start Devnet");
+
+        let result = &devnet
+            .send_custom_rpc(
+                "starknet_call",
+                json!({
+                    "request": eth_balance_of_call(DUMMY_ADDRESS),
+                    "block_id": "latest",
+                    "state_override": {
+                        to_hex_felt(&FieldElement::from(DUMMY_ADDRESS)): { "strk_balance": "0x1" },
+                        ETH_ERC20_CONTRACT_ADDRESS: { "storage": { "0x1": "0x2" } }
+                    }
+                }),
+            )
+            .await["result"];
+        assert_eq!(result, &json!(["0x0", "0x0"]));
+    }
+}
@@ -9,16 +9,23 @@ mod trace_tests {
         ETH_ERC20_CONTRACT_ADDRESS,
     };
     use starknet_rs_accounts::{
-        Account, AccountFactory, ExecutionEncoding, OpenZeppelinAccountFactory, SingleOwnerAccount,
+        Account, AccountFactory, Call, ExecutionEncoding, OpenZeppelinAccountFactory,
+        SingleOwnerAccount,
     };
+    use starknet_rs_contract::ContractFactory;
     use starknet_rs_core::chain_id;
     use starknet_rs_core::types::{FieldElement, FunctionInvocation, StarknetError};
+    use starknet_rs_core::utils::{
+        cairo_short_string_to_felt, get_selector_from_name, get_udc_deployed_address, UdcUniqueness,
+    };
     use starknet_rs_providers::{Provider, ProviderError};
     use starknet_types::rpc::transactions::BlockTransactionTrace;
 
     use crate::common::background_devnet::BackgroundDevnet;
+    use crate::common::constants::CAIRO_1_PANICKING_CONTRACT_SIERRA_PATH;
     use crate::common::utils::{
         get_deployable_account_signer, get_events_contract_in_sierra_and_compiled_class_hash,
+        get_flattened_sierra_contract_and_casm_hash,
     };
 
     static DUMMY_ADDRESS: u128 = 1;
@@ -240,4 +247,110 @@ mod trace_tests {
             FieldElement::from_hex_be(ETH_ERC20_CONTRACT_ADDRESS).unwrap()
         );
     }
+
+    #[tokio::test]
+    async fn reverted_invoke_has_decoded_panic_reason() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+        let (signer, account_address) = devnet.get_first_predeployed_account().await;
+        let account = Arc::new(SingleOwnerAccount::new(
+            devnet.clone_provider(),
+            signer,
+            account_address,
+            chain_id::TESTNET,
+            ExecutionEncoding::New,
+        ));
+
+        let (contract_artifact, casm_hash) =
+            get_flattened_sierra_contract_and_casm_hash(CAIRO_1_PANICKING_CONTRACT_SIERRA_PATH);
+        let class_hash = contract_artifact.class_hash();
+        account.declare(Arc::new(contract_artifact), casm_hash).send().await.unwrap();
+
+        let salt = FieldElement::ZERO;
+        ContractFactory::new(class_hash, account.clone())
+            .deploy(vec![], salt, false)
+            .send()
+            .await
+            .unwrap();
+        let contract_address =
+            get_udc_deployed_address(salt, class_hash, &UdcUniqueness::NotUnique, &[]);
+
+        // the fee is provided, since estimating it would fail
+        let panic_reason = cairo_short_string_to_felt("Insufficient balance").unwrap();
+        let invoke_result = account
+            .execute(vec![Call {
+                to: contract_address,
+                selector: get_selector_from_name("create_panic").unwrap(),
+                calldata: vec![panic_reason],
+            }])
+            .max_fee(FieldElement::from(1e18 as u128))
+            .send()
+            .await
+            .unwrap();
+
+        let expected_prefix =
+            format!("Panic reason: 'Insufficient balance'\nPanic data: [{panic_reason:#x}]\n");
+
+        let receipt = devnet
+            .send_custom_rpc(
+                "starknet_getTransactionReceipt",
+                json!({ "transaction_hash": format!("{:#x}", invoke_result.transaction_hash) }),
+            )
+            .await;
+        let revert_reason = receipt["result"]["revert_reason"].as_str().unwrap();
+        assert!(revert_reason.starts_with(&expected_prefix), "{revert_reason}");
+
+        let trace = devnet
+            .send_custom_rpc(
+                "starknet_traceTransaction",
+                json!({ "transaction_hash": format!("{:#x}", invoke_result.transaction_hash) }),
+            )
+            .await;
+        let revert_reason =
+            trace["result"]["execute_invocation"]["revert_reason"].as_str().unwrap();
+        assert!(revert_reason.starts_with(&expected_prefix), "{revert_reason}");
+    }
+
+    #[tokio::test]
+    async fn get_decoded_invoke_trace() {
+        let devnet = BackgroundDevnet::spawn().await.expect("Could not start Devnet");
+
+        let mint_tx_hash = devnet.mint(DUMMY_ADDRESS, DUMMY_AMOUNT).await;
+
+        let trace = &devnet
+            .send_custom_rpc(
+                "devnet_traceTransactionDecoded",
+                json!({ "transaction_hash": format!("{mint_tx_hash:#x}") }),
+            )
+            .await["result"];
+
+        let execute_invocation = &trace["execute_invocation"];
+        assert_eq!(execute_invocation["decoded"]["function"], "__execute__");
+
+        let mint_invocation = &execute_invocation["calls"][0];
+        assert_eq!(
+            mint_invocation["decoded"],
+            json!({
+                "function": "mint",
+                "calldata": {
+                    "recipient": format!("{DUMMY_ADDRESS:#x}"),
+                    "amount": DUMMY_AMOUNT.to_string()
+                },
+                "result": []
+            })
+        );
+        assert_eq!(
+            mint_invocation["events"][0]["decoded"],
+            json!({
+                "name": "openzeppelin::token::erc20::erc20::ERC20Component::Transfer",
+                "fields": {
+                    "from": "0x0",
+                    "to": format!("{DUMMY_ADDRESS:#x}"),
+                    "value": DUMMY_AMOUNT.to_string()
+                }
+            })
+        );
+
+        // the raw values are kept next to the decoded ones
+        assert_eq!(mint_invocation["calldata"][0], format!("{DUMMY_ADDRESS:#x}"));
+    }
 }
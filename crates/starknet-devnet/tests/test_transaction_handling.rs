@@ -3,14 +3,18 @@ pub mod common;
 mod trace_tests {
     use std::sync::Arc;
 
+    use serde_json::json;
     use starknet_core::utils::exported_test_utils::dummy_cairo_0_contract_class;
     use starknet_rs_accounts::{Account, AccountError, ExecutionEncoding, SingleOwnerAccount};
     use starknet_rs_core::types::contract::legacy::LegacyContractClass;
-    use starknet_rs_core::types::{FieldElement, StarknetError};
-    use starknet_rs_providers::ProviderError;
+    use starknet_rs_core::types::{FieldElement, StarknetError, TransactionStatus};
+    use starknet_rs_providers::{Provider, ProviderError};
 
     use crate::common::background_devnet::BackgroundDevnet;
     use crate::common::constants::{CHAIN_ID, INVALID_ACCOUNT_SIERRA_PATH};
+    use crate::common::utils::{
+        dummy_transfer_call, get_first_predeployed_single_owner_account, to_hex_felt,
+    };
 
     #[tokio::test]
     async fn test_failed_validation_with_expected_message() {
@@ -52,4 +56,40 @@ mod trace_tests {
             other => panic!("Unexpected result: {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn rejected_transaction_has_rejected_status() {
+        let devnet = BackgroundDevnet::spawn_with_additional_args(&["--validate-rpc-schema"])
+            .await
+            .expect("Could not start Devnet");
+
+        let account = get_first_predeployed_single_owner_account(&devnet).await;
+
+        // the max fee exceeds the balance of the account
+        let execution = account
+            .execute(vec![dummy_transfer_call()])
+            .nonce(FieldElement::ZERO)
+            .max_fee(FieldElement::from(1e30 as u128))
+            .prepared()
+            .unwrap();
+        let transaction_hash = execution.transaction_hash(false);
+        execution.send().await.unwrap_err();
+
+        let status = devnet.json_rpc_client.get_transaction_status(transaction_hash).await.unwrap();
+        assert_eq!(status, TransactionStatus::Rejected);
+
+        let status = &devnet
+            .send_custom_rpc(
+                "starknet_getTransactionStatus",
+                json!({ "transaction_hash": to_hex_felt(&transaction_hash) }),
+            )
+            .await["result"];
+        assert_eq!(
+            status,
+            &json!({
+                "finality_status": "REJECTED",
+                "failure_reason": "Account balance is not enough to cover the transaction cost.",
+            })
+        );
+    }
 }